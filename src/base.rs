@@ -1,4 +1,8 @@
 use peroxide::fuga::*;
+use crate::rolling::{rolling_sum, rolling_max, rolling_min, rolling_rank, previous_percent_rank};
+use crate::error::{QuantError, check_nonempty, check_window, check_len};
+use crate::price::{typical_price, typical_price_checked, ohlc4};
+pub use crate::price::median_price;
 
 // =============================================================================
 // High Level Structure
@@ -8,9 +12,12 @@ use peroxide::fuga::*;
 /// # Arguments
 /// * `period` - usize
 /// * `amplitude` - f64
+/// * `upper_amplitude`, `lower_amplitude` - Option<f64> (override `amplitude` for one band)
 pub struct BollingerBand {
     pub period: usize,
     pub amplitude: f64,
+    pub upper_amplitude: Option<f64>,
+    pub lower_amplitude: Option<f64>,
     pub ubb: Vec<f64>,
     pub mbb: Vec<f64>,
     pub lbb: Vec<f64>,
@@ -21,12 +28,57 @@ impl BollingerBand {
         BollingerBand {
             period,
             amplitude,
+            upper_amplitude: None,
+            lower_amplitude: None,
             ubb: vec![],
             mbb: vec![],
             lbb: vec![],
         }
     }
 
+    /// Bollinger Band with separate multipliers for the upper and lower band
+    ///
+    /// # Description
+    /// `amplitude` is set to the mean of the two, the half-width of the band in std units.
+    ///
+    /// # Examples
+    /// ```
+    /// use quantauri::base::BollingerBand;
+    ///
+    /// fn main() {
+    ///     let v = vec![1f64, 3f64, 1f64, 3f64, 1f64, 3f64];
+    ///     // std = 1 over every full window of 2
+    ///     let mut bb = BollingerBand::new_asymmetric(2, 2f64, 1f64);
+    ///     bb.bb_mut(&v);
+    ///     assert_eq!((bb.ubb[5], bb.mbb[5], bb.lbb[5]), (4f64, 2f64, 1f64));
+    ///
+    ///     // %B and bandwidth use the asymmetric bands
+    ///     assert_eq!(bb.per_b(&v)[5], (3f64 - 1f64) / 3f64);
+    ///     assert_eq!(bb.bw(&v)[5], 3f64 / 2f64);
+    ///
+    ///     // Same multipliers: identical to `new`
+    ///     assert_eq!(BollingerBand::new_asymmetric(2, 2f64, 2f64).bb(&v), BollingerBand::new(2, 2f64).bb(&v));
+    /// }
+    /// ```
+    pub fn new_asymmetric(period: usize, upper_amplitude: f64, lower_amplitude: f64) -> Self {
+        BollingerBand {
+            amplitude: (upper_amplitude + lower_amplitude) / 2f64,
+            upper_amplitude: Some(upper_amplitude),
+            lower_amplitude: Some(lower_amplitude),
+            ..Self::new(period, upper_amplitude)
+        }
+    }
+
+    /// Multiplier of the upper band (`amplitude` unless overridden)
+    pub fn upper(&self) -> f64 {
+        self.upper_amplitude.unwrap_or(self.amplitude)
+    }
+
+    /// Multiplier of the lower band (`amplitude` unless overridden)
+    pub fn lower(&self) -> f64 {
+        self.lower_amplitude.unwrap_or(self.amplitude)
+    }
+
     pub fn get_ubb(&self) -> &Vec<f64> {
         &self.ubb
     }
@@ -46,22 +98,34 @@ impl BollingerBand {
     ///
     /// # Returns
     /// * (Vec<f64>, Vec<f64>)
-    ///  * (ubb, lbb)
-    ///  * ubb = sma + amplitude * mstd
+    ///  * (ubb, mbb, lbb)
+    ///  * ubb = sma + upper() * mstd
     ///  * mbb = sma
-    ///  * lbb = sma - amplitude * mstd
+    ///  * lbb = sma - lower() * mstd
     pub fn bb(&self, v: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
         let sma = sma(v, self.period);
         let mstd = mstd(v, self.period, &sma);
-        let (ubb, lbb) = bollinger_band(v, self.amplitude, &sma, &mstd);
+        let (ubb, lbb) = bollinger_band_asymmetric(v, self.upper(), self.lower(), &sma, &mstd);
         (ubb, sma, lbb)
     }
 
     pub fn bb_mut(&mut self, v: &[f64]) {
-        let (ubb, mbb, lbb) = self.bb(v);
+        self.bb_mut_checked(v).expect("bb_mut: invalid input")
+    }
+
+    /// Calculate and cache the bands (fallible)
+    ///
+    /// # Errors
+    /// * `InvalidPeriod` if `period == 0`
+    /// * `WindowTooLarge` if `period > v.len()`
+    pub fn bb_mut_checked(&mut self, v: &[f64]) -> Result<(), QuantError> {
+        let sma = sma_checked(v, self.period)?;
+        let mstd = mstd_checked(v, self.period, &sma)?;
+        let (ubb, lbb) = bollinger_band_asymmetric_checked(v, self.upper(), self.lower(), &sma, &mstd)?;
         self.ubb = ubb;
-        self.mbb = mbb;
+        self.mbb = sma;
         self.lbb = lbb;
+        Ok(())
     }
 
     /// Calculate Percentage Band
@@ -72,27 +136,17 @@ impl BollingerBand {
     /// # Returns
     /// * Vec<f64>
     ///   * (v - lbb) / (ubb - lbb)
+    ///
+    /// # Description
+    /// Uses the cached bands from `bb_mut` only when they match the length of `v`,
+    /// otherwise the bands are recomputed from `v`.
     pub fn per_b(&self, v: &[f64]) -> Vec<f64> {
-        let mut result = vec![0f64; v.len()];
-        let ubb = self.get_ubb();
-        let lbb = self.get_lbb();
-        if ubb.len() > 0 {
-            for i in 0 .. v.len() {
-                let u = ubb[i];
-                let l = lbb[i];
-                if u == l {
-                    result[i] = (v[i] - l) / (u - l + 1e-3);
-                } else {
-                    result[i] = (v[i] - lbb[i]) / (ubb[i] - lbb[i]);
-                }
-            }
+        if self.ubb.len() == v.len() {
+            percent_b(v, self.get_ubb(), self.get_lbb())
         } else {
             let (ubb, _, lbb) = self.bb(v);
-            for i in 0 .. v.len() {
-                result[i] = (v[i] - lbb[i]) / (ubb[i] - lbb[i]);
-            }
+            percent_b(v, &ubb, &lbb)
         }
-        result
     }
 
     /// Calculate Band Width
@@ -103,22 +157,176 @@ impl BollingerBand {
     /// # Returns
     /// * Vec<f64>
     ///  * (ubb - lbb) / mbb
+    ///
+    /// # Description
+    /// Uses the cached bands from `bb_mut` only when they match the length of `v`,
+    /// otherwise the bands are recomputed from `v`.
     pub fn bw(&self, v: &[f64]) -> Vec<f64> {
-        let mut result = vec![0f64; v.len()];
-        let ubb = self.get_ubb();
-        let mbb = self.get_mbb();
-        let lbb = self.get_lbb();
-        if self.ubb.len() > 0 {
-            for i in 0 .. v.len() {
-                result[i] = (ubb[i] - lbb[i]) / mbb[i];
-            }
+        if self.ubb.len() == v.len() {
+            bandwidth(self.get_ubb(), self.get_mbb(), self.get_lbb())
         } else {
             let (ubb, mbb, lbb) = self.bb(v);
-            for i in 0 .. v.len() {
-                result[i] = (ubb[i] - lbb[i]) / mbb[i];
-            }
+            bandwidth(&ubb, &mbb, &lbb)
         }
-        result
+    }
+
+    /// Percent-rank of the Band Width in its own history
+    ///
+    /// # Arguments
+    /// * `v` - &[f64]
+    /// * `lookback` - usize
+    ///
+    /// # Returns
+    /// * Vec<f64>
+    ///   * `rolling_rank(bw, lookback)`: 0 when the bands are the tightest of the trailing
+    ///     `lookback` bars (current bar included), 100 when they are the widest
+    ///   * The first `period - 1` bandwidths come from truncated windows, so they are NAN
+    ///     and left out of later ranks
+    ///
+    /// # Description
+    /// A value near 0 is the classic squeeze setup: volatility is at a local low and tends
+    /// to expand from there. Unlike raw `bw`, the rank is comparable across instruments.
+    ///
+    /// # Examples
+    /// ```
+    /// use quantauri::base::BollingerBand;
+    ///
+    /// fn main() {
+    ///     // Swings that shrink, then widen again
+    ///     let close = (0 .. 120).map(|x| {
+    ///         let amp = if x < 80 { 5f64 - x as f64 / 20f64 } else { 1f64 + (x - 80) as f64 / 5f64 };
+    ///         100f64 + amp * (x as f64 * 0.7).sin()
+    ///     }).collect::<Vec<f64>>();
+    ///     let bb = BollingerBand::new(20, 2f64);
+    ///     let p = bb.bandwidth_percentile(&close, 60);
+    ///     assert!(p[18].is_nan());
+    ///     assert!(p[80] < 10f64);  // squeeze
+    ///     assert!(p[119] > 90f64); // expansion
+    /// }
+    /// ```
+    pub fn bandwidth_percentile(&self, v: &[f64], lookback: usize) -> Vec<f64> {
+        let mut width = self.bw(v);
+        let warmup = self.period.saturating_sub(1).min(width.len());
+        width[.. warmup].iter_mut().for_each(|x| *x = f64::NAN);
+        rolling_rank(&width, lookback)
+    }
+
+    /// Bollinger Band Squeeze
+    ///
+    /// # Arguments
+    /// * `keltner` - &KeltnerChannel
+    ///
+    /// # Returns
+    /// * Vec<bool>
+    ///   * `true` where lbb > keltner lower and ubb < keltner upper (TTM squeeze)
+    ///
+    /// # Description
+    /// Compares the cached bands of `bb_mut` and `KeltnerChannel::kc_mut` bar by bar,
+    /// so both must have been computed on the same series. The periods may differ:
+    /// the first `max(period, keltner.period) - 1` bars, where either side is still
+    /// warming up, are `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// use quantauri::base::{BollingerBand, KeltnerChannel};
+    ///
+    /// fn main() {
+    ///     let close = (0 .. 60).map(|x| 100f64 + (x as f64 * 0.3).sin()).collect::<Vec<f64>>();
+    ///     let high = close.iter().map(|x| x + 1f64).collect::<Vec<f64>>();
+    ///     let low = close.iter().map(|x| x - 1f64).collect::<Vec<f64>>();
+    ///     let mut bb = BollingerBand::new(20, 2f64);
+    ///     bb.bb_mut(&close);
+    ///     let mut kc = KeltnerChannel::new(20, 1.5);
+    ///     kc.kc_mut(&high, &low, &close);
+    ///     let sq = bb.squeeze(&kc);
+    ///     assert!(!sq[18]);
+    ///     // Wide bars, tight closes: Bollinger sits inside Keltner
+    ///     assert!(sq[19 ..].iter().all(|&x| x));
+    ///
+    ///     // Different lengths are an error
+    ///     kc.kc_mut(&high[.. 50], &low[.. 50], &close[.. 50]);
+    ///     assert!(bb.squeeze_checked(&kc).is_err());
+    /// }
+    /// ```
+    pub fn squeeze(&self, keltner: &KeltnerChannel) -> Vec<bool> {
+        self.squeeze_checked(keltner).expect("squeeze: bands have different lengths")
+    }
+
+    /// Bollinger Band Squeeze (fallible)
+    ///
+    /// # Errors
+    /// * `LengthMismatch` if the cached Bollinger and Keltner bands differ in length
+    pub fn squeeze_checked(&self, keltner: &KeltnerChannel) -> Result<Vec<bool>, QuantError> {
+        check_len(self.ubb.len(), keltner.upper.len())?;
+        let warmup = self.period.max(keltner.period).saturating_sub(1);
+        Ok((0 .. self.ubb.len()).map(|i| {
+            i >= warmup && self.lbb[i] > keltner.lower[i] && self.ubb[i] < keltner.upper[i]
+        }).collect())
+    }
+}
+
+/// Keltner Channel
+///
+/// # Arguments
+/// * `period` - usize (EMA and ATR period, default: 20)
+/// * `multiplier` - f64 (ATR multiple, default: 1.5 for the TTM squeeze, 2 classically)
+pub struct KeltnerChannel {
+    pub period: usize,
+    pub multiplier: f64,
+    pub upper: Vec<f64>,
+    pub middle: Vec<f64>,
+    pub lower: Vec<f64>,
+}
+
+impl KeltnerChannel {
+    pub fn new(period: usize, multiplier: f64) -> Self {
+        KeltnerChannel {
+            period,
+            multiplier,
+            upper: vec![],
+            middle: vec![],
+            lower: vec![],
+        }
+    }
+
+    pub fn get_upper(&self) -> &Vec<f64> {
+        &self.upper
+    }
+
+    pub fn get_middle(&self) -> &Vec<f64> {
+        &self.middle
+    }
+
+    pub fn get_lower(&self) -> &Vec<f64> {
+        &self.lower
+    }
+
+    /// Calculate Keltner Channel
+    ///
+    /// # Arguments
+    /// * `high` - &[f64]
+    /// * `low` - &[f64]
+    /// * `close` - &[f64]
+    ///
+    /// # Returns
+    /// * (Vec<f64>, Vec<f64>, Vec<f64>)
+    ///  * (upper, middle, lower)
+    ///  * middle = ema(close, period)
+    ///  * upper = middle + multiplier * atr(period)
+    ///  * lower = middle - multiplier * atr(period)
+    pub fn kc(&self, high: &[f64], low: &[f64], close: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let middle = ema(close, self.period);
+        let range = atr(high, low, close, self.period);
+        let upper = (0 .. close.len()).map(|i| middle[i] + self.multiplier * range[i]).collect();
+        let lower = (0 .. close.len()).map(|i| middle[i] - self.multiplier * range[i]).collect();
+        (upper, middle, lower)
+    }
+
+    pub fn kc_mut(&mut self, high: &[f64], low: &[f64], close: &[f64]) {
+        let (upper, middle, lower) = self.kc(high, low, close);
+        self.upper = upper;
+        self.middle = middle;
+        self.lower = lower;
     }
 }
 
@@ -134,10 +342,14 @@ impl BollingerBand {
 /// # Returns
 /// * Vec<f64>
 ///
+/// # Caution
+/// * The recurrence carries a NAN forward: every value after the first NAN input is NAN
+///   (see `sma_skipna`)
+///
 /// # Examples
 /// ```
 /// use peroxide::fuga::*;
-/// use quantauri::algorithm::sma;
+/// use quantauri::base::sma;
 ///
 /// fn main() {
 ///     let v = seq(1, 10, 1);
@@ -146,6 +358,29 @@ impl BollingerBand {
 /// }
 /// ```
 pub fn sma(v: &[f64], window_size: usize) -> Vec<f64> {
+    sma_checked(v, window_size).expect("sma: invalid input")
+}
+
+/// Simple Moving Average (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `window_size == 0`
+/// * `WindowTooLarge` if `window_size > v.len()`
+///
+/// # Examples
+/// ```
+/// use quantauri::base::sma_checked;
+/// use quantauri::error::QuantError;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 3f64];
+///     assert_eq!(sma_checked(&v, 2), Ok(vec![1f64, 1.5, 2.5]));
+///     assert_eq!(sma_checked(&v, 4), Err(QuantError::WindowTooLarge { window: 4, len: 3 }));
+///     assert_eq!(sma_checked(&v, 0), Err(QuantError::InvalidPeriod));
+/// }
+/// ```
+pub fn sma_checked(v: &[f64], window_size: usize) -> Result<Vec<f64>, QuantError> {
+    check_window(window_size, v.len())?;
     let mut result = vec![0f64; v.len()];
     for i in 0 .. window_size {
         result[i] = (0 .. i+1).map(|x| v[x]).sum::<f64>() / (i+1) as f64;
@@ -153,7 +388,7 @@ pub fn sma(v: &[f64], window_size: usize) -> Vec<f64> {
     for i in window_size .. v.len() {
         result[i] = result[i-1] + (v[i] - v[i-window_size]) / window_size as f64;
     }
-    result
+    Ok(result)
 }
 
 /// Exponential Moving Average
@@ -165,10 +400,14 @@ pub fn sma(v: &[f64], window_size: usize) -> Vec<f64> {
 /// # Returns
 /// * Vec<f64>
 ///
+/// # Caution
+/// * The recurrence carries a NAN forward: every value after the first NAN input is NAN
+///   (see `ema_skipna`)
+///
 /// # Examples
 /// ```
 /// use peroxide::fuga::*;
-/// use quantauri::algorithm::ema;
+/// use quantauri::base::ema;
 /// fn main() {
 ///    let v = seq(1, 10, 1);
 ///    let ema = ema(&v, 12);
@@ -176,13 +415,106 @@ pub fn sma(v: &[f64], window_size: usize) -> Vec<f64> {
 /// }
 /// ```
 pub fn ema(v: &[f64], window_size: usize) -> Vec<f64> {
+    ema_checked(v, window_size).expect("ema: invalid input")
+}
+
+/// Exponential Moving Average (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+/// * `InvalidPeriod` if `window_size == 0`
+pub fn ema_checked(v: &[f64], window_size: usize) -> Result<Vec<f64>, QuantError> {
+    if window_size == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    ema_alpha_checked(v, 2f64 / (window_size as f64 + 1f64))
+}
+
+/// Exponential Moving Average with an explicit smoothing factor
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `alpha` - f64 in (0, 1]
+///
+/// # Returns
+/// * Vec<f64>
+///   * ema[i] = alpha * v[i] + (1 - alpha) * ema[i-1], ema[0] = v[0]
+///   * `ema(v, w)` is `ema_alpha(v, 2 / (w + 1))`, `wilder_ema(v, p)` is `ema_alpha(v, 1 / p)`
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{ema, ema_alpha};
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 4f64, 8f64];
+///     let e = ema_alpha(&v, 0.5);
+///     assert_eq!(e, vec![1f64, 1.5, 2.75, 5.375]);
+///     assert_eq!(ema_alpha(&v, 2f64 / 4f64), ema(&v, 3));
+///     // alpha = 1 is the series itself
+///     assert_eq!(ema_alpha(&v, 1f64), v);
+/// }
+/// ```
+pub fn ema_alpha(v: &[f64], alpha: f64) -> Vec<f64> {
+    ema_alpha_checked(v, alpha).expect("ema_alpha: invalid input")
+}
+
+/// Exponential Moving Average with an explicit smoothing factor (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+/// * `InvalidParameter` if `alpha` is not in (0, 1]
+pub fn ema_alpha_checked(v: &[f64], alpha: f64) -> Result<Vec<f64>, QuantError> {
+    check_nonempty(v)?;
+    if alpha.is_nan() || alpha <= 0f64 || alpha > 1f64 {
+        return Err(QuantError::InvalidParameter(format!("alpha {} is not in (0, 1]", alpha)));
+    }
     let mut result = vec![0f64; v.len()];
-    let alpha = 2f64 / (window_size as f64 + 1f64);
     result[0] = v[0];
     for i in 1 .. v.len() {
         result[i] = alpha * v[i] + (1f64 - alpha) * result[i-1];
     }
-    result
+    Ok(result)
+}
+
+/// Wilder's Exponential Moving Average
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * `ema_alpha(v, 1 / period)`, the smoothing of `rsi` and `atr` (via `rma`)
+///   * Equivalent to `ema(v, 2 * period - 1)`
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{wilder_ema, ema, rma};
+///
+/// fn main() {
+///     let v = vec![1f64, 3f64, 2f64, 5f64, 4f64];
+///     let w = wilder_ema(&v, 3);
+///     assert_eq!(w, rma(&v, 3));
+///     let e = ema(&v, 5);
+///     for i in 0 .. 5 {
+///         assert!((w[i] - e[i]).abs() < 1e-12);
+///     }
+/// }
+/// ```
+pub fn wilder_ema(v: &[f64], period: usize) -> Vec<f64> {
+    wilder_ema_checked(v, period).expect("wilder_ema: invalid input")
+}
+
+/// Wilder's Exponential Moving Average (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+/// * `InvalidPeriod` if `period == 0`
+pub fn wilder_ema_checked(v: &[f64], period: usize) -> Result<Vec<f64>, QuantError> {
+    if period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    ema_alpha_checked(v, 1f64 / period as f64)
 }
 
 // Moving Standard Deviation
@@ -198,7 +530,7 @@ pub fn ema(v: &[f64], window_size: usize) -> Vec<f64> {
 // # Examples
 // ```
 // use peroxide::fuga::*;
-// use quantauri::algorithm::mstd;
+// use quantauri::base::mstd;
 //
 // fn main() {
 //    let v = seq(1, 10, 1);
@@ -208,49 +540,4199 @@ pub fn ema(v: &[f64], window_size: usize) -> Vec<f64> {
 // }
 // ```
 pub fn mstd(v: &[f64], window_size: usize, sma: &[f64]) -> Vec<f64> {
+    mstd_checked(v, window_size, sma).expect("mstd: invalid input")
+}
+
+/// Moving Standard Deviation (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `window_size == 0`
+/// * `LengthMismatch` if `v` and `sma` differ in length
+pub fn mstd_checked(v: &[f64], window_size: usize, sma: &[f64]) -> Result<Vec<f64>, QuantError> {
+    if window_size == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    check_len(v.len(), sma.len())?;
     let mut result = vec![0f64; v.len()];
     for i in window_size-1 .. v.len() {
         result[i] = (0 .. window_size).map(|x| (v[i-x] - sma[i]).powi(2)).sum::<f64>() / window_size as f64;
         result[i] = result[i].sqrt();
     }
-    result
+    Ok(result)
 }
 
-// Bollinger Band
-//
-// # Arguments
-// * `v` - &Vec<f64>
-// * `amplitude` - f64
-// * `sma` - &[f64]
-// * `mstd` - &[f64]
-//
-// # Returns
-// * (Vec<f64>, Vec<f64>)
-//   * (ubb, lbb)
-//   * ubb = sma + amplitude * mstd
-//   * lbb = sma - amplitude * mstd
-pub fn bollinger_band(v: &[f64], amplitude: f64, sma: &[f64], mstd: &[f64]) -> (Vec<f64>, Vec<f64>) {
-    let mut ubb = vec![0f64; v.len()];
-    let mut lbb = vec![0f64; v.len()];
+// =============================================================================
+// Missing Data
+// =============================================================================
+/// Handling of NAN (missing) bars in `sma_skipna` / `ema_skipna`
+///
+/// * `Propagate` - the plain recurrence (`sma`, `ema`): everything after the first NAN is NAN
+/// * `ForwardFill` - a NAN bar repeats the last valid value (leading NANs stay missing)
+/// * `Skip` - NAN bars are left out: the average of the valid values in the window,
+///   the EMA carries its last value over a gap
+/// * `Reset` - a NAN bar is NAN and the average restarts after it, as if a new series began
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NanPolicy {
+    Propagate,
+    ForwardFill,
+    Skip,
+    Reset,
+}
+
+/// Replace each NAN with the last valid value (leading NANs are kept)
+fn forward_fill(v: &[f64]) -> Vec<f64> {
+    let mut last = f64::NAN;
+    v.iter().map(|&x| {
+        if !x.is_nan() {
+            last = x;
+        }
+        last
+    }).collect()
+}
+
+/// Simple Moving Average of a series with missing bars
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window_size` - usize
+/// * `policy` - NanPolicy
+///
+/// # Returns
+/// * Vec<f64> (partial windows at the start, as `sma`)
+///   * `Skip`: mean of the valid values of `v[i+1-window_size ..= i]`, NAN if there is none
+///   * `Reset`: NAN at a NAN bar, then partial windows from the next bar on
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{sma, sma_skipna, NanPolicy};
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, f64::NAN, 4f64, 5f64];
+///
+///     // Plain sma: one missing bar contaminates the rest of the series
+///     let plain = sma(&v, 2);
+///     assert_eq!(plain[.. 2], [1f64, 1.5]);
+///     assert!(plain[2 ..].iter().all(|x| x.is_nan()));
+///     assert!(sma_skipna(&v, 2, NanPolicy::Propagate)[4].is_nan());
+///
+///     assert_eq!(sma_skipna(&v, 2, NanPolicy::Skip), vec![1f64, 1.5, 2f64, 4f64, 4.5]);
+///     assert_eq!(sma_skipna(&v, 2, NanPolicy::ForwardFill), vec![1f64, 1.5, 2f64, 3f64, 4.5]);
+///     let r = sma_skipna(&v, 2, NanPolicy::Reset);
+///     assert!(r[2].is_nan());
+///     assert_eq!(r[3 ..], [4f64, 4.5]);
+/// }
+/// ```
+pub fn sma_skipna(v: &[f64], window_size: usize, policy: NanPolicy) -> Vec<f64> {
+    sma_skipna_checked(v, window_size, policy).expect("sma_skipna: invalid input")
+}
+
+/// Simple Moving Average of a series with missing bars (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `window_size == 0`
+/// * `WindowTooLarge` if `window_size > v.len()`
+pub fn sma_skipna_checked(v: &[f64], window_size: usize, policy: NanPolicy) -> Result<Vec<f64>, QuantError> {
+    check_window(window_size, v.len())?;
+    let filled;
+    let v = match policy {
+        NanPolicy::Propagate => return sma_checked(v, window_size),
+        NanPolicy::ForwardFill => {
+            filled = forward_fill(v);
+            &filled[..]
+        }
+        NanPolicy::Skip | NanPolicy::Reset => v,
+    };
+    let mut result = vec![f64::NAN; v.len()];
+    let mut sum = 0f64;
+    let mut count = 0usize;
+    // First bar of the current segment (only moves under `Reset`)
+    let mut start = 0usize;
     for i in 0 .. v.len() {
-        ubb[i] = sma[i] + amplitude * mstd[i];
-        lbb[i] = sma[i] - amplitude * mstd[i];
+        if v[i].is_nan() && policy == NanPolicy::Reset {
+            sum = 0f64;
+            count = 0;
+            start = i + 1;
+            continue;
+        }
+        if !v[i].is_nan() {
+            sum += v[i];
+            count += 1;
+        }
+        if i >= window_size && i - window_size >= start && !v[i - window_size].is_nan() {
+            sum -= v[i - window_size];
+            count -= 1;
+        }
+        if count > 0 {
+            result[i] = sum / count as f64;
+        }
     }
-    (ubb, lbb)
+    Ok(result)
 }
 
-// Moving Average Convergence Divergence
-//
-// # Arguments
-// * `v` - &Vec<f64>
-//
-// # Returns
-// * (Vec<f64>, Vec<f64>)
-//   * (macd, signal)
-//   * macd = ema(v, 12) - ema(v, 26)
-//   * signal = ema(macd, 9)
-pub fn macd(v: &[f64]) -> (Vec<f64>, Vec<f64>) {
-    let macd = ema(v, 12).sub_v(&ema(v, 26));
-    let signal = ema(&macd, 9);
-    (macd, signal)
+/// Exponential Moving Average of a series with missing bars
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window_size` - usize
+/// * `policy` - NanPolicy
+///
+/// # Returns
+/// * Vec<f64> (alpha = 2 / (window_size + 1), seeded with the first valid value)
+///   * NAN before the first valid value (except `Propagate`, which is `ema` as is)
+///   * `Skip`: a NAN bar repeats the previous EMA without updating it
+///   * `Reset`: NAN at a NAN bar, the next valid value reseeds the EMA
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{ema, ema_skipna, NanPolicy};
+///
+/// fn main() {
+///     // alpha = 0.5
+///     let v = vec![1f64, 3f64, f64::NAN, 5f64];
+///     let plain = ema(&v, 3);
+///     assert!(plain[2].is_nan() && plain[3].is_nan());
+///
+///     assert_eq!(ema_skipna(&v, 3, NanPolicy::Skip), vec![1f64, 2f64, 2f64, 3.5]);
+///     assert_eq!(ema_skipna(&v, 3, NanPolicy::ForwardFill), vec![1f64, 2f64, 2.5, 3.75]);
+///     let r = ema_skipna(&v, 3, NanPolicy::Reset);
+///     assert!(r[2].is_nan());
+///     assert_eq!(r[3], 5f64);
+/// }
+/// ```
+pub fn ema_skipna(v: &[f64], window_size: usize, policy: NanPolicy) -> Vec<f64> {
+    ema_skipna_checked(v, window_size, policy).expect("ema_skipna: invalid input")
+}
+
+/// Exponential Moving Average of a series with missing bars (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `window_size == 0`
+/// * `EmptyInput` if `v` is empty
+pub fn ema_skipna_checked(v: &[f64], window_size: usize, policy: NanPolicy) -> Result<Vec<f64>, QuantError> {
+    if window_size == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    check_nonempty(v)?;
+    let filled;
+    let v = match policy {
+        NanPolicy::Propagate => return ema_checked(v, window_size),
+        NanPolicy::ForwardFill => {
+            filled = forward_fill(v);
+            &filled[..]
+        }
+        NanPolicy::Skip | NanPolicy::Reset => v,
+    };
+    let alpha = 2f64 / (window_size as f64 + 1f64);
+    let mut result = vec![f64::NAN; v.len()];
+    let mut state = f64::NAN;
+    for i in 0 .. v.len() {
+        if v[i].is_nan() {
+            if policy == NanPolicy::Reset {
+                state = f64::NAN;
+            }
+        } else if state.is_nan() {
+            state = v[i];
+        } else {
+            state = alpha * v[i] + (1f64 - alpha) * state;
+        }
+        result[i] = state;
+    }
+    Ok(result)
+}
+
+// =============================================================================
+// Warm-up Policy
+// =============================================================================
+/// Warm-up policy for the first `window - 1` values of a windowed indicator
+///
+/// * `Partial` - compute over the bars available so far (`sma`, `ema`)
+/// * `Zero` - emit 0 (`mstd`)
+/// * `Nan` - emit NAN, so plots show a gap (`wma`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Warmup {
+    Partial,
+    Zero,
+    Nan,
+}
+
+/// Overwrite the first `window - 1` values according to `warmup`
+/// (`Partial` leaves them as computed)
+fn apply_warmup(mut v: Vec<f64>, window: usize, warmup: Warmup) -> Vec<f64> {
+    let fill = match warmup {
+        Warmup::Partial => return v,
+        Warmup::Zero => 0f64,
+        Warmup::Nan => f64::NAN,
+    };
+    let end = window.saturating_sub(1).min(v.len());
+    v[.. end].iter_mut().for_each(|x| *x = fill);
+    v
+}
+
+/// Simple Moving Average with a warm-up policy
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window_size` - usize
+/// * `warmup` - Warmup (`sma` is `Warmup::Partial`)
+///
+/// # Returns
+/// * Vec<f64>
+///
+/// # Examples
+/// ```
+/// use quantauri::Warmup;
+/// use quantauri::base::{sma, sma_with_warmup, mstd, mstd_with_warmup};
+///
+/// fn main() {
+///     let v = vec![1f64, 3f64, 5f64, 7f64];
+///
+///     // Policy matrix for window 3: only the first 2 values differ
+///     let s = |w| sma_with_warmup(&v, 3, w);
+///     assert_eq!(s(Warmup::Partial), vec![1f64, 2f64, 3f64, 5f64]);
+///     assert_eq!(s(Warmup::Zero), vec![0f64, 0f64, 3f64, 5f64]);
+///     let s_nan = s(Warmup::Nan);
+///     assert!(s_nan[0].is_nan() && s_nan[1].is_nan());
+///     assert_eq!(s_nan[2 ..], [3f64, 5f64]);
+///
+///     let m = sma(&v, 3);
+///     let d = |w| mstd_with_warmup(&v, 3, &m, w);
+///     assert_eq!(d(Warmup::Partial)[.. 2], [0f64, 1f64]);
+///     assert_eq!(d(Warmup::Zero)[.. 2], [0f64, 0f64]);
+///     assert!(d(Warmup::Nan)[.. 2].iter().all(|x| x.is_nan()));
+///     assert!((0 .. 3).all(|i| {
+///         let w = [Warmup::Partial, Warmup::Zero, Warmup::Nan][i];
+///         d(w)[2 ..] == d(Warmup::Zero)[2 ..]
+///     }));
+///
+///     // Defaults are unchanged
+///     assert_eq!(sma(&v, 3), s(Warmup::Partial));
+///     assert_eq!(mstd(&v, 3, &m), d(Warmup::Zero));
+/// }
+/// ```
+pub fn sma_with_warmup(v: &[f64], window_size: usize, warmup: Warmup) -> Vec<f64> {
+    apply_warmup(sma(v, window_size), window_size, warmup)
+}
+
+/// Exponential Moving Average with a warm-up policy
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window_size` - usize
+/// * `warmup` - Warmup (`ema` is `Warmup::Partial`: seeded with v[0])
+///
+/// # Returns
+/// * Vec<f64>
+///   * `Zero` / `Nan` blank the first `window_size - 1` values, the recursion is unchanged
+pub fn ema_with_warmup(v: &[f64], window_size: usize, warmup: Warmup) -> Vec<f64> {
+    apply_warmup(ema(v, window_size), window_size, warmup)
+}
+
+/// Weighted Moving Average with a warm-up policy
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window_size` - usize
+/// * `warmup` - Warmup (`wma` is `Warmup::Nan`)
+///
+/// # Returns
+/// * Vec<f64>
+///   * `Partial`: weights i+1, i, ..., 1 over the i+1 bars available
+///
+/// # Examples
+/// ```
+/// use quantauri::Warmup;
+/// use quantauri::base::wma_with_warmup;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 3f64];
+///     assert_eq!(wma_with_warmup(&v, 3, Warmup::Partial)[1], (2f64 * 2f64 + 1f64) / 3f64);
+///     assert_eq!(wma_with_warmup(&v, 3, Warmup::Zero)[1], 0f64);
+/// }
+/// ```
+pub fn wma_with_warmup(v: &[f64], window_size: usize, warmup: Warmup) -> Vec<f64> {
+    let mut result = wma(v, window_size);
+    if warmup == Warmup::Partial {
+        for i in 0 .. window_size.saturating_sub(1).min(v.len()) {
+            let m = i + 1;
+            let denom = (m * (m + 1)) as f64 / 2f64;
+            result[i] = (0 .. m).map(|k| (m - k) as f64 * v[i-k]).sum::<f64>() / denom;
+        }
+        result
+    } else {
+        apply_warmup(result, window_size, warmup)
+    }
+}
+
+/// Moving Standard Deviation with a warm-up policy
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window_size` - usize
+/// * `sma` - &[f64]
+/// * `warmup` - Warmup (`mstd` is `Warmup::Zero`)
+///
+/// # Returns
+/// * Vec<f64>
+///   * `Partial`: population std of the i+1 bars available about sma[i]
+pub fn mstd_with_warmup(v: &[f64], window_size: usize, sma: &[f64], warmup: Warmup) -> Vec<f64> {
+    let mut result = mstd(v, window_size, sma);
+    if warmup == Warmup::Partial {
+        for i in 0 .. window_size.saturating_sub(1).min(v.len()) {
+            result[i] = ((0 ..= i).map(|x| (v[x] - sma[i]).powi(2)).sum::<f64>() / (i + 1) as f64).sqrt();
+        }
+        result
+    } else {
+        apply_warmup(result, window_size, warmup)
+    }
+}
+
+/// Bollinger Band with a warm-up policy
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window_size` - usize
+/// * `amplitude` - f64
+/// * `warmup` - Warmup (`BollingerBand::bb` is partial `sma` with `Zero` `mstd`)
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>, Vec<f64>)
+///   * (ubb, mbb, lbb), where the policy applies to all three bands
+///
+/// # Examples
+/// ```
+/// use quantauri::Warmup;
+/// use quantauri::base::bollinger_band_with_warmup;
+///
+/// fn main() {
+///     let v = vec![1f64, 3f64, 5f64, 7f64];
+///     let (ubb, mbb, lbb) = bollinger_band_with_warmup(&v, 3, 2f64, Warmup::Nan);
+///     assert!(ubb[1].is_nan() && mbb[1].is_nan() && lbb[1].is_nan());
+///     assert_eq!(mbb[3], 5f64);
+/// }
+/// ```
+pub fn bollinger_band_with_warmup(v: &[f64], window_size: usize, amplitude: f64, warmup: Warmup) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let mbb = sma(v, window_size);
+    let std = mstd_with_warmup(v, window_size, &mbb, if warmup == Warmup::Nan { Warmup::Partial } else { warmup });
+    let (ubb, lbb) = bollinger_band(v, amplitude, &mbb, &std);
+    (
+        apply_warmup(ubb, window_size, warmup),
+        apply_warmup(mbb, window_size, warmup),
+        apply_warmup(lbb, window_size, warmup),
+    )
+}
+
+// Bollinger Band
+//
+// # Arguments
+// * `v` - &Vec<f64>
+// * `amplitude` - f64
+// * `sma` - &[f64]
+// * `mstd` - &[f64]
+//
+// # Returns
+// * (Vec<f64>, Vec<f64>)
+//   * (ubb, lbb)
+//   * ubb = sma + amplitude * mstd
+//   * lbb = sma - amplitude * mstd
+pub fn bollinger_band(v: &[f64], amplitude: f64, sma: &[f64], mstd: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    bollinger_band_checked(v, amplitude, sma, mstd).expect("bollinger_band: invalid input")
+}
+
+/// Bollinger Band (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `sma` or `mstd` differ in length from `v`
+pub fn bollinger_band_checked(v: &[f64], amplitude: f64, sma: &[f64], mstd: &[f64]) -> Result<(Vec<f64>, Vec<f64>), QuantError> {
+    bollinger_band_asymmetric_checked(v, amplitude, amplitude, sma, mstd)
+}
+
+/// Bollinger Band with separate upper and lower multipliers
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `upper_amplitude` - f64
+/// * `lower_amplitude` - f64
+/// * `sma` - &[f64]
+/// * `mstd` - &[f64]
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (ubb, lbb)
+///   * ubb = sma + upper_amplitude * mstd
+///   * lbb = sma - lower_amplitude * mstd
+pub fn bollinger_band_asymmetric(v: &[f64], upper_amplitude: f64, lower_amplitude: f64, sma: &[f64], mstd: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    bollinger_band_asymmetric_checked(v, upper_amplitude, lower_amplitude, sma, mstd).expect("bollinger_band_asymmetric: invalid input")
+}
+
+/// Bollinger Band with separate upper and lower multipliers (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `sma` or `mstd` differ in length from `v`
+pub fn bollinger_band_asymmetric_checked(v: &[f64], upper_amplitude: f64, lower_amplitude: f64, sma: &[f64], mstd: &[f64]) -> Result<(Vec<f64>, Vec<f64>), QuantError> {
+    check_len(v.len(), sma.len())?;
+    check_len(v.len(), mstd.len())?;
+    let mut ubb = vec![0f64; v.len()];
+    let mut lbb = vec![0f64; v.len()];
+    for i in 0 .. v.len() {
+        ubb[i] = sma[i] + upper_amplitude * mstd[i];
+        lbb[i] = sma[i] - lower_amplitude * mstd[i];
+    }
+    Ok((ubb, lbb))
+}
+
+/// Percentage Band (%B)
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `ubb` - &[f64]
+/// * `lbb` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * (v - lbb) / (ubb - lbb)
+///   * If ubb == lbb, (v - lbb) / (ubb - lbb + 1e-3)
+///
+/// # Examples
+/// ```
+/// use quantauri::base::percent_b;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 3f64];
+///     let ubb = vec![3f64, 3f64, 3f64];
+///     let lbb = vec![1f64, 1f64, 3f64];
+///     assert_eq!(percent_b(&v, &ubb, &lbb), vec![0f64, 0.5, 0f64]);
+/// }
+/// ```
+pub fn percent_b(v: &[f64], ubb: &[f64], lbb: &[f64]) -> Vec<f64> {
+    let mut result = vec![0f64; v.len()];
+    for i in 0 .. v.len() {
+        let u = ubb[i];
+        let l = lbb[i];
+        if u == l {
+            result[i] = (v[i] - l) / (u - l + 1e-3);
+        } else {
+            result[i] = (v[i] - l) / (u - l);
+        }
+    }
+    result
+}
+
+/// Band Width
+///
+/// # Arguments
+/// * `ubb` - &[f64]
+/// * `mbb` - &[f64]
+/// * `lbb` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * (ubb - lbb) / mbb
+///   * 0 where mbb == 0
+pub fn bandwidth(ubb: &[f64], mbb: &[f64], lbb: &[f64]) -> Vec<f64> {
+    let mut result = vec![0f64; mbb.len()];
+    for i in 0 .. mbb.len() {
+        if mbb[i] != 0f64 {
+            result[i] = (ubb[i] - lbb[i]) / mbb[i];
+        }
+    }
+    result
+}
+
+/// Rolling Z-Score
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * (v - sma(v, window)) / mstd(v, window)
+///   * 0 where the standard deviation is zero, including the `mstd` warm-up
+///     (first `window - 1` values) and constant windows
+///
+/// # Examples
+/// ```
+/// use quantauri::base::rolling_zscore;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 3f64, 3f64, 3f64, 3f64];
+///     let z = rolling_zscore(&v, 3);
+///     assert_eq!(z[0], 0f64);
+///     // window [1, 2, 3]: mean 2, std sqrt(2/3)
+///     assert!((z[2] - 1f64 / (2f64 / 3f64).sqrt()).abs() < 1e-12);
+///     assert_eq!(z[5], 0f64); // flat window
+/// }
+/// ```
+pub fn rolling_zscore(v: &[f64], window: usize) -> Vec<f64> {
+    rolling_zscore_checked(v, window).expect("rolling_zscore: invalid input")
+}
+
+/// Rolling Z-Score (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `window == 0`
+/// * `WindowTooLarge` if `window > v.len()`
+pub fn rolling_zscore_checked(v: &[f64], window: usize) -> Result<Vec<f64>, QuantError> {
+    check_window(window, v.len())?;
+    let mean = sma_checked(v, window)?;
+    let mut std = mstd_checked(v, window, &mean)?;
+    // Constant windows are detected exactly: the `sma` running sum leaves
+    // rounding noise in `mstd` that would otherwise be amplified.
+    let hi = rolling_max(v, window);
+    let lo = rolling_min(v, window);
+    for i in 0 .. v.len() {
+        if hi[i] == lo[i] {
+            std[i] = 0f64;
+        }
+    }
+    rolling_zscore_of_checked(v, &mean, &std)
+}
+
+/// Z-Score against supplied center and spread
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `center` - &[f64] (e.g. a moving average of a pair spread)
+/// * `spread` - &[f64] (e.g. a moving standard deviation)
+///
+/// # Returns
+/// * Vec<f64>
+///   * (v - center) / spread
+///   * 0 where spread == 0, NAN propagates
+///
+/// # Examples
+/// ```
+/// use quantauri::base::rolling_zscore_of;
+///
+/// fn main() {
+///     let z = rolling_zscore_of(&[3f64, 5f64, 1f64], &[1f64, 5f64, 0f64], &[2f64, 1f64, 0f64]);
+///     assert_eq!(z, vec![1f64, 0f64, 0f64]);
+/// }
+/// ```
+pub fn rolling_zscore_of(v: &[f64], center: &[f64], spread: &[f64]) -> Vec<f64> {
+    rolling_zscore_of_checked(v, center, spread).expect("rolling_zscore_of: invalid input")
+}
+
+/// Z-Score against supplied center and spread (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if the inputs differ in length
+pub fn rolling_zscore_of_checked(v: &[f64], center: &[f64], spread: &[f64]) -> Result<Vec<f64>, QuantError> {
+    check_len(v.len(), center.len())?;
+    check_len(v.len(), spread.len())?;
+    Ok((0 .. v.len()).map(|i| {
+        if spread[i] == 0f64 {
+            0f64
+        } else {
+            (v[i] - center[i]) / spread[i]
+        }
+    }).collect())
+}
+
+// Moving Average Convergence Divergence
+//
+// # Arguments
+// * `v` - &Vec<f64>
+//
+// # Returns
+// * (Vec<f64>, Vec<f64>)
+//   * (macd, signal)
+//   * macd = ema(v, 12) - ema(v, 26)
+//   * signal = ema(macd, 9)
+pub fn macd(v: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    macd_checked(v).expect("macd: invalid input")
+}
+
+/// Moving Average Convergence Divergence (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+pub fn macd_checked(v: &[f64]) -> Result<(Vec<f64>, Vec<f64>), QuantError> {
+    macd_with_checked(v, 12, 26, 9)
+}
+
+/// Moving Average Convergence Divergence with custom periods
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `fast` - usize
+/// * `slow` - usize
+/// * `signal` - usize
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (macd, signal)
+///   * macd = ema(v, fast) - ema(v, slow)
+///   * signal = ema(macd, signal)
+pub fn macd_with(v: &[f64], fast: usize, slow: usize, signal: usize) -> (Vec<f64>, Vec<f64>) {
+    macd_with_checked(v, fast, slow, signal).expect("macd_with: invalid input")
+}
+
+/// Moving Average Convergence Divergence with custom periods (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+/// * `InvalidPeriod` if any period is zero
+/// * `InvalidParameter` if `fast >= slow`
+pub fn macd_with_checked(v: &[f64], fast: usize, slow: usize, signal: usize) -> Result<(Vec<f64>, Vec<f64>), QuantError> {
+    let macd = price_oscillator_checked(v, fast, slow, MaKind::Ema, true)?;
+    let signal = ema_checked(&macd, signal)?;
+    Ok((macd, signal))
+}
+
+/// Volume-Weighted Exponential Moving Average
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `volume` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * ema(close * volume, period) / ema(volume, period)
+///   * NAN until the first bar with volume
+///   * Over a zero-volume stretch both EMAs decay together; once ema(volume) has fallen below
+///     `f64::EPSILON` times its peak, the last value is carried instead of dividing
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{vwema, ema};
+///
+/// fn main() {
+///     let close = vec![10f64, 12f64, 11f64, 14f64];
+///     // Constant volume: plain EMA
+///     let e = ema(&close, 3);
+///     let w = vwema(&close, &vec![5f64; 4], 3);
+///     assert!((0 .. 4).all(|i| (w[i] - e[i]).abs() < 1e-12));
+///
+///     // Heavy volume pulls the average toward its bar
+///     let w = vwema(&close, &[1f64, 1f64, 1f64, 10f64], 3);
+///     assert!(w[3] > e[3]);
+///
+///     let w = vwema(&close, &[0f64, 0f64, 2f64, 0f64], 3);
+///     assert!(w[1].is_nan());
+///     assert_eq!(w[2..], [11f64, 11f64]);
+/// }
+/// ```
+pub fn vwema(close: &[f64], volume: &[f64], period: usize) -> Vec<f64> {
+    vwema_checked(close, volume, period).expect("vwema: invalid input")
+}
+
+/// Volume-Weighted Exponential Moving Average (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `close` and `volume` differ in length
+/// * `InvalidPeriod` if `period == 0`
+/// * `EmptyInput` if `close` is empty
+pub fn vwema_checked(close: &[f64], volume: &[f64], period: usize) -> Result<Vec<f64>, QuantError> {
+    check_len(close.len(), volume.len())?;
+    let pv = close.iter().zip(volume).map(|(&c, &v)| c * v).collect::<Vec<f64>>();
+    let num = ema_checked(&pv, period)?;
+    let den = ema_checked(volume, period)?;
+    let mut result = vec![f64::NAN; close.len()];
+    let mut peak = 0f64;
+    for i in 0 .. close.len() {
+        peak = peak.max(den[i]);
+        result[i] = if peak > 0f64 && den[i] > f64::EPSILON * peak {
+            num[i] / den[i]
+        } else if i > 0 {
+            result[i-1]
+        } else {
+            f64::NAN
+        };
+    }
+    Ok(result)
+}
+
+/// Volume-Weighted MACD
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `volume` - &[f64]
+/// * `fast` - usize (default: 12)
+/// * `slow` - usize (default: 26)
+/// * `signal` - usize (default: 9)
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>, Vec<f64>)
+///   * (macd, signal, histogram)
+///   * macd = vwema(close, volume, fast) - vwema(close, volume, slow)
+///   * signal = ema of macd (NAN bars before the first volume are skipped)
+///   * histogram = macd - signal
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{vw_macd, macd};
+///
+/// fn main() {
+///     let close = (0 .. 60).map(|x| 100f64 + (x as f64 * 0.3).sin() * 5f64).collect::<Vec<f64>>();
+///     // Constant volume: the ordinary MACD
+///     let (line, signal, hist) = vw_macd(&close, &vec![1000f64; 60], 12, 26, 9);
+///     let (m, s) = macd(&close);
+///     assert!((0 .. 60).all(|i| (line[i] - m[i]).abs() < 1e-9 && (signal[i] - s[i]).abs() < 1e-9));
+///     assert!((0 .. 60).all(|i| hist[i] == line[i] - signal[i]));
+///
+///     // Leading zero volume: NAN there, finite once volume arrives
+///     let mut volume = vec![1000f64; 60];
+///     volume[.. 5].iter_mut().for_each(|x| *x = 0f64);
+///     let (line, signal, _) = vw_macd(&close, &volume, 12, 26, 9);
+///     assert!(line[4].is_nan() && signal[4].is_nan());
+///     assert!(line[5 ..].iter().chain(&signal[5 ..]).all(|x| x.is_finite()));
+/// }
+/// ```
+pub fn vw_macd(close: &[f64], volume: &[f64], fast: usize, slow: usize, signal: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    vw_macd_checked(close, volume, fast, slow, signal).expect("vw_macd: invalid input")
+}
+
+/// (macd, signal, histogram) lines
+pub type MacdLines = (Vec<f64>, Vec<f64>, Vec<f64>);
+
+/// Volume-Weighted MACD (fallible)
+///
+/// # Errors
+/// * `InvalidParameter` if `fast >= slow`
+/// * `InvalidPeriod` if `signal == 0`
+/// * Errors of `vwema_checked`
+pub fn vw_macd_checked(close: &[f64], volume: &[f64], fast: usize, slow: usize, signal: usize) -> Result<MacdLines, QuantError> {
+    if fast >= slow {
+        return Err(QuantError::InvalidParameter(format!("fast period {} must be less than slow period {}", fast, slow)));
+    }
+    let fast_ma = vwema_checked(close, volume, fast)?;
+    let slow_ma = vwema_checked(close, volume, slow)?;
+    let macd = (0 .. close.len()).map(|i| fast_ma[i] - slow_ma[i]).collect::<Vec<f64>>();
+    let signal = ema_skipna_checked(&macd, signal, NanPolicy::Skip)?;
+    let hist = (0 .. close.len()).map(|i| macd[i] - signal[i]).collect();
+    Ok((macd, signal, hist))
+}
+
+/// Price Oscillator
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `fast` - usize
+/// * `slow` - usize (> fast)
+/// * `kind` - MaKind (the same smoothing for both averages)
+/// * `absolute` - bool
+///
+/// # Returns
+/// * Vec<f64>
+///   * absolute: fast_ma - slow_ma (APO, the MACD line for `MaKind::Ema`)
+///   * otherwise: 100 * (fast_ma - slow_ma) / slow_ma (PPO), NAN where slow_ma is 0
+///   * Warm-up of the chosen `MaKind`
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{price_oscillator, macd_with, sma, MaKind};
+///
+/// fn main() {
+///     let v = (0 .. 60).map(|x| 100f64 + (x as f64 * 0.3).sin() * 5f64).collect::<Vec<f64>>();
+///     // EMA case is the MACD line
+///     let (macd, _) = macd_with(&v, 12, 26, 9);
+///     assert_eq!(price_oscillator(&v, 12, 26, MaKind::Ema, true), macd);
+///
+///     // SMA-based percentage oscillator
+///     let ppo = price_oscillator(&v, 5, 20, MaKind::Sma, false);
+///     let (f, s) = (sma(&v, 5), sma(&v, 20));
+///     assert!((ppo[40] - 100f64 * (f[40] - s[40]) / s[40]).abs() < 1e-12);
+///
+///     assert!(quantauri::base::price_oscillator_checked(&v, 26, 12, MaKind::Ema, true).is_err());
+/// }
+/// ```
+pub fn price_oscillator(v: &[f64], fast: usize, slow: usize, kind: MaKind, absolute: bool) -> Vec<f64> {
+    price_oscillator_checked(v, fast, slow, kind, absolute).expect("price_oscillator: invalid input")
+}
+
+/// Price Oscillator (fallible)
+///
+/// # Errors
+/// * `InvalidParameter` if `fast >= slow`
+/// * Errors of `moving_average_checked`
+pub fn price_oscillator_checked(v: &[f64], fast: usize, slow: usize, kind: MaKind, absolute: bool) -> Result<Vec<f64>, QuantError> {
+    if fast >= slow {
+        return Err(QuantError::InvalidParameter(format!("fast period {} must be less than slow period {}", fast, slow)));
+    }
+    let fast_ma = moving_average_checked(v, fast, kind)?;
+    let slow_ma = moving_average_checked(v, slow, kind)?;
+    Ok((0 .. v.len()).map(|i| {
+        let diff = fast_ma[i] - slow_ma[i];
+        if absolute {
+            diff
+        } else if slow_ma[i] == 0f64 {
+            f64::NAN
+        } else {
+            100f64 * diff / slow_ma[i]
+        }
+    }).collect())
+}
+
+/// True Range
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * tr = max(high, prev close) - min(low, prev close)
+///   * tr[0] = high[0] - low[0] (no previous close)
+pub fn true_range(high: &[f64], low: &[f64], close: &[f64]) -> Vec<f64> {
+    let mut result = vec![0f64; close.len()];
+    if close.is_empty() {
+        return result;
+    }
+    result[0] = high[0] - low[0];
+    for i in 1 .. close.len() {
+        result[i] = high[i].max(close[i-1]) - low[i].min(close[i-1]);
+    }
+    result
+}
+
+/// Choppiness Index
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `period` - usize (> 1)
+///
+/// # Returns
+/// * Vec<f64>
+///   * chop = 100 * log10(sum(tr, period) / (max(high, period) - min(low, period))) / log10(period)
+///   * First `period - 1` values are NAN (incomplete window)
+///   * Flat window (max high == min low) gives 100 (maximum chop)
+///   * Clamped to [0, 100]: the first bar of a window may gap away from the
+///     previous close, which can push the raw ratio slightly above `period`.
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::base::choppiness;
+///
+/// fn main() {
+///     // Random walk
+///     let n = 500;
+///     let dx = Normal(0, 1).sample(n);
+///     let mut close = vec![100f64; n];
+///     for i in 1 .. n {
+///         close[i] = close[i-1] + dx[i];
+///     }
+///     let high = close.iter().map(|x| x + 0.5).collect::<Vec<f64>>();
+///     let low = close.iter().map(|x| x - 0.5).collect::<Vec<f64>>();
+///
+///     let chop = choppiness(&high, &low, &close, 14);
+///     assert!(chop[.. 13].iter().all(|x| x.is_nan()));
+///     assert!(chop[13 ..].iter().all(|&x| (0f64 ..= 100f64).contains(&x)));
+///
+///     // Flat window
+///     let flat = vec![1f64; 20];
+///     let chop = choppiness(&flat, &flat, &flat, 14);
+///     assert_eq!(chop[19], 100f64);
+///
+///     // log10(1) = 0: a one-bar window has no meaning
+///     assert!(quantauri::base::choppiness_checked(&flat, &flat, &flat, 1).is_err());
+/// }
+/// ```
+pub fn choppiness(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Vec<f64> {
+    choppiness_checked(high, low, close, period).expect("choppiness: invalid input")
+}
+
+/// Choppiness Index (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `period < 2` (log10(period) would be 0)
+/// * `LengthMismatch` if `high`, `low` and `close` differ in length
+pub fn choppiness_checked(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Result<Vec<f64>, QuantError> {
+    if period < 2 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    check_len(close.len(), high.len())?;
+    check_len(close.len(), low.len())?;
+    let mut result = vec![f64::NAN; close.len()];
+    let tr_sum = rolling_sum(&true_range(high, low, close), period);
+    let max_high = rolling_max(high, period);
+    let min_low = rolling_min(low, period);
+    let log_period = (period as f64).log10();
+    for i in period - 1 .. close.len() {
+        let range = max_high[i] - min_low[i];
+        if range == 0f64 {
+            result[i] = 100f64;
+        } else {
+            result[i] = (100f64 * (tr_sum[i] / range).log10() / log_period).clamp(0f64, 100f64);
+        }
+    }
+    Ok(result)
+}
+
+/// Fisher Transform recursion state
+///
+/// Holds the smoothed normalized value and the previous Fisher value so the
+/// same core can be driven bar by bar (streaming) or over a whole series.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FisherState {
+    pub value: f64,
+    pub fisher: f64,
+}
+
+impl FisherState {
+    pub fn new() -> Self {
+        FisherState::default()
+    }
+
+    /// Feed one bar and return the new Fisher value
+    ///
+    /// # Arguments
+    /// * `median` - f64 (current median price)
+    /// * `highest` - f64 (highest median price in window)
+    /// * `lowest` - f64 (lowest median price in window)
+    ///
+    /// # Description
+    /// * x = 2 * ((median - lowest) / (highest - lowest) - 0.5) (0 for a flat window)
+    /// * value = clamp(0.33 * x + 0.67 * value_prev, -0.999, 0.999)
+    /// * fisher = 0.5 * ln((1 + value) / (1 - value)) + 0.5 * fisher_prev
+    pub fn update(&mut self, median: f64, highest: f64, lowest: f64) -> f64 {
+        let range = highest - lowest;
+        let x = if range == 0f64 { 0f64 } else { 2f64 * ((median - lowest) / range - 0.5) };
+        self.value = (0.33 * x + 0.67 * self.value).clamp(-0.999, 0.999);
+        self.fisher = 0.5 * ((1f64 + self.value) / (1f64 - self.value)).ln() + 0.5 * self.fisher;
+        self.fisher
+    }
+}
+
+/// Fisher Transform
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (fisher, trigger)
+///   * median price = (high + low) / 2, normalized over the trailing `period` bars
+///   * trigger = fisher lagged by one bar
+///   * First `period - 1` values of fisher (and `period` of trigger) are NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::base::fisher_transform;
+///
+/// fn main() {
+///     // Monotone rise pins the normalized value at +1: the clamp keeps it finite
+///     let high = (0 .. 200).map(|x| x as f64 + 1f64).collect::<Vec<f64>>();
+///     let low = (0 .. 200).map(|x| x as f64).collect::<Vec<f64>>();
+///     let (fisher, trigger) = fisher_transform(&high, &low, 10);
+///     assert!(fisher[9 ..].iter().all(|x| x.is_finite()));
+///     assert_eq!(trigger[199], fisher[198]);
+///
+///     // Flat window
+///     let flat = vec![5f64; 50];
+///     let (fisher, _) = fisher_transform(&flat, &flat, 10);
+///     assert!(fisher[9 ..].iter().all(|&x| x == 0f64));
+///
+///     // A flat stretch after a pinned rise: no infinity, and Fisher decays toward 0
+///     let mut high = (0 .. 50).map(|x| x as f64 + 1f64).collect::<Vec<f64>>();
+///     let mut low = (0 .. 50).map(|x| x as f64).collect::<Vec<f64>>();
+///     high.extend(vec![50f64; 100]);
+///     low.extend(vec![50f64; 100]);
+///     let (fisher, _) = fisher_transform(&high, &low, 10);
+///     assert!(fisher[9 ..].iter().all(|x| x.is_finite()));
+///     assert!(fisher[49] > 3f64);
+///     assert!(fisher[149].abs() < 1e-6);
+/// }
+/// ```
+pub fn fisher_transform(high: &[f64], low: &[f64], period: usize) -> (Vec<f64>, Vec<f64>) {
+    fisher_transform_checked(high, low, period).expect("fisher_transform: invalid input")
+}
+
+/// Fisher Transform (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `period == 0`
+/// * `LengthMismatch` if `high` and `low` differ in length
+pub fn fisher_transform_checked(high: &[f64], low: &[f64], period: usize) -> Result<(Vec<f64>, Vec<f64>), QuantError> {
+    if period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    check_len(high.len(), low.len())?;
+    let n = high.len();
+    let median = median_price(high, low);
+    let highest = rolling_max(&median, period);
+    let lowest = rolling_min(&median, period);
+    let mut fisher = vec![f64::NAN; n];
+    let mut trigger = vec![f64::NAN; n];
+    let mut state = FisherState::new();
+    for i in period - 1 .. n {
+        fisher[i] = state.update(median[i], highest[i], lowest[i]);
+        if i > 0 {
+            trigger[i] = fisher[i-1];
+        }
+    }
+    Ok((fisher, trigger))
+}
+
+/// Wilder's Running Moving Average (RMA)
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window_size` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * rma[i] = (v[i] + (window_size - 1) * rma[i-1]) / window_size
+///   * rma[0] = v[0]
+///   * Same as `wilder_ema`, except that an empty input gives an empty output
+pub fn rma(v: &[f64], window_size: usize) -> Vec<f64> {
+    if v.is_empty() {
+        return vec![];
+    }
+    wilder_ema(v, window_size)
+}
+
+/// Relative Strength Index
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * rsi = 100 - 100 / (1 + avg_gain / avg_loss) with Wilder's averages:
+///     avg[period] = mean of the first `period` changes (bars 1 ..= period),
+///     then avg[i] = ((period - 1) * avg[i-1] + x[i]) / period
+///   * 100 if there is no loss, 50 if there is neither gain nor loss
+///   * First `period` values are NAN (all NAN if `period == 0` or `period >= v.len()`)
+///
+/// # Examples
+/// ```
+/// use quantauri::base::rsi;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 3f64, 4f64, 5f64];
+///     let rsi = rsi(&v, 2);
+///     assert!(rsi[1].is_nan());
+///     assert_eq!(rsi[4], 100f64);
+///
+///     // Seeded from the simple average of the first two changes: gain 1, loss 0,
+///     // then gain (1 + 0) / 2 = 0.5, loss (0 + 1) / 2 = 0.5
+///     assert_eq!(quantauri::base::rsi(&[1f64, 2f64, 3f64, 2f64], 2)[2 ..], [100f64, 50f64]);
+/// }
+/// ```
+pub fn rsi(v: &[f64], period: usize) -> Vec<f64> {
+    let n = v.len();
+    let mut result = vec![f64::NAN; n];
+    if period == 0 || period >= n {
+        return result;
+    }
+    let mut gain = vec![0f64; n];
+    let mut loss = vec![0f64; n];
+    for i in 1 .. n {
+        let d = v[i] - v[i-1];
+        if d > 0f64 {
+            gain[i] = d;
+        } else {
+            loss[i] = -d;
+        }
+    }
+    let p = period as f64;
+    let mut avg_gain = gain[1 ..= period].iter().sum::<f64>() / p;
+    let mut avg_loss = loss[1 ..= period].iter().sum::<f64>() / p;
+    for i in period .. n {
+        if i > period {
+            avg_gain = ((p - 1f64) * avg_gain + gain[i]) / p;
+            avg_loss = ((p - 1f64) * avg_loss + loss[i]) / p;
+        }
+        result[i] = if avg_loss == 0f64 {
+            if avg_gain == 0f64 { 50f64 } else { 100f64 }
+        } else {
+            100f64 - 100f64 / (1f64 + avg_gain / avg_loss)
+        };
+    }
+    result
+}
+
+/// Rolling sums of up-moves and down-moves
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (up, down): sums of max(d, 0) and max(-d, 0) over the last `period` changes
+///     d = v[j] - v[j-1], j in i+1-period ..= i (partial windows at the start, both 0 at bar 0)
+///   * Exactly 0 when the window has no move in that direction (no rounding residue),
+///     never negative
+///   * NAN while the window holds a change involving a NAN value
+pub fn up_down_sums(v: &[f64], period: usize) -> (Vec<f64>, Vec<f64>) {
+    let n = v.len();
+    let mut up_moves = vec![0f64; n];
+    let mut down_moves = vec![0f64; n];
+    let mut missing = vec![0f64; n];
+    for i in 1 .. n {
+        let d = v[i] - v[i-1];
+        if d > 0f64 {
+            up_moves[i] = d;
+        } else if d < 0f64 {
+            down_moves[i] = -d;
+        } else if d.is_nan() {
+            missing[i] = 1f64;
+        }
+    }
+    let mut up = rolling_sum(&up_moves, period);
+    let mut down = rolling_sum(&down_moves, period);
+    let count = |moves: &[f64]| rolling_sum(&moves.iter().map(|&x| if x > 0f64 { 1f64 } else { 0f64 }).collect::<Vec<f64>>(), period);
+    let (up_count, down_count, missing_count) = (count(&up_moves), count(&down_moves), count(&missing));
+    for i in 0 .. n {
+        if missing_count[i] > 0f64 {
+            up[i] = f64::NAN;
+            down[i] = f64::NAN;
+            continue;
+        }
+        up[i] = if up_count[i] == 0f64 { 0f64 } else { up[i].max(0f64) };
+        down[i] = if down_count[i] == 0f64 { 0f64 } else { down[i].max(0f64) };
+    }
+    (up, down)
+}
+
+/// Chande Momentum Oscillator
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize (default: 14)
+///
+/// # Returns
+/// * Vec<f64>
+///   * 100 * (up - down) / (up + down) with (up, down) = `up_down_sums(v, period)`
+///   * In [-100, 100] (the sums are never negative); 0 for a flat window
+///   * First `period` values are NAN
+///   * NAN while the window holds a change involving a NAN value (a gap is not a flat move)
+///
+/// # Description
+/// Unlike `rsi`, the sums are not smoothed, so every change leaves the window after
+/// `period` bars. |cmo| / 100 = |net change| / sum of |moves| is the efficiency ratio
+/// of `kama` over the same window.
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::base::cmo;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 4f64, 3f64, 3f64, 3f64, 3f64];
+///     let c = cmo(&v, 3);
+///     assert!(c[2].is_nan());
+///     // up = 1 + 2, down = 1
+///     assert_eq!(c[3], 50f64);
+///     assert!((c[4] - 100f64 / 3f64).abs() < 1e-12);
+///     assert_eq!(c[5], -100f64); // only a down-move left
+///     assert_eq!(c[6], 0f64);    // flat window
+///
+///     // A missing bar poisons the two changes touching it until they leave the window
+///     let c = cmo(&[1f64, 2f64, f64::NAN, 3f64, 4f64, 5f64, 6f64], 2);
+///     assert!(c[2 .. 5].iter().all(|x| x.is_nan()));
+///     assert_eq!(c[5 ..], [100f64, 100f64]);
+///
+///     // Bounded on random data
+///     let walk = Normal(0f64, 1f64).sample(500).iter().scan(100f64, |s, x| { *s += x; Some(*s) }).collect::<Vec<f64>>();
+///     assert!(cmo(&walk, 9)[9 ..].iter().all(|&x| (-100f64 ..= 100f64).contains(&x)));
+/// }
+/// ```
+pub fn cmo(v: &[f64], period: usize) -> Vec<f64> {
+    let n = v.len();
+    let mut result = vec![f64::NAN; n];
+    if period == 0 {
+        return result;
+    }
+    let (up, down) = up_down_sums(v, period);
+    for i in period .. n {
+        let total = up[i] + down[i];
+        result[i] = if total == 0f64 {
+            0f64
+        } else {
+            // Ratio first: |up - down| <= up + down, so it never rounds past 1
+            100f64 * ((up[i] - down[i]) / total)
+        };
+    }
+    result
+}
+
+/// Relative Volatility Index (Dorsey)
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `std_period` - usize (default: 10)
+/// * `smooth_period` - usize (default: 14)
+///
+/// # Returns
+/// * Vec<f64>
+///   * s = mstd(close, std_period) (population std)
+///   * up = s on bars where close rose, down = s on bars where it fell (0 otherwise)
+///   * 100 * rma(up) / (rma(up) + rma(down)), in [0, 100]; 50 when both are 0
+///   * The RMAs start at the first full std window; the first `std_period + smooth_period`
+///     values (std window, then `smooth_period` bars of RMA seeding) are NAN
+///
+/// # Description
+/// `rsi` with the size of each move replaced by the current volatility: above 50 when
+/// volatility comes with rising prices.
+///
+/// # Examples
+/// ```
+/// use quantauri::base::rvol_index;
+///
+/// fn main() {
+///     // Calm drift down, then a volatile rally
+///     let mut close = (0 .. 60).map(|x| 100f64 - 0.05 * x as f64).collect::<Vec<f64>>();
+///     for k in 0 .. 40 {
+///         let last = close[close.len() - 1];
+///         close.push(last + if k % 3 == 2 { -1f64 } else { 2.5 });
+///     }
+///     let r = rvol_index(&close, 10, 14);
+///     assert!(r[23].is_nan() && !r[24].is_nan());
+///     assert!(r[59] < 10f64);
+///     assert!(r[99] > 60f64);
+///     let cross = (61 .. 100).find(|&i| r[i-1] <= 50f64 && r[i] > 50f64);
+///     assert!(cross.is_some());
+///     assert!(r[24 ..].iter().all(|&x| (0f64 ..= 100f64).contains(&x)));
+/// }
+/// ```
+pub fn rvol_index(close: &[f64], std_period: usize, smooth_period: usize) -> Vec<f64> {
+    rvol_index_checked(close, std_period, smooth_period).expect("rvol_index: invalid input")
+}
+
+/// Relative Volatility Index (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `std_period` or `smooth_period` is 0
+/// * `WindowTooLarge` if `std_period > close.len()`
+pub fn rvol_index_checked(close: &[f64], std_period: usize, smooth_period: usize) -> Result<Vec<f64>, QuantError> {
+    if smooth_period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    check_window(std_period, close.len())?;
+    let n = close.len();
+    let std = mstd_checked(close, std_period, &sma_checked(close, std_period)?)?;
+    // First bar with a full std window and a previous close
+    let start = (std_period - 1).max(1);
+    let mut result = vec![f64::NAN; n];
+    if start >= n {
+        return Ok(result);
+    }
+    let mut up = vec![0f64; n - start];
+    let mut down = vec![0f64; n - start];
+    for i in start .. n {
+        if close[i] > close[i-1] {
+            up[i - start] = std[i];
+        } else if close[i] < close[i-1] {
+            down[i - start] = std[i];
+        }
+    }
+    let (up, down) = (rma(&up, smooth_period), rma(&down, smooth_period));
+    let warmup = std_period + smooth_period;
+    for i in warmup.max(start) .. n {
+        let k = i - start;
+        let total = up[k] + down[k];
+        result[i] = if total == 0f64 { 50f64 } else { 100f64 * up[k] / total };
+    }
+    Ok(result)
+}
+
+/// Rate of Change
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * roc = 100 * (v[i] - v[i-period]) / v[i-period]
+///   * First `period` values are NAN, NAN where v[i-period] == 0
+pub fn roc(v: &[f64], period: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; v.len()];
+    for i in period .. v.len() {
+        let prev = v[i-period];
+        if prev != 0f64 {
+            result[i] = 100f64 * (v[i] - prev) / prev;
+        }
+    }
+    result
+}
+
+/// Up/Down Streak
+///
+/// # Arguments
+/// * `v` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * Number of consecutive up (+) or down (-) closes ending at each bar
+///   * An unchanged close resets the streak to 0, streak[0] = 0
+///
+/// # Examples
+/// ```
+/// use quantauri::base::streak;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 3f64, 3f64, 2f64, 1f64, 2f64];
+///     assert_eq!(streak(&v), vec![0f64, 1f64, 2f64, 0f64, -1f64, -2f64, 1f64]);
+/// }
+/// ```
+pub fn streak(v: &[f64]) -> Vec<f64> {
+    let mut result = vec![0f64; v.len()];
+    for i in 1 .. v.len() {
+        if v[i] > v[i-1] {
+            result[i] = if result[i-1] > 0f64 { result[i-1] + 1f64 } else { 1f64 };
+        } else if v[i] < v[i-1] {
+            result[i] = if result[i-1] < 0f64 { result[i-1] - 1f64 } else { -1f64 };
+        }
+    }
+    result
+}
+
+/// Percent Rank
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * 100 * (number of the previous `window` values below v[i]) / window, in [0, 100]
+///   * A previous value equal to v[i] counts as half a value below
+///   * First `window` values are NAN, NAN if the current or any window value is NAN
+///   * O(log n) per bar (rolling order statistics)
+///
+/// # Examples
+/// ```
+/// use quantauri::base::percent_rank;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 3f64, 4f64, 2.5, 0f64];
+///     let r = percent_rank(&v, 4);
+///     assert!(r[.. 4].iter().all(|x| x.is_nan()));
+///     assert_eq!(r[4], 50f64);    // above 1, 2 of [1, 2, 3, 4]
+///     assert_eq!(r[5], 0f64);     // below everything in [2, 3, 4, 2.5]
+///
+///     // Ties count as 0.5
+///     let v = vec![1f64, 2f64, 2f64, 3f64, 2f64];
+///     let r = percent_rank(&v, 4);
+///     assert_eq!(r[4], 100f64 * (1f64 + 0.5 + 0.5) / 4f64);
+///     assert_eq!(percent_rank(&[5f64; 6], 5)[5], 50f64);
+/// }
+/// ```
+pub fn percent_rank(v: &[f64], window: usize) -> Vec<f64> {
+    previous_percent_rank(v, window)
+}
+
+/// Connors RSI
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `rsi_period` - usize (default: 3)
+/// * `streak_period` - usize (default: 2)
+/// * `rank_period` - usize (default: 100)
+///
+/// # Returns
+/// * Vec<f64>
+///   * (rsi(close, rsi_period) + rsi(streak(close), streak_period) + percent_rank(roc(close, 1), rank_period)) / 3
+///   * NAN until all three components are valid (`rank_period + 1` bars with defaults)
+///
+/// # Description
+/// * Streak (`streak`): +k after k consecutive higher closes, -k after k consecutive
+///   lower closes. An unchanged close (a flat day) resets it to 0, and the next move
+///   starts a new streak at +1 or -1, so a flat day pulls the streak RSI toward its
+///   midpoint instead of extending either run.
+/// * Return rank (`percent_rank`): previous returns equal to the current one count
+///   as half below it, so a run of identical returns ranks at 50.
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{connors_rsi, rsi, streak, percent_rank, roc};
+///
+/// fn main() {
+///     // Worked by hand (Wilder averages seeded with the mean of the first `period` changes)
+///     let close = vec![10f64, 11f64, 12f64, 11f64, 11.5];
+///
+///     // rsi(close, 3): avg gain 2/3 -> 11/18, avg loss 1/3 -> 2/9
+///     let c1 = rsi(&close, 3);
+///     assert!((c1[3] - 200f64 / 3f64).abs() < 1e-12);
+///     assert!((c1[4] - 220f64 / 3f64).abs() < 1e-12);
+///
+///     // streak = [0, 1, 2, -1, 1], rsi(streak, 2):
+///     // avg gain 1 -> 0.5 -> 1.25, avg loss 0 -> 1.5 -> 0.75
+///     let s = streak(&close);
+///     assert_eq!(s, vec![0f64, 1f64, 2f64, -1f64, 1f64]);
+///     let c2 = rsi(&s, 2);
+///     assert_eq!(c2[2 .. 4], [100f64, 25f64]);
+///     assert!((c2[4] - 62.5).abs() < 1e-12);
+///
+///     // roc(close, 1) = [NAN, 10, 9.09, -8.33, 4.55]: 4.55 is above 1 of the previous 3
+///     let c3 = percent_rank(&roc(&close, 1), 3);
+///     assert!(c3[3].is_nan());
+///     assert!((c3[4] - 100f64 / 3f64).abs() < 1e-12);
+///
+///     let crsi = connors_rsi(&close, 3, 2, 3);
+///     assert!(crsi[3].is_nan());
+///     assert!((crsi[4] - 56.388888888888886).abs() < 1e-12);
+///
+///     // Composite on a longer series
+///     let close = (0 .. 50).map(|x| 100f64 + (x as f64 * 0.7).sin() * 5f64).collect::<Vec<f64>>();
+///     let crsi = connors_rsi(&close, 3, 2, 20);
+///
+///     let c1 = rsi(&close, 3);
+///     let c2 = rsi(&streak(&close), 2);
+///     let c3 = percent_rank(&roc(&close, 1), 20);
+///     assert!(c1[3 ..].iter().all(|x| (0f64 ..= 100f64).contains(x)));
+///     assert!(c2[2 ..].iter().all(|x| (0f64 ..= 100f64).contains(x)));
+///     assert!(c3[21 ..].iter().all(|x| (0f64 ..= 100f64).contains(x)));
+///
+///     assert!(crsi[20].is_nan());
+///     for i in 21 .. 50 {
+///         assert!((crsi[i] - (c1[i] + c2[i] + c3[i]) / 3f64).abs() < 1e-12);
+///     }
+///
+///     // A flat day resets the streak
+///     assert_eq!(streak(&[1f64, 2f64, 3f64, 3f64, 4f64]), vec![0f64, 1f64, 2f64, 0f64, 1f64]);
+/// }
+/// ```
+pub fn connors_rsi(close: &[f64], rsi_period: usize, streak_period: usize, rank_period: usize) -> Vec<f64> {
+    let price_rsi = rsi(close, rsi_period);
+    let streak_rsi = rsi(&streak(close), streak_period);
+    let rank = percent_rank(&roc(close, 1), rank_period);
+    let mut result = vec![0f64; close.len()];
+    for i in 0 .. close.len() {
+        result[i] = (price_rsi[i] + streak_rsi[i] + rank[i]) / 3f64;
+    }
+    result
+}
+
+/// Stochastic Normalization
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * (v - min(v, period)) / (max(v, period) - min(v, period)) in [0, 1]
+///   * 0.5 for a flat window (max == min)
+///   * NAN for the first `period - 1` values and whenever the window contains NAN
+pub fn stoch_normalize(v: &[f64], period: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; v.len()];
+    if period == 0 {
+        return result;
+    }
+    let highest = rolling_max(v, period);
+    let lowest = rolling_min(v, period);
+    let mut last_nan = None;
+    for i in 0 .. v.len() {
+        if v[i].is_nan() {
+            last_nan = Some(i);
+        }
+        if i + 1 < period || last_nan.is_some_and(|j| j + period > i) {
+            continue;
+        }
+        let range = highest[i] - lowest[i];
+        result[i] = if range == 0f64 { 0.5 } else { (v[i] - lowest[i]) / range };
+    }
+    result
+}
+
+/// SMA over the valid part of a series with a leading NAN warm-up
+///
+/// The partial-window warm-up of `sma` is replaced by NAN, so the output is
+/// valid from `first valid index + window_size - 1`.
+fn sma_after_warmup(v: &[f64], window_size: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; v.len()];
+    let start = match v.iter().position(|x| !x.is_nan()) {
+        Some(s) => s,
+        None => return result,
+    };
+    if window_size == 0 || start + window_size > v.len() {
+        return result;
+    }
+    let smoothed = sma(&v[start ..], window_size);
+    result[start + window_size - 1 ..].copy_from_slice(&smoothed[window_size - 1 ..]);
+    result
+}
+
+/// Stochastic RSI
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `rsi_period` - usize
+/// * `stoch_period` - usize
+/// * `k_smooth` - usize
+/// * `d_smooth` - usize
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (%K, %D) in [0, 1]
+///   * raw = stoch_normalize(rsi(close, rsi_period), stoch_period) (0.5 for a flat RSI window)
+///   * %K = sma(raw, k_smooth), %D = sma(%K, d_smooth)
+///   * Warm-up compounds: RSI needs `rsi_period` bars, the stochastic window
+///     `stoch_period - 1` more, and each SMA `k_smooth - 1` / `d_smooth - 1` more
+///     * first valid %K index: `rsi_period + stoch_period + k_smooth - 2`
+///     * first valid %D index: `rsi_period + stoch_period + k_smooth + d_smooth - 3`
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{stoch_rsi, rsi, stoch_normalize, sma};
+///
+/// fn main() {
+///     // Composition: RSI -> stochastic normalization -> SMA smoothing
+///     let close = (0 .. 80).map(|x| 50f64 + (x as f64 * 0.4).sin() * 4f64 + x as f64 * 0.05).collect::<Vec<f64>>();
+///     let (k, _) = stoch_rsi(&close, 5, 5, 3, 3);
+///     let raw = stoch_normalize(&rsi(&close, 5), 5);
+///     let manual_k = sma(&raw[9 ..], 3);
+///     for i in 11 .. 80 {
+///         assert!((k[i] - manual_k[i - 9]).abs() < 1e-12);
+///     }
+/// }
+/// ```
+///
+/// ```
+/// use quantauri::base::stoch_rsi;
+///
+/// fn main() {
+///     let close = (0 .. 100).map(|x| 100f64 + (x as f64 * 0.3).sin() * 10f64).collect::<Vec<f64>>();
+///     let (k, d) = stoch_rsi(&close, 14, 14, 3, 3);
+///     assert!(k[28].is_nan() && !k[29].is_nan());
+///     assert!(d[30].is_nan() && !d[31].is_nan());
+///     assert!(k[29 ..].iter().all(|x| (-1e-12 ..= 1f64 + 1e-12).contains(x)));
+///
+///     // Flat RSI window
+///     let flat = vec![1f64; 40];
+///     let (k, _) = stoch_rsi(&flat, 14, 14, 3, 3);
+///     assert_eq!(k[39], 0.5);
+/// }
+/// ```
+pub fn stoch_rsi(close: &[f64], rsi_period: usize, stoch_period: usize, k_smooth: usize, d_smooth: usize) -> (Vec<f64>, Vec<f64>) {
+    let raw = stoch_normalize(&rsi(close, rsi_period), stoch_period);
+    let k = sma_after_warmup(&raw, k_smooth);
+    let d = sma_after_warmup(&k, d_smooth);
+    (k, d)
+}
+
+/// Shift (lag / lead) a series
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `n` - isize
+///
+/// # Returns
+/// * Vec<f64>
+///   * n > 0: result[i] = v[i - n] (lag, first `n` values are NAN)
+///   * n < 0: result[i] = v[i + |n|] (lead, last `|n|` values are NAN)
+///
+/// # Examples
+/// ```
+/// use quantauri::base::shift;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 3f64];
+///     let lag = shift(&v, 1);
+///     let lead = shift(&v, -1);
+///     assert!(lag[0].is_nan() && lag[1] == 1f64 && lag[2] == 2f64);
+///     assert!(lead[0] == 2f64 && lead[1] == 3f64 && lead[2].is_nan());
+/// }
+/// ```
+pub fn shift(v: &[f64], n: isize) -> Vec<f64> {
+    let len = v.len();
+    let mut result = vec![f64::NAN; len];
+    let k = n.unsigned_abs();
+    if k >= len {
+        return result;
+    }
+    if n >= 0 {
+        result[k ..].copy_from_slice(&v[.. len - k]);
+    } else {
+        result[.. len - k].copy_from_slice(&v[k ..]);
+    }
+    result
+}
+
+/// Detrended Price Oscillator (non-displaced)
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * dpo[i] = v[i - lag] - sma(v, period)[i], lag = period / 2 + 1
+///   * Uses only past data: the price is lagged instead of displacing the SMA,
+///     so the last bar is always defined (use this one for signals)
+///   * NAN for i < max(lag, period - 1) (lagged index underflows or SMA window is incomplete)
+///   * Equals `dpo_centered` shifted forward by `lag` bars
+///
+/// # Examples
+/// ```
+/// use quantauri::base::dpo;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 3f64, 4f64, 5f64, 6f64];
+///     // period 4 -> lag 3
+///     let dpo = dpo(&v, 4);
+///     assert!(dpo[2].is_nan());
+///     assert_eq!(dpo[3], 1f64 - 2.5);
+///     assert_eq!(dpo[5], 3f64 - 4.5);
+/// }
+/// ```
+pub fn dpo(v: &[f64], period: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; v.len()];
+    let ma = match sma_checked(v, period) {
+        Ok(ma) => ma,
+        Err(_) => return result,
+    };
+    let lag = period / 2 + 1;
+    let lagged = shift(v, lag as isize);
+    for i in lag.max(period - 1) .. v.len() {
+        result[i] = lagged[i] - ma[i];
+    }
+    result
+}
+
+/// Detrended Price Oscillator (displaced / centered)
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * dpo[i] = v[i] - sma(v, period)[i + lag], lag = period / 2 + 1
+///   * The SMA is displaced back so each price is compared to the average centered on it
+///   * The last `lag` values need future data and are NAN (chart/cycle analysis only)
+///   * NAN where the displaced SMA window is incomplete (i + lag < period - 1)
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{dpo, dpo_centered};
+///
+/// fn main() {
+///     let v = vec![1f64, 4f64, 2f64, 6f64, 3f64, 8f64, 5f64, 9f64];
+///     let centered = dpo_centered(&v, 4);
+///     let lagged = dpo(&v, 4);
+///     assert!(centered[5].is_nan());
+///     for i in 3 .. 8 {
+///         assert_eq!(lagged[i], centered[i - 3]);
+///     }
+/// }
+/// ```
+pub fn dpo_centered(v: &[f64], period: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; v.len()];
+    let ma = match sma_checked(v, period) {
+        Ok(ma) => ma,
+        Err(_) => return result,
+    };
+    let lag = period / 2 + 1;
+    let displaced = shift(&ma, -(lag as isize));
+    for i in (period - 1).saturating_sub(lag) .. v.len() {
+        result[i] = v[i] - displaced[i];
+    }
+    result
+}
+
+/// Schaff Trend Cycle
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `fast` - usize (default: 23)
+/// * `slow` - usize (default: 50)
+/// * `cycle` - usize (default: 10)
+///
+/// # Returns
+/// * Vec<f64>
+///   * macd = ema(close, fast) - ema(close, slow)
+///   * k1 = 100 * stochastic of macd over `cycle`, f1 = f1_prev + 0.5 * (k1 - f1_prev)
+///   * k2 = 100 * stochastic of f1 over `cycle`, stc = stc_prev + 0.5 * (k2 - stc_prev)
+///   * A flat window keeps the previous stochastic value
+///   * Bounded in [0, 100]
+///   * First `2 * cycle - 2` values are NAN. The EMAs are seeded at bar 0,
+///     so values within roughly `slow` bars of the start are still settling.
+///
+/// # Examples
+/// ```
+/// use quantauri::base::schaff_trend_cycle;
+///
+/// fn main() {
+///     let close = (0 .. 400).map(|x| 100f64 + (x as f64 * 2f64 * std::f64::consts::PI / 50f64).sin() * 10f64).collect::<Vec<f64>>();
+///     let stc = schaff_trend_cycle(&close, 23, 50, 10);
+///     assert!(stc[17].is_nan() && !stc[18].is_nan());
+///
+///     let settled = &stc[100 ..];
+///     assert!(settled.iter().all(|x| (0f64 ..= 100f64).contains(x)));
+///     // Swings between the extremes once per sine period
+///     assert!(settled.iter().any(|&x| x > 90f64));
+///     assert!(settled.iter().any(|&x| x < 10f64));
+///     let crossings = (1 .. settled.len()).filter(|&i| (settled[i-1] - 50f64) * (settled[i] - 50f64) < 0f64).count();
+///     assert!(crossings >= 8);
+///
+///     // Flat series: every stochastic window is flat, so no division by zero
+///     let flat = schaff_trend_cycle(&vec![100f64; 100], 23, 50, 10);
+///     assert!(flat[18 ..].iter().all(|&x| x == 0f64));
+/// }
+/// ```
+pub fn schaff_trend_cycle(close: &[f64], fast: usize, slow: usize, cycle: usize) -> Vec<f64> {
+    schaff_trend_cycle_checked(close, fast, slow, cycle).expect("schaff_trend_cycle: invalid input")
+}
+
+/// Schaff Trend Cycle (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `close` is empty
+/// * `InvalidPeriod` if `fast`, `slow` or `cycle` is zero
+/// * `InvalidParameter` if `fast >= slow`
+/// * `WindowTooLarge` if `2 * cycle - 1` exceeds the input length
+pub fn schaff_trend_cycle_checked(close: &[f64], fast: usize, slow: usize, cycle: usize) -> Result<Vec<f64>, QuantError> {
+    check_nonempty(close)?;
+    if cycle == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    check_window(2 * cycle - 1, close.len())?;
+    let n = close.len();
+    let mut result = vec![f64::NAN; n];
+    let (macd, _) = macd_with_checked(close, fast, slow, 1)?;
+    let macd_max = rolling_max(&macd, cycle);
+    let macd_min = rolling_min(&macd, cycle);
+
+    // First stochastic + smoothing
+    let mut f1 = vec![f64::NAN; n];
+    let mut k1 = 0f64;
+    for i in cycle - 1 .. n {
+        let range = macd_max[i] - macd_min[i];
+        if range > 0f64 {
+            k1 = 100f64 * (macd[i] - macd_min[i]) / range;
+        }
+        f1[i] = if i == cycle - 1 { k1 } else { f1[i-1] + 0.5 * (k1 - f1[i-1]) };
+    }
+
+    // Second stochastic + smoothing
+    let f1_max = rolling_max(&f1, cycle);
+    let f1_min = rolling_min(&f1, cycle);
+    let mut k2 = 0f64;
+    for i in 2 * cycle - 2 .. n {
+        let range = f1_max[i] - f1_min[i];
+        if range > 0f64 {
+            k2 = 100f64 * (f1[i] - f1_min[i]) / range;
+        }
+        result[i] = if i == 2 * cycle - 2 { k2 } else { result[i-1] + 0.5 * (k2 - result[i-1]) };
+    }
+    Ok(result)
+}
+
+/// Weighted Moving Average (linear weights)
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window_size` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * wma[i] = sum_{k=0}^{w-1} (w - k) * v[i-k] / (w (w + 1) / 2)
+///   * First `window_size - 1` values are NAN, NAN whenever the window contains NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::base::wma;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 3f64, 4f64];
+///     let wma = wma(&v, 3);
+///     assert!(wma[1].is_nan());
+///     assert_eq!(wma[2], (1f64 + 4f64 + 9f64) / 6f64);
+/// }
+/// ```
+pub fn wma(v: &[f64], window_size: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; v.len()];
+    if window_size == 0 {
+        return result;
+    }
+    let denom = (window_size * (window_size + 1)) as f64 / 2f64;
+    for i in window_size - 1 .. v.len() {
+        result[i] = (0 .. window_size).map(|k| (window_size - k) as f64 * v[i-k]).sum::<f64>() / denom;
+    }
+    result
+}
+
+/// Hull Moving Average
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window_size` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * hma = wma(2 * wma(v, window_size / 2) - wma(v, window_size), round(sqrt(window_size)))
+///   * First `window_size + round(sqrt(window_size)) - 2` values are NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::base::hma;
+///
+/// fn main() {
+///     // A line passes through with no lag
+///     let v = (0 .. 30).map(|x| 2f64 * x as f64).collect::<Vec<f64>>();
+///     let h = hma(&v, 9);
+///     assert!(h[9].is_nan() && !h[10].is_nan());
+///     assert!(h[10 ..].iter().zip(v[10 ..].iter()).all(|(a, b)| (a - b).abs() < 1e-9));
+/// }
+/// ```
+pub fn hma(v: &[f64], window_size: usize) -> Vec<f64> {
+    if window_size == 0 {
+        return vec![f64::NAN; v.len()];
+    }
+    let half = wma(v, (window_size / 2).max(1));
+    let full = wma(v, window_size);
+    let raw = (0 .. v.len()).map(|i| 2f64 * half[i] - full[i]).collect::<Vec<f64>>();
+    wma(&raw, ((window_size as f64).sqrt().round() as usize).max(1))
+}
+
+/// Triangular Moving Average
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * tma = sma(sma(v, ceil(period / 2)), floor(period / 2) + 1)
+///   * The two windows overlap by one bar, so the weights span exactly `period` bars:
+///     odd 2k+1 -> sma(k+1) of sma(k+1), weights 1, 2, ..., k+1, ..., 2, 1;
+///     even 2k -> sma(k) of sma(k+1), weights 1, 2, ..., k, k, ..., 2, 1
+///   * Partial windows at the start (as `sma`)
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{tma, sma};
+///
+/// fn main() {
+///     // Impulse response = weights
+///     let mut v = vec![0f64; 12];
+///     v[4] = 9f64;
+///     let t = tma(&v, 5);
+///     assert!(t[4 ..= 8].iter().zip([1f64, 2f64, 3f64, 2f64, 1f64]).all(|(a, b)| (a - b).abs() < 1e-12));
+///
+///     v[4] = 6f64;
+///     let t = tma(&v, 4);
+///     assert!(t[4 ..= 7].iter().zip([1f64, 2f64, 2f64, 1f64]).all(|(a, b)| (a - b).abs() < 1e-12));
+///     assert_eq!(t[8], 0f64);
+///
+///     assert_eq!(tma(&v, 6), sma(&sma(&v, 3), 4));
+/// }
+/// ```
+pub fn tma(v: &[f64], period: usize) -> Vec<f64> {
+    tma_checked(v, period).expect("tma: invalid input")
+}
+
+/// Triangular Moving Average (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `period == 0`
+/// * `WindowTooLarge` if `period > v.len()`
+pub fn tma_checked(v: &[f64], period: usize) -> Result<Vec<f64>, QuantError> {
+    check_window(period, v.len())?;
+    sma_checked(&sma_checked(v, period.div_ceil(2))?, period / 2 + 1)
+}
+
+/// Coppock Curve
+///
+/// # Arguments
+/// * `v` - &[f64] (typically monthly closes)
+/// * `roc_long` - usize (default: 14)
+/// * `roc_short` - usize (default: 11)
+/// * `wma_period` - usize (default: 10)
+///
+/// # Returns
+/// * Vec<f64>
+///   * coppock = wma(roc(v, roc_long) + roc(v, roc_short), wma_period)
+///   * First `max(roc_long, roc_short) + wma_period - 1` values are NAN
+///     (23 bars with the defaults)
+///   * A cross from below zero to above zero is the classic long-term buy signal
+///
+/// # Examples
+/// ```
+/// use quantauri::base::coppock;
+///
+/// fn main() {
+///     // Monthly index: 3 years of decline then 3 years of recovery
+///     let v = (0 .. 72).map(|m| {
+///         let t = m as f64;
+///         if m < 36 { 1000f64 - 10f64 * t } else { 640f64 + 15f64 * (t - 36f64) }
+///     }).collect::<Vec<f64>>();
+///     let c = coppock(&v, 14, 11, 10);
+///     assert!(c[22].is_nan() && !c[23].is_nan());
+///     assert!(c[35] < 0f64);
+///
+///     // Buy signal: first cross above zero comes after the trough
+///     let cross = (24 .. 72).find(|&i| c[i-1] < 0f64 && c[i] >= 0f64).unwrap();
+///     assert!(cross > 36);
+/// }
+/// ```
+pub fn coppock(v: &[f64], roc_long: usize, roc_short: usize, wma_period: usize) -> Vec<f64> {
+    let long = roc(v, roc_long);
+    let short = roc(v, roc_short);
+    let combined = long.add_v(&short);
+    wma(&combined, wma_period)
+}
+
+/// Balance of Power
+///
+/// # Arguments
+/// * `open` - &[f64]
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `smooth` - usize (SMA period, 0 or 1 for no smoothing)
+///
+/// # Returns
+/// * Vec<f64>
+///   * bop = (close - open) / (high - low), 0 where high == low
+///   * sma(bop, smooth) if smooth > 1
+///
+/// # Examples
+/// ```
+/// use quantauri::base::balance_of_power;
+///
+/// fn main() {
+///     let open  = vec![10f64, 11f64, 12f64];
+///     let high  = vec![12f64, 13f64, 12f64];
+///     let low   = vec![ 9f64, 10f64, 12f64];
+///     let close = vec![11.5, 10f64, 12f64];
+///     assert_eq!(balance_of_power(&open, &high, &low, &close, 1), vec![0.5, -1f64 / 3f64, 0f64]);
+/// }
+/// ```
+pub fn balance_of_power(open: &[f64], high: &[f64], low: &[f64], close: &[f64], smooth: usize) -> Vec<f64> {
+    balance_of_power_checked(open, high, low, close, smooth).expect("balance_of_power: invalid input")
+}
+
+/// Balance of Power (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `open`, `high` or `low` differ in length from `close`
+/// * `WindowTooLarge` if `smooth > close.len()`
+pub fn balance_of_power_checked(open: &[f64], high: &[f64], low: &[f64], close: &[f64], smooth: usize) -> Result<Vec<f64>, QuantError> {
+    check_len(open.len(), close.len())?;
+    check_len(high.len(), close.len())?;
+    check_len(low.len(), close.len())?;
+    let mut result = vec![0f64; close.len()];
+    for i in 0 .. close.len() {
+        let range = high[i] - low[i];
+        if range != 0f64 {
+            result[i] = (close[i] - open[i]) / range;
+        }
+    }
+    if smooth > 1 {
+        sma_checked(&result, smooth)
+    } else {
+        Ok(result)
+    }
+}
+
+/// Price input for TWAP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwapPrice {
+    /// (open + high + low + close) / 4
+    Ohlc4,
+    /// (high + low + close) / 3
+    Typical,
+}
+
+fn twap_price(open: &[f64], high: &[f64], low: &[f64], close: &[f64], price: TwapPrice) -> Vec<f64> {
+    match price {
+        TwapPrice::Ohlc4 => ohlc4(open, high, low, close),
+        TwapPrice::Typical => typical_price(high, low, close),
+    }
+}
+
+/// Neumaier compensated summation
+#[derive(Debug, Clone, Copy, Default)]
+struct CompensatedSum {
+    sum: f64,
+    c: f64,
+}
+
+impl CompensatedSum {
+    fn add(&mut self, x: f64) {
+        let t = self.sum + x;
+        if self.sum.abs() >= x.abs() {
+            self.c += (self.sum - t) + x;
+        } else {
+            self.c += (x - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    fn value(&self) -> f64 {
+        self.sum + self.c
+    }
+}
+
+/// Whether bar `i` starts a new session (`session_starts` cursor at `next`)
+///
+/// Bar 0 always opens the first session. Indices already passed (duplicates or
+/// out-of-order entries) are skipped, so any list of bar indices is accepted.
+fn session_reset(session_starts: &[usize], next: &mut usize, i: usize) -> bool {
+    let mut reset = false;
+    while *next < session_starts.len() && session_starts[*next] <= i {
+        reset |= session_starts[*next] == i;
+        *next += 1;
+    }
+    reset
+}
+
+/// Time Weighted Average Price
+///
+/// # Arguments
+/// * `open` - &[f64]
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `price` - TwapPrice
+/// * `session_starts` - &[usize] (indices where the average resets, empty for no reset)
+///
+/// # Returns
+/// * Vec<f64>
+///   * Cumulative mean of the selected price since the start of the current session
+///   * Uses compensated summation, so precision holds over very long series
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{twap, TwapPrice};
+///
+/// fn main() {
+///     let n = 200_000;
+///     let close = (0 .. n).map(|i| 30_000f64 + (i as f64 * 0.01).sin() * 0.1 + 1e-3 * (i % 7) as f64).collect::<Vec<f64>>();
+///     let t = twap(&close, &close, &close, &close, TwapPrice::Ohlc4, &[]);
+///     for &i in [0, 999, n - 1].iter() {
+///         let naive = close[..= i].iter().sum::<f64>() / (i + 1) as f64;
+///         assert!((t[i] - naive).abs() / naive < 1e-12);
+///     }
+///
+///     // Session reset
+///     let v = vec![1f64, 3f64, 10f64, 20f64];
+///     let t = twap(&v, &v, &v, &v, TwapPrice::Typical, &[0, 2]);
+///     assert_eq!(t, vec![1f64, 2f64, 10f64, 15f64]);
+/// }
+/// ```
+pub fn twap(open: &[f64], high: &[f64], low: &[f64], close: &[f64], price: TwapPrice, session_starts: &[usize]) -> Vec<f64> {
+    let p = twap_price(open, high, low, close, price);
+    let mut result = vec![0f64; p.len()];
+    let mut acc = CompensatedSum::default();
+    let mut count = 0usize;
+    let mut next_session = 0usize;
+    for i in 0 .. p.len() {
+        if session_reset(session_starts, &mut next_session, i) {
+            acc = CompensatedSum::default();
+            count = 0;
+        }
+        acc.add(p[i]);
+        count += 1;
+        result[i] = acc.value() / count as f64;
+    }
+    result
+}
+
+/// Rolling Time Weighted Average Price
+///
+/// # Arguments
+/// * `open` - &[f64]
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `price` - TwapPrice
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * sma of the selected price over `window` (partial windows at the start, like `sma`)
+pub fn rolling_twap(open: &[f64], high: &[f64], low: &[f64], close: &[f64], price: TwapPrice, window: usize) -> Vec<f64> {
+    sma(&twap_price(open, high, low, close, price), window)
+}
+
+/// Kaufman Adaptive Moving Average
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `er_period` - usize (default: 10)
+/// * `fast` - usize (default: 2)
+/// * `slow` - usize (default: 30)
+///
+/// # Returns
+/// * Vec<f64>
+///   * er = |v[i] - v[i-er_period]| / sum of |v[j] - v[j-1]| over the last `er_period` changes
+///     (0 when there is no movement at all)
+///   * sc = (er * (2/(fast+1) - 2/(slow+1)) + 2/(slow+1))^2
+///   * kama[i] = kama[i-1] + sc * (v[i] - kama[i-1])
+///   * Seeded with kama[er_period-1] = v[er_period-1]; the first `er_period - 1` values are NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::base::kama;
+///
+/// fn main() {
+///     // Perfectly efficient trend: er = 1, sc = (2/3)^2
+///     let v = vec![1f64, 2f64, 3f64, 4f64];
+///     let k = kama(&v, 2, 2, 30);
+///     assert!(k[0].is_nan());
+///     assert_eq!(k[1], 2f64);
+///     assert!((k[2] - (2f64 + 4f64 / 9f64)).abs() < 1e-12);
+///
+///     // Flat input does not divide by zero
+///     let flat = vec![5f64; 20];
+///     assert!(kama(&flat, 10, 2, 30)[10 ..].iter().all(|&x| x == 5f64));
+///
+///     // Inverted smoothing band
+///     assert!(quantauri::base::kama_checked(&flat, 10, 30, 2).is_err());
+/// }
+/// ```
+pub fn kama(v: &[f64], er_period: usize, fast: usize, slow: usize) -> Vec<f64> {
+    kama_checked(v, er_period, fast, slow).expect("kama: invalid input")
+}
+
+/// Kaufman Adaptive Moving Average (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `er_period`, `fast` or `slow` is 0
+/// * `WindowTooLarge` if `er_period > v.len()`
+/// * `InvalidParameter` if `fast >= slow` (the smoothing band would be inverted)
+pub fn kama_checked(v: &[f64], er_period: usize, fast: usize, slow: usize) -> Result<Vec<f64>, QuantError> {
+    check_window(er_period, v.len())?;
+    if fast == 0 || slow == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    if fast >= slow {
+        return Err(QuantError::InvalidParameter(format!("fast period {} must be less than slow period {}", fast, slow)));
+    }
+    let n = v.len();
+    let mut result = vec![f64::NAN; n];
+    let fast_sc = 2f64 / (fast as f64 + 1f64);
+    let slow_sc = 2f64 / (slow as f64 + 1f64);
+    // Running sums of |v[j] - v[j-1]| over the last `er_period` changes, exact 0 when flat
+    let (up, down) = up_down_sums(v, er_period);
+    result[er_period - 1] = v[er_period - 1];
+    for i in er_period .. n {
+        let change = (v[i] - v[i - er_period]).abs();
+        let volatility = up[i] + down[i];
+        // min: rounding residue of the running sums could push er just above 1
+        let er = if volatility == 0f64 { 0f64 } else { (change / volatility).min(1f64) };
+        let sc = (er * (fast_sc - slow_sc) + slow_sc).powi(2);
+        result[i] = result[i-1] + sc * (v[i] - result[i-1]);
+    }
+    Ok(result)
+}
+
+/// Histogram Color
+///
+/// # Arguments
+/// * `v` - &[f64]
+///
+/// # Returns
+/// * Vec<i8>
+///   * +1 if v[i] > v[i-1] (rising), -1 if v[i] < v[i-1] (falling)
+///   * 0 for the first bar, unchanged values, or where either value is NAN
+pub fn histogram_color(v: &[f64]) -> Vec<i8> {
+    let mut result = vec![0i8; v.len()];
+    for i in 1 .. v.len() {
+        if v[i] > v[i-1] {
+            result[i] = 1;
+        } else if v[i] < v[i-1] {
+            result[i] = -1;
+        }
+    }
+    result
+}
+
+/// Awesome Oscillator
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * sma(median_price, 5) - sma(median_price, 34)
+///   * First 33 values are NAN (34-bar window)
+///   * Use `histogram_color` for the rising/falling bars
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{awesome_oscillator, histogram_color};
+///
+/// fn main() {
+///     let high = (0 .. 60).map(|x| x as f64 + 1f64).collect::<Vec<f64>>();
+///     let low = (0 .. 60).map(|x| x as f64).collect::<Vec<f64>>();
+///     let ao = awesome_oscillator(&high, &low);
+///     assert!(ao[32].is_nan());
+///     // Linear trend: sma lag difference is (34 - 5) / 2
+///     assert!((ao[40] - 14.5).abs() < 1e-9);
+///     let color = histogram_color(&ao);
+///     assert_eq!(color[33], 0);
+/// }
+/// ```
+pub fn awesome_oscillator(high: &[f64], low: &[f64]) -> Vec<f64> {
+    awesome_from_median(&median_price(high, low))
+}
+
+fn awesome_from_median(median: &[f64]) -> Vec<f64> {
+    let mut result = vec![f64::NAN; median.len()];
+    if median.len() < 34 {
+        return result;
+    }
+    let fast = sma(median, 5);
+    let slow = sma(median, 34);
+    for i in 33 .. median.len() {
+        result[i] = fast[i] - slow[i];
+    }
+    result
+}
+
+/// Accelerator Oscillator
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * ao - sma(ao, 5), ao = `awesome_oscillator(high, low)`
+///   * First 37 values are NAN (34-bar AO window + 5-bar smoothing)
+///   * Use `histogram_color` for the rising/falling bars
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{awesome_oscillator, accelerator_oscillator, sma};
+///
+/// fn main() {
+///     let high = (0 .. 80).map(|x| 50f64 + (x as f64 * 0.15).cos() * 3f64 + 1f64).collect::<Vec<f64>>();
+///     let low = (0 .. 80).map(|x| 50f64 + (x as f64 * 0.15).cos() * 3f64 - 1f64).collect::<Vec<f64>>();
+///     let ao = awesome_oscillator(&high, &low);
+///     let ac = accelerator_oscillator(&high, &low);
+///
+///     // ac = ao - sma(ao, 5) on the valid part of ao
+///     let ao_sma = sma(&ao[33 ..], 5);
+///     for i in 37 .. 80 {
+///         assert!((ac[i] - (ao[i] - ao_sma[i - 33])).abs() < 1e-9);
+///     }
+/// }
+/// ```
+pub fn accelerator_oscillator(high: &[f64], low: &[f64]) -> Vec<f64> {
+    awesome_accelerator(high, low).1
+}
+
+/// Awesome and Accelerator Oscillator from a single median-price pass
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (ao, ac)
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{awesome_accelerator, awesome_oscillator, accelerator_oscillator, histogram_color};
+///
+/// fn main() {
+///     let high = (0 .. 100).map(|x| 10f64 + (x as f64 * 0.2).sin() + 0.5).collect::<Vec<f64>>();
+///     let low = (0 .. 100).map(|x| 10f64 + (x as f64 * 0.2).sin() - 0.5).collect::<Vec<f64>>();
+///     let (ao, ac) = awesome_accelerator(&high, &low);
+///     assert_eq!(ao[40 ..], awesome_oscillator(&high, &low)[40 ..]);
+///     assert_eq!(ac[40 ..], accelerator_oscillator(&high, &low)[40 ..]);
+///     assert!(ac[36].is_nan() && !ac[37].is_nan());
+///
+///     let ao_color = histogram_color(&ao);
+///     let ac_color = histogram_color(&ac);
+///     assert!(ao_color[34 ..].iter().all(|&c| c != 0));
+///     assert!(ac_color[38 ..].iter().all(|&c| c != 0));
+/// }
+/// ```
+pub fn awesome_accelerator(high: &[f64], low: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let ao = awesome_from_median(&median_price(high, low));
+    let ac_signal = sma_after_warmup(&ao, 5);
+    let ac = ao.sub_v(&ac_signal);
+    (ao, ac)
+}
+
+/// Alligator periods and forward shifts
+///
+/// # Fields
+/// * `jaw_period`, `jaw_shift` - default: 13, 8
+/// * `teeth_period`, `teeth_shift` - default: 8, 5
+/// * `lips_period`, `lips_shift` - default: 5, 3
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlligatorConfig {
+    pub jaw_period: usize,
+    pub jaw_shift: usize,
+    pub teeth_period: usize,
+    pub teeth_shift: usize,
+    pub lips_period: usize,
+    pub lips_shift: usize,
+}
+
+impl Default for AlligatorConfig {
+    fn default() -> Self {
+        AlligatorConfig {
+            jaw_period: 13,
+            jaw_shift: 8,
+            teeth_period: 8,
+            teeth_shift: 5,
+            lips_period: 5,
+            lips_shift: 3,
+        }
+    }
+}
+
+/// Williams Alligator
+///
+/// # Arguments
+/// * `v` - &[f64] (typically `median_price`)
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>, Vec<f64>)
+///   * (jaw, teeth, lips) with the default `AlligatorConfig`
+pub fn alligator(v: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    alligator_with(v, &AlligatorConfig::default())
+}
+
+/// Williams Alligator with custom periods/shifts
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `config` - &AlligatorConfig
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>, Vec<f64>)
+///   * jaw = rma(v, jaw_period) shifted forward by `jaw_shift` (teeth, lips likewise)
+///   * Output has the same length as `v`: the first `shift` values of each line are NAN
+///     and the projected values past the last bar are dropped
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{alligator, rma};
+///
+/// fn main() {
+///     let v = (0 .. 30).map(|x| x as f64).collect::<Vec<f64>>();
+///     let (jaw, teeth, lips) = alligator(&v);
+///     assert_eq!(jaw.len(), 30);
+///     assert!(jaw[7].is_nan() && teeth[4].is_nan() && lips[2].is_nan());
+///     assert_eq!(jaw[20], rma(&v, 13)[12]);
+///     assert_eq!(lips[3], v[0]);
+/// }
+/// ```
+pub fn alligator_with(v: &[f64], config: &AlligatorConfig) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let jaw = shift(&rma(v, config.jaw_period), config.jaw_shift as isize);
+    let teeth = shift(&rma(v, config.teeth_period), config.teeth_shift as isize);
+    let lips = shift(&rma(v, config.lips_period), config.lips_shift as isize);
+    (jaw, teeth, lips)
+}
+
+/// Gator Oscillator
+///
+/// # Arguments
+/// * `v` - &[f64]
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (upper, lower) with the default `AlligatorConfig`
+pub fn gator(v: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    gator_with(v, &AlligatorConfig::default())
+}
+
+/// Gator Oscillator with custom periods/shifts
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `config` - &AlligatorConfig
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * upper = |jaw - teeth|, lower = -|teeth - lips|
+///   * NAN wherever one of the shifted lines is NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{gator, alligator};
+///
+/// fn main() {
+///     let v = (0 .. 40).map(|x| (x as f64 * 0.3).sin()).collect::<Vec<f64>>();
+///     let (upper, lower) = gator(&v);
+///     let (jaw, teeth, lips) = alligator(&v);
+///     assert!(upper[7].is_nan());
+///     assert_eq!(upper[20], (jaw[20] - teeth[20]).abs());
+///     assert_eq!(lower[20], -(teeth[20] - lips[20]).abs());
+/// }
+/// ```
+pub fn gator_with(v: &[f64], config: &AlligatorConfig) -> (Vec<f64>, Vec<f64>) {
+    let (jaw, teeth, lips) = alligator_with(v, config);
+    let upper = (0 .. v.len()).map(|i| (jaw[i] - teeth[i]).abs()).collect();
+    let lower = (0 .. v.len()).map(|i| -(teeth[i] - lips[i]).abs()).collect();
+    (upper, lower)
+}
+
+/// Average True Range
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * rma(true_range(high, low, close), period)
+pub fn atr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Vec<f64> {
+    rma(&true_range(high, low, close), period)
+}
+
+/// Chaikin Volatility
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `ema_period` - usize (default: 10)
+/// * `roc_period` - usize (default: 10)
+///
+/// # Returns
+/// * Vec<f64>
+///   * roc(ema(high - low, ema_period), roc_period), in percent
+///   * Positive when the trading range expands, negative when it contracts
+///   * First `roc_period` values are NAN, NAN where the earlier smoothed range is 0
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{chaikin_volatility, ema};
+///
+/// fn main() {
+///     let high = vec![10f64, 11f64, 13f64, 14f64];
+///     let low = vec![10f64, 10f64, 10f64, 10f64];
+///     let cv = chaikin_volatility(&high, &low, 2, 1);
+///     let smoothed = ema(&[0f64, 1f64, 3f64, 4f64], 2);
+///     assert!(cv[0].is_nan());
+///     assert!(cv[1].is_nan()); // smoothed range was 0
+///     assert!((cv[3] - 100f64 * (smoothed[3] / smoothed[2] - 1f64)).abs() < 1e-12);
+/// }
+/// ```
+pub fn chaikin_volatility(high: &[f64], low: &[f64], ema_period: usize, roc_period: usize) -> Vec<f64> {
+    let range = (0 .. high.len()).map(|i| high[i] - low[i]).collect::<Vec<f64>>();
+    if range.is_empty() {
+        return range;
+    }
+    roc(&ema(&range, ema_period), roc_period)
+}
+
+/// Mass Index
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `ema_period` - usize (default: 9)
+/// * `sum_period` - usize (default: 25)
+///
+/// # Returns
+/// * Vec<f64>
+///   * range = high - low, single = ema(range, ema_period), double = ema(single, ema_period)
+///   * mass = sum of single / double over the trailing `sum_period` bars
+///   * The ratio is 1 where double == 0 (no range at all)
+///   * First `sum_period - 1` values are NAN. Both EMAs are seeded at bar 0 and
+///     need roughly `2 * ema_period` more bars to settle, so the first
+///     `sum_period + 2 * ema_period` values (43 with defaults) should be treated as warm-up.
+///
+/// # Examples
+/// ```
+/// use quantauri::base::mass_index;
+///
+/// fn main() {
+///     // Constant range: single == double, every ratio is 1
+///     let high = vec![11f64; 40];
+///     let low = vec![10f64; 40];
+///     let mi = mass_index(&high, &low, 9, 25);
+///     assert!(mi[23].is_nan());
+///     assert!((mi[30] - 25f64).abs() < 1e-9);
+///
+///     // Zero range does not divide by zero
+///     let mi = mass_index(&high, &high, 9, 25);
+///     assert_eq!(mi[30], 25f64);
+/// }
+/// ```
+pub fn mass_index(high: &[f64], low: &[f64], ema_period: usize, sum_period: usize) -> Vec<f64> {
+    let n = high.len();
+    let mut result = vec![f64::NAN; n];
+    if n == 0 || sum_period == 0 {
+        return result;
+    }
+    let range = (0 .. n).map(|i| high[i] - low[i]).collect::<Vec<f64>>();
+    let single = ema(&range, ema_period);
+    let double = ema(&single, ema_period);
+    let ratio = (0 .. n).map(|i| if double[i] == 0f64 { 1f64 } else { single[i] / double[i] }).collect::<Vec<f64>>();
+    let sum = rolling_sum(&ratio, sum_period);
+    if sum_period <= n {
+        result[sum_period - 1 ..].copy_from_slice(&sum[sum_period - 1 ..]);
+    }
+    result
+}
+
+/// Ease of Movement
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `volume` - &[f64]
+/// * `period` - usize (default: 14)
+/// * `scale` - f64 (volume divisor, e.g. 1e6 or 1e8 depending on the instrument)
+///
+/// # Returns
+/// * Vec<f64>
+///   * distance = (high + low) / 2 - (prev high + prev low) / 2
+///   * box ratio = (volume / scale) / (high - low)
+///   * emv = distance / box ratio, 0 where high == low or volume == 0
+///   * sma(emv, period), the first `period` values are NAN (emv starts at bar 1)
+///
+/// # Examples
+/// ```
+/// use quantauri::base::ease_of_movement;
+///
+/// fn main() {
+///     let high = vec![10f64, 12f64, 14f64, 14f64];
+///     let low = vec![8f64, 10f64, 12f64, 14f64];
+///     let volume = vec![1e6, 2e6, 0f64, 1e6];
+///     let emv = ease_of_movement(&high, &low, &volume, 1, 1e6);
+///     assert!(emv[0].is_nan());
+///     assert_eq!(emv[1], 2f64 / (2f64 / 2f64));
+///     assert_eq!(emv[2], 0f64); // zero volume
+///     assert_eq!(emv[3], 0f64); // zero range
+///
+///     assert!(quantauri::base::ease_of_movement_checked(&high, &low, &volume[.. 3], 1, 1e6).is_err());
+/// }
+/// ```
+pub fn ease_of_movement(high: &[f64], low: &[f64], volume: &[f64], period: usize, scale: f64) -> Vec<f64> {
+    ease_of_movement_checked(high, low, volume, period, scale).expect("ease_of_movement: invalid input")
+}
+
+/// Ease of Movement (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `high`, `low` and `volume` differ in length
+/// * `InvalidPeriod` if `period == 0`
+pub fn ease_of_movement_checked(high: &[f64], low: &[f64], volume: &[f64], period: usize, scale: f64) -> Result<Vec<f64>, QuantError> {
+    check_len(high.len(), low.len())?;
+    check_len(high.len(), volume.len())?;
+    if period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    let n = high.len();
+    let mut emv = vec![f64::NAN; n];
+    for i in 1 .. n {
+        let distance = (high[i] + low[i]) / 2f64 - (high[i-1] + low[i-1]) / 2f64;
+        let range = high[i] - low[i];
+        emv[i] = if range == 0f64 || volume[i] == 0f64 {
+            0f64
+        } else {
+            distance / ((volume[i] / scale) / range)
+        };
+    }
+    Ok(sma_after_warmup(&emv, period))
+}
+
+// =============================================================================
+// Elder Indicators
+// =============================================================================
+/// Force Index
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `volume` - &[f64]
+/// * `period` - usize (default: 13)
+///
+/// # Returns
+/// * Vec<f64>
+///   * ema((close[i] - close[i-1]) * volume[i], period)
+///   * force[0] is NAN (no previous close), the EMA is seeded at bar 1
+///
+/// # Examples
+/// ```
+/// use quantauri::base::force_index;
+///
+/// fn main() {
+///     let close = vec![10f64, 11f64, 10.5];
+///     let volume = vec![100f64, 200f64, 400f64];
+///     let fi = force_index(&close, &volume, 1);
+///     assert!(fi[0].is_nan());
+///     assert_eq!(fi[1 ..], [200f64, -200f64]);
+///
+///     // A short volume column is an error, not an out-of-bounds panic
+///     assert!(quantauri::base::force_index_checked(&close, &volume[.. 2], 1).is_err());
+/// }
+/// ```
+pub fn force_index(close: &[f64], volume: &[f64], period: usize) -> Vec<f64> {
+    force_index_checked(close, volume, period).expect("force_index: invalid input")
+}
+
+/// Force Index (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `close` and `volume` differ in length
+/// * `InvalidPeriod` if `period == 0`
+pub fn force_index_checked(close: &[f64], volume: &[f64], period: usize) -> Result<Vec<f64>, QuantError> {
+    check_len(close.len(), volume.len())?;
+    if period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    let n = close.len();
+    let mut result = vec![f64::NAN; n];
+    if n < 2 {
+        return Ok(result);
+    }
+    let raw = (1 .. n).map(|i| (close[i] - close[i-1]) * volume[i]).collect::<Vec<f64>>();
+    result[1 ..].copy_from_slice(&ema_checked(&raw, period)?);
+    Ok(result)
+}
+
+/// Elder Ray (Bull Power / Bear Power)
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `period` - usize (default: 13)
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (bull, bear)
+///   * bull = high - ema(close, period)
+///   * bear = low - ema(close, period)
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{elder_ray, ema};
+///
+/// fn main() {
+///     let close = vec![10f64, 11f64, 12f64];
+///     let high = vec![10.5, 11.5, 12.5];
+///     let low = vec![9.5, 10.5, 11.5];
+///     let (bull, bear) = elder_ray(&high, &low, &close, 2);
+///     let e = ema(&close, 2);
+///     assert_eq!(bull[2], 12.5 - e[2]);
+///     assert_eq!(bear[2], 11.5 - e[2]);
+///     assert!(quantauri::base::elder_ray_checked(&high, &low[.. 2], &close, 2).is_err());
+/// }
+/// ```
+pub fn elder_ray(high: &[f64], low: &[f64], close: &[f64], period: usize) -> (Vec<f64>, Vec<f64>) {
+    elder_ray_checked(high, low, close, period).expect("elder_ray: invalid input")
+}
+
+/// Elder Ray (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `high`, `low` and `close` differ in length
+/// * Errors of `ema_checked`
+pub fn elder_ray_checked(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Result<(Vec<f64>, Vec<f64>), QuantError> {
+    check_len(close.len(), high.len())?;
+    check_len(close.len(), low.len())?;
+    let e = ema_checked(close, period)?;
+    let bull = (0 .. close.len()).map(|i| high[i] - e[i]).collect();
+    let bear = (0 .. close.len()).map(|i| low[i] - e[i]).collect();
+    Ok((bull, bear))
+}
+
+// =============================================================================
+// Trailing Stops
+// =============================================================================
+/// Gann HiLo Activator
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64] (the switching rule compares close with the averages)
+/// * `period` - usize
+///
+/// # Returns
+/// * (Vec<f64>, Vec<i8>)
+///   * (line, direction)
+///   * direction = +1 (uptrend) once close > sma(high, period) of the previous bar,
+///     -1 (downtrend) once close < sma(low, period) of the previous bar,
+///     otherwise the previous direction is kept
+///   * line = sma(low, period) in an uptrend (stop below price),
+///     sma(high, period) in a downtrend (stop above price)
+///   * First `period` bars and bars before the first switch: line NAN, direction 0
+///
+/// # Examples
+/// ```
+/// use quantauri::base::hilo_activator;
+///
+/// fn main() {
+///     // Rally, then a sell-off
+///     let close = vec![10f64, 10f64, 11f64, 12f64, 13f64, 12f64, 10f64, 8f64, 7f64];
+///     let high = close.iter().map(|x| x + 0.5).collect::<Vec<f64>>();
+///     let low = close.iter().map(|x| x - 0.5).collect::<Vec<f64>>();
+///     let (line, dir) = hilo_activator(&high, &low, &close, 2);
+///     assert_eq!(dir, vec![0, 0, 1, 1, 1, 1, -1, -1, -1]);
+///     assert_eq!(line[3], (10.5 + 11.5) / 2f64); // sma(low) of bar 3
+///     assert_eq!(line[6], (12.5 + 10.5) / 2f64); // sma(high) of bar 6
+///     assert!(line[1].is_nan());
+/// }
+/// ```
+pub fn hilo_activator(high: &[f64], low: &[f64], close: &[f64], period: usize) -> (Vec<f64>, Vec<i8>) {
+    let n = close.len();
+    let mut line = vec![f64::NAN; n];
+    let mut direction = vec![0i8; n];
+    if period == 0 || period >= n {
+        return (line, direction);
+    }
+    let hi_avg = sma(high, period);
+    let lo_avg = sma(low, period);
+    for i in period .. n {
+        direction[i] = if close[i] > hi_avg[i-1] {
+            1
+        } else if close[i] < lo_avg[i-1] {
+            -1
+        } else {
+            direction[i-1]
+        };
+        line[i] = match direction[i] {
+            1 => lo_avg[i],
+            -1 => hi_avg[i],
+            _ => f64::NAN,
+        };
+    }
+    (line, direction)
+}
+
+/// Chande Kroll Stop
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `atr_period` - usize (p, default: 10)
+/// * `atr_mult` - f64 (x, default: 1)
+/// * `stop_period` - usize (q, default: 9)
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (long_stop, short_stop)
+///   * long_stop = highest(highest(high, p) - x * atr(p), q): trails below price for long positions
+///   * short_stop = lowest(lowest(low, p) + x * atr(p), q): trails above price for short positions
+///   * Every window is trailing, so bar i uses bars up to i only
+///   * First `p + q - 2` values are NAN (both windows full)
+///
+/// # Description
+/// Some charting packages label the two lines the other way round ("stop short" for the
+/// highest of the high-based stops); the values are the same.
+///
+/// # Examples
+/// ```
+/// use quantauri::base::chande_kroll_stop;
+///
+/// fn main() {
+///     // Strong uptrend: true range 1.5 on every bar
+///     let close = (0 .. 40).map(|x| 100f64 + x as f64).collect::<Vec<f64>>();
+///     let high = close.iter().map(|x| x + 0.5).collect::<Vec<f64>>();
+///     let low = close.iter().map(|x| x - 0.5).collect::<Vec<f64>>();
+///     let (long, short) = chande_kroll_stop(&high, &low, &close, 10, 1f64, 9);
+///     assert!(long[16].is_nan() && !long[17].is_nan());
+///     assert!((17 .. 40).all(|i| close[i] > long[i]));
+///     assert!((18 .. 40).all(|i| long[i] > long[i-1])); // ratchets up with the trend
+///     assert!(short[39] < close[39]);                      // price never falls below it
+///
+///     // No look-ahead: changing the future leaves the past alone
+///     let mut crash = close.clone();
+///     crash[30 ..].iter_mut().for_each(|x| *x -= 50f64);
+///     let crash_high = crash.iter().map(|x| x + 0.5).collect::<Vec<f64>>();
+///     let crash_low = crash.iter().map(|x| x - 0.5).collect::<Vec<f64>>();
+///     let (long2, _) = chande_kroll_stop(&crash_high, &crash_low, &crash, 10, 1f64, 9);
+///     assert_eq!(long[17 .. 30], long2[17 .. 30]);
+/// }
+/// ```
+pub fn chande_kroll_stop(high: &[f64], low: &[f64], close: &[f64], atr_period: usize, atr_mult: f64, stop_period: usize) -> (Vec<f64>, Vec<f64>) {
+    chande_kroll_stop_checked(high, low, close, atr_period, atr_mult, stop_period).expect("chande_kroll_stop: invalid input")
+}
+
+/// Chande Kroll Stop (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `atr_period` or `stop_period` is 0
+/// * `LengthMismatch` if the inputs differ in length
+pub fn chande_kroll_stop_checked(high: &[f64], low: &[f64], close: &[f64], atr_period: usize, atr_mult: f64, stop_period: usize) -> Result<(Vec<f64>, Vec<f64>), QuantError> {
+    if atr_period == 0 || stop_period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    let n = close.len();
+    check_len(n, high.len())?;
+    check_len(n, low.len())?;
+    let range = atr(high, low, close, atr_period);
+    let hh = rolling_max(high, atr_period);
+    let ll = rolling_min(low, atr_period);
+    let first_long = (0 .. n).map(|i| hh[i] - atr_mult * range[i]).collect::<Vec<f64>>();
+    let first_short = (0 .. n).map(|i| ll[i] + atr_mult * range[i]).collect::<Vec<f64>>();
+    let mut long = rolling_max(&first_long, stop_period);
+    let mut short = rolling_min(&first_short, stop_period);
+    let warmup = (atr_period + stop_period - 2).min(n);
+    long[.. warmup].iter_mut().for_each(|x| *x = f64::NAN);
+    short[.. warmup].iter_mut().for_each(|x| *x = f64::NAN);
+    Ok((long, short))
+}
+
+// =============================================================================
+// Double-smoothed Momentum
+// =============================================================================
+/// True Strength Index
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `long` - usize (default: 25)
+/// * `short` - usize (default: 13)
+///
+/// # Returns
+/// * Vec<f64>
+///   * 100 * ema(ema(m, long), short) / ema(ema(|m|, long), short), with m[i] = v[i] - v[i-1]
+///   * tsi[0] is NAN (no momentum yet), the EMAs are seeded at bar 1
+///   * 0 where the smoothed absolute momentum is 0 (flat series)
+///
+/// # Examples
+/// ```
+/// use quantauri::base::tsi;
+///
+/// fn main() {
+///     let up = (0 .. 50).map(|x| x as f64).collect::<Vec<f64>>();
+///     let t = tsi(&up, 25, 13);
+///     assert!(t[0].is_nan());
+///     assert!(t[1 ..].iter().all(|x| (x - 100f64).abs() < 1e-9));
+///
+///     let flat = vec![3f64; 10];
+///     assert!(tsi(&flat, 25, 13)[1 ..].iter().all(|&x| x == 0f64));
+/// }
+/// ```
+pub fn tsi(v: &[f64], long: usize, short: usize) -> Vec<f64> {
+    let n = v.len();
+    let mut result = vec![f64::NAN; n];
+    if n < 2 {
+        return result;
+    }
+    let momentum = (1 .. n).map(|i| v[i] - v[i-1]).collect::<Vec<f64>>();
+    let abs_momentum = momentum.iter().map(|x| x.abs()).collect::<Vec<f64>>();
+    let num = ema(&ema(&momentum, long), short);
+    let den = ema(&ema(&abs_momentum, long), short);
+    for i in 1 .. n {
+        result[i] = if den[i-1] == 0f64 { 0f64 } else { 100f64 * num[i-1] / den[i-1] };
+    }
+    result
+}
+
+/// True Strength Index with signal line
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `long` - usize (default: 25)
+/// * `short` - usize (default: 13)
+/// * `signal` - usize (default: 7)
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (tsi, ema(tsi, signal)), the signal is NAN at bar 0 and seeded at bar 1
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{tsi, tsi_with_signal, ema};
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 1.5, 3f64, 2.5, 4f64];
+///     let (t, s) = tsi_with_signal(&v, 25, 13, 7);
+///     assert_eq!(t[1 ..], tsi(&v, 25, 13)[1 ..]);
+///     assert!(s[0].is_nan());
+///     assert_eq!(s[1 ..], ema(&t[1 ..], 7)[..]);
+/// }
+/// ```
+pub fn tsi_with_signal(v: &[f64], long: usize, short: usize, signal: usize) -> (Vec<f64>, Vec<f64>) {
+    let t = tsi(v, long, short);
+    let mut s = vec![f64::NAN; t.len()];
+    if t.len() > 1 {
+        s[1 ..].copy_from_slice(&ema(&t[1 ..], signal));
+    }
+    (t, s)
+}
+
+// =============================================================================
+// Volume Indicators
+// =============================================================================
+/// Price Volume Trend
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `volume` - &[f64] (same length as `close`)
+///
+/// # Returns
+/// * Vec<f64> (same length as the inputs, no warm-up)
+///   * pvt[i] = pvt[i-1] + volume[i] * (close[i] - close[i-1]) / close[i-1], pvt[0] = 0
+///   * A zero previous close adds nothing (the line carries forward)
+///
+/// # Examples
+/// ```
+/// use quantauri::base::pvt;
+///
+/// fn main() {
+///     let close = vec![10f64, 11f64, 0f64, 5f64];
+///     let volume = vec![100f64, 200f64, 300f64, 400f64];
+///     let p = pvt(&close, &volume);
+///     assert_eq!(p[0], 0f64);
+///     assert!((p[1] - 20f64).abs() < 1e-12);
+///     assert!((p[2] - (20f64 - 300f64)).abs() < 1e-12);
+///     assert_eq!(p[3], p[2]); // previous close is 0
+///
+///     assert!(quantauri::base::pvt_checked(&close, &volume[.. 3]).is_err());
+/// }
+/// ```
+pub fn pvt(close: &[f64], volume: &[f64]) -> Vec<f64> {
+    pvt_checked(close, volume).expect("pvt: invalid input")
+}
+
+/// Price Volume Trend (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `close` and `volume` differ in length
+pub fn pvt_checked(close: &[f64], volume: &[f64]) -> Result<Vec<f64>, QuantError> {
+    check_len(close.len(), volume.len())?;
+    let mut result = vec![0f64; close.len()];
+    for i in 1 .. close.len() {
+        let change = if close[i-1] == 0f64 {
+            0f64
+        } else {
+            volume[i] * (close[i] - close[i-1]) / close[i-1]
+        };
+        result[i] = result[i-1] + change;
+    }
+    Ok(result)
+}
+
+/// Positive Volume Index
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `volume` - &[f64]
+///
+/// # Returns
+/// * Vec<f64> (same length as `close`, no warm-up)
+///   * pvi[0] = 1000
+///   * pvi[i] = pvi[i-1] * close[i] / close[i-1] if volume[i] > volume[i-1], else pvi[i-1]
+///   * A zero previous close leaves the index unchanged
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{pvi, nvi};
+///
+/// fn main() {
+///     // Volume alternates up / down: PVI moves on bars 1, 3 and NVI on bars 2, 4
+///     let close = vec![10f64, 11f64, 12f64, 11f64, 12f64];
+///     let volume = vec![100f64, 200f64, 100f64, 200f64, 100f64];
+///     let p = pvi(&close, &volume);
+///     let n = nvi(&close, &volume);
+///
+///     let expect_p = [1000f64, 1100f64, 1100f64, 1100f64 * 11f64 / 12f64, 1100f64 * 11f64 / 12f64];
+///     let expect_n = [1000f64, 1000f64, 1000f64 * 12f64 / 11f64, 1000f64 * 12f64 / 11f64, 1000f64 * 144f64 / 121f64];
+///     for i in 0 .. 5 {
+///         assert!((p[i] - expect_p[i]).abs() < 1e-9);
+///         assert!((n[i] - expect_n[i]).abs() < 1e-9);
+///     }
+///
+///     // Equal volume: neither index moves
+///     let flat = pvi(&close, &[100f64; 5]);
+///     assert!(flat.iter().all(|&x| x == 1000f64));
+///
+///     // Zero previous close: no update
+///     assert_eq!(pvi(&[0f64, 5f64], &[1f64, 2f64]), vec![1000f64, 1000f64]);
+/// }
+/// ```
+pub fn pvi(close: &[f64], volume: &[f64]) -> Vec<f64> {
+    volume_index(close, volume, |v, prev| v > prev)
+}
+
+/// Negative Volume Index
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `volume` - &[f64]
+///
+/// # Returns
+/// * Vec<f64> (same length as `close`, no warm-up)
+///   * nvi[0] = 1000
+///   * nvi[i] = nvi[i-1] * close[i] / close[i-1] if volume[i] < volume[i-1], else nvi[i-1]
+///   * A zero previous close leaves the index unchanged
+pub fn nvi(close: &[f64], volume: &[f64]) -> Vec<f64> {
+    volume_index(close, volume, |v, prev| v < prev)
+}
+
+/// Volume index seeded at 1000, compounding the price change on bars where `update(volume, prev)`
+fn volume_index(close: &[f64], volume: &[f64], update: impl Fn(f64, f64) -> bool) -> Vec<f64> {
+    let n = close.len();
+    let mut result = vec![1000f64; n];
+    for i in 1 .. n {
+        result[i] = if update(volume[i], volume[i-1]) && close[i-1] != 0f64 {
+            result[i-1] * close[i] / close[i-1]
+        } else {
+            result[i-1]
+        };
+    }
+    result
+}
+
+/// Negative Volume Index signal
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `volume` - &[f64]
+/// * `period` - usize (default: 255)
+///
+/// # Returns
+/// * Vec<f64>
+///   * 1 if nvi > ema(nvi, period) (bullish), -1 if below, 0 if equal
+///
+/// # Description
+/// Low-volume days are read as the "smart money" days, so an NVI above its long EMA
+/// suggests a bull market.
+///
+/// # Examples
+/// ```
+/// use quantauri::base::nvi_signal;
+///
+/// fn main() {
+///     // Prices rise on every quiet bar
+///     let close = (0 .. 10).map(|x| 100f64 + x as f64).collect::<Vec<f64>>();
+///     let volume = (0 .. 10).map(|x| if x % 2 == 0 { 100f64 } else { 50f64 }).collect::<Vec<f64>>();
+///     let s = nvi_signal(&close, &volume, 255);
+///     assert_eq!(s[0], 0f64);
+///     assert!(s[1 ..].iter().all(|&x| x == 1f64));
+/// }
+/// ```
+pub fn nvi_signal(close: &[f64], volume: &[f64], period: usize) -> Vec<f64> {
+    let index = nvi(close, volume);
+    if index.is_empty() {
+        return index;
+    }
+    let trend = ema(&index, period);
+    index.iter().zip(trend.iter()).map(|(&x, &t)| {
+        if x > t {
+            1f64
+        } else if x < t {
+            -1f64
+        } else {
+            0f64
+        }
+    }).collect()
+}
+
+/// Volume Oscillator
+///
+/// # Arguments
+/// * `volume` - &[f64]
+/// * `fast` - usize (default: 5)
+/// * `slow` - usize (default: 10)
+/// * `as_percent` - bool
+///
+/// # Returns
+/// * Vec<f64>
+///   * `volume_oscillator_with(volume, fast, slow, MaKind::Sma, as_percent)`
+///
+/// # Examples
+/// ```
+/// use quantauri::base::volume_oscillator;
+///
+/// fn main() {
+///     let volume = vec![100f64, 100f64, 200f64, 400f64, 0f64, 0f64, 0f64, 0f64];
+///     let diff = volume_oscillator(&volume, 2, 4, false);
+///     // fast sma = 300, slow sma = 200
+///     assert_eq!(diff[3], 100f64);
+///
+///     let pct = volume_oscillator(&volume, 2, 4, true);
+///     assert_eq!(pct[3], 50f64);
+///     // A window without volume: 0 instead of 0 / 0
+///     assert_eq!(pct[7], 0f64);
+/// }
+/// ```
+pub fn volume_oscillator(volume: &[f64], fast: usize, slow: usize, as_percent: bool) -> Vec<f64> {
+    volume_oscillator_with(volume, fast, slow, MaKind::Sma, as_percent)
+}
+
+/// Volume Oscillator with a selectable moving average
+///
+/// # Arguments
+/// * `volume` - &[f64]
+/// * `fast` - usize
+/// * `slow` - usize
+/// * `kind` - MaKind
+/// * `as_percent` - bool
+///
+/// # Returns
+/// * Vec<f64>
+///   * fast_ma - slow_ma (`price_oscillator` of volume), or 100 * (fast_ma - slow_ma) / slow_ma
+///     if `as_percent`
+///   * Percent form: 0 where the trailing `slow` bars have no volume at all (or slow_ma is 0)
+///   * Warm-up of the chosen `MaKind`
+pub fn volume_oscillator_with(volume: &[f64], fast: usize, slow: usize, kind: MaKind, as_percent: bool) -> Vec<f64> {
+    volume_oscillator_checked(volume, fast, slow, kind, as_percent).expect("volume_oscillator: invalid input")
+}
+
+/// Volume Oscillator (fallible)
+///
+/// # Errors
+/// * Errors of `price_oscillator_checked`
+pub fn volume_oscillator_checked(volume: &[f64], fast: usize, slow: usize, kind: MaKind, as_percent: bool) -> Result<Vec<f64>, QuantError> {
+    let diff = price_oscillator_checked(volume, fast, slow, kind, true)?;
+    if !as_percent {
+        return Ok(diff);
+    }
+    let slow_ma = moving_average_checked(volume, slow, kind)?;
+    // Count of bars with volume in each slow window (exact, unlike the MA itself)
+    let active = rolling_sum(&volume.iter().map(|&x| if x != 0f64 { 1f64 } else { 0f64 }).collect::<Vec<f64>>(), slow);
+    Ok((0 .. volume.len()).map(|i| {
+        if diff[i].is_nan() {
+            f64::NAN
+        } else if active[i] == 0f64 || slow_ma[i] == 0f64 {
+            0f64
+        } else {
+            100f64 * diff[i] / slow_ma[i]
+        }
+    }).collect())
+}
+
+/// Output of `vwap_bands`
+///
+/// # Fields
+/// * `vwap` - session VWAP
+/// * `upper`, `lower` - one band per multiplier, in the order given
+#[derive(Debug, Clone, PartialEq)]
+pub struct VwapBands {
+    pub vwap: Vec<f64>,
+    pub upper: Vec<Vec<f64>>,
+    pub lower: Vec<Vec<f64>>,
+}
+
+/// VWAP with standard deviation bands
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `volume` - &[f64]
+/// * `session_starts` - &[usize] (indices where the accumulation resets, empty for no reset; as `twap`)
+/// * `multipliers` - &[f64] (e.g. `[1, 2, 3]`)
+///
+/// # Returns
+/// * VwapBands
+///   * p = typical price, accumulated from the start of the bar's session
+///   * vwap = sum(volume * p) / sum(volume)
+///   * sd = sqrt(sum(volume * (p - vwap)^2) / sum(volume)), weighted by volume exactly as
+///     the VWAP (updated in one pass with West's weighted algorithm, no cancellation)
+///   * upper[k] = vwap + multipliers[k] * sd, lower[k] = vwap - multipliers[k] * sd
+///   * NAN while the session has no volume yet; NAN prices or volumes are skipped
+///
+/// # Examples
+/// ```
+/// use quantauri::base::vwap_bands;
+///
+/// fn main() {
+///     // high = low = close, so p = close
+///     let p = vec![10f64, 12f64, 20f64, 22f64];
+///     let volume = vec![1f64, 3f64, 2f64, 2f64];
+///     let b = vwap_bands(&p, &p, &p, &volume, &[0, 2], &[1f64, 2f64]);
+///
+///     assert_eq!(b.vwap[1], 11.5);
+///     // sd = sqrt((1 * 1.5^2 + 3 * 0.5^2) / 4) = sqrt(0.75)
+///     let sd = 0.75f64.sqrt();
+///     assert!((b.upper[0][1] - (11.5 + sd)).abs() < 1e-12);
+///     assert!((b.lower[1][1] - (11.5 - 2f64 * sd)).abs() < 1e-12);
+///
+///     // New session: the first bar is its own VWAP, with zero width
+///     assert_eq!(b.vwap[2], 20f64);
+///     assert_eq!(b.upper[1][2], 20f64);
+///     assert_eq!(b.vwap[3], 21f64);
+///
+///     // Same session convention as `twap`: empty means one session from bar 0
+///     assert_eq!(vwap_bands(&p, &p, &p, &volume, &[], &[]).vwap, vwap_bands(&p, &p, &p, &volume, &[0], &[]).vwap);
+/// }
+/// ```
+pub fn vwap_bands(high: &[f64], low: &[f64], close: &[f64], volume: &[f64], session_starts: &[usize], multipliers: &[f64]) -> VwapBands {
+    vwap_bands_checked(high, low, close, volume, session_starts, multipliers).expect("vwap_bands: invalid input")
+}
+
+/// VWAP with standard deviation bands (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if the price and volume inputs differ in length
+pub fn vwap_bands_checked(high: &[f64], low: &[f64], close: &[f64], volume: &[f64], session_starts: &[usize], multipliers: &[f64]) -> Result<VwapBands, QuantError> {
+    let n = close.len();
+    check_len(n, volume.len())?;
+    let price = typical_price_checked(high, low, close)?;
+    let mut vwap = vec![f64::NAN; n];
+    let mut sd = vec![f64::NAN; n];
+    let mut next = 0usize;
+    let (mut weight, mut mean, mut scatter) = (0f64, 0f64, 0f64);
+    for i in 0 .. n {
+        if session_reset(session_starts, &mut next, i) {
+            (weight, mean, scatter) = (0f64, 0f64, 0f64);
+        }
+        let (x, w) = (price[i], volume[i]);
+        if !x.is_nan() && !w.is_nan() && w > 0f64 {
+            weight += w;
+            let delta = x - mean;
+            mean += w / weight * delta;
+            scatter += w * delta * (x - mean);
+        }
+        if weight > 0f64 {
+            vwap[i] = mean;
+            sd[i] = (scatter.max(0f64) / weight).sqrt();
+        }
+    }
+    let band = |sign: f64, m: f64| (0 .. n).map(|i| vwap[i] + sign * m * sd[i]).collect::<Vec<f64>>();
+    let upper = multipliers.iter().map(|&m| band(1f64, m)).collect();
+    let lower = multipliers.iter().map(|&m| band(-1f64, m)).collect();
+    Ok(VwapBands { vwap, upper, lower })
+}
+
+/// `mfi_bw` state: MFI up, volume up (trend is strengthening)
+pub const MFI_GREEN: i8 = 0;
+/// `mfi_bw` state: MFI down, volume down (interest is fading)
+pub const MFI_FADE: i8 = 1;
+/// `mfi_bw` state: MFI up, volume down (move without participation)
+pub const MFI_FAKE: i8 = 2;
+/// `mfi_bw` state: MFI down, volume up (battle before a breakout)
+pub const MFI_SQUAT: i8 = 3;
+/// `mfi_bw` state: undefined (first bar, zero or NAN volume on this or the prior bar)
+pub const MFI_NONE: i8 = -1;
+
+/// Market Facilitation Index (Bill Williams)
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `volume` - &[f64]
+///
+/// # Returns
+/// * (Vec<f64>, Vec<i8>)
+///   * mfi = (high - low) / volume, NAN where volume is 0
+///   * state, comparing mfi and volume with the prior bar ("up" means strictly greater,
+///     an unchanged value counts as down):
+///     * `MFI_GREEN` (0): mfi up, volume up
+///     * `MFI_FADE` (1): mfi down, volume down
+///     * `MFI_FAKE` (2): mfi up, volume down
+///     * `MFI_SQUAT` (3): mfi down, volume up
+///     * `MFI_NONE` (-1): first bar, or mfi is NAN on this or the prior bar
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{mfi_bw, MFI_GREEN, MFI_FADE, MFI_FAKE, MFI_SQUAT, MFI_NONE};
+///
+/// fn main() {
+///     let high   = vec![11f64, 13f64, 12f64, 14f64, 14f64, 13f64, 13f64];
+///     let low    = vec![10f64, 10f64, 10f64, 10f64, 10f64, 10f64, 10f64];
+///     let volume = vec![10f64, 20f64, 10f64, 5f64, 20f64, 0f64, 10f64];
+///     let (mfi, state) = mfi_bw(&high, &low, &volume);
+///     // mfi: 0.1, 0.15, 0.2, 0.8, 0.2, NAN, 0.3
+///     assert!((mfi[3] - 0.8).abs() < 1e-12);
+///     assert!(mfi[5].is_nan());
+///     assert_eq!(state, vec![MFI_NONE, MFI_GREEN, MFI_FAKE, MFI_FAKE, MFI_SQUAT, MFI_NONE, MFI_NONE]);
+///
+///     // Fade: both fall
+///     let (_, state) = mfi_bw(&[12f64, 11f64], &[10f64, 10f64], &[10f64, 5f64]);
+///     assert_eq!(state[1], MFI_FADE);
+/// }
+/// ```
+pub fn mfi_bw(high: &[f64], low: &[f64], volume: &[f64]) -> (Vec<f64>, Vec<i8>) {
+    mfi_bw_checked(high, low, volume).expect("mfi_bw: invalid input")
+}
+
+/// Market Facilitation Index (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if the inputs differ in length
+pub fn mfi_bw_checked(high: &[f64], low: &[f64], volume: &[f64]) -> Result<(Vec<f64>, Vec<i8>), QuantError> {
+    let n = high.len();
+    check_len(n, low.len())?;
+    check_len(n, volume.len())?;
+    let mfi = (0 .. n).map(|i| {
+        if volume[i] == 0f64 { f64::NAN } else { (high[i] - low[i]) / volume[i] }
+    }).collect::<Vec<f64>>();
+    let mut state = vec![MFI_NONE; n];
+    for i in 1 .. n {
+        if mfi[i].is_nan() || mfi[i-1].is_nan() {
+            continue;
+        }
+        state[i] = match (mfi[i] > mfi[i-1], volume[i] > volume[i-1]) {
+            (true, true) => MFI_GREEN,
+            (false, false) => MFI_FADE,
+            (true, false) => MFI_FAKE,
+            (false, true) => MFI_SQUAT,
+        };
+    }
+    Ok((mfi, state))
+}
+
+/// Klinger Volume Oscillator
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `volume` - &[f64]
+/// * `fast` - usize (default: 34)
+/// * `slow` - usize (default: 55)
+/// * `signal` - usize (default: 13)
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (kvo, signal)
+///
+/// # Description
+/// * hlc = high + low + close, dm = high - low
+/// * trend[i] = +1 if hlc[i] > hlc[i-1], -1 if hlc[i] < hlc[i-1],
+///   trend[i-1] if they are equal (an unchanged bar never flips the trend)
+/// * cm[i] = cm[i-1] + dm[i] while the trend continues,
+///   cm[i] = dm[i-1] + dm[i] on the bar where it flips;
+///   the first trend bar (bar 1) continues from cm[0] = dm[0]
+/// * vf[i] = volume[i] * |2 * dm[i] / cm[i] - 1| * trend[i] * 100 (0 where cm is 0)
+/// * kvo = ema(vf, fast) - ema(vf, slow), signal = ema(kvo, signal)
+/// * Index 0 is NAN (no trend yet), the EMAs are seeded at bar 1
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{klinger, ema};
+///
+/// fn main() {
+///     let high = vec![11f64, 12f64, 13f64, 12f64, 11f64];
+///     let low = vec![9f64, 10f64, 11f64, 10f64, 9f64];
+///     let close = vec![10f64, 11f64, 12f64, 11f64, 10f64];
+///     let volume = vec![100f64; 5];
+///     let (kvo, sig) = klinger(&high, &low, &close, &volume, 1, 1, 1);
+///     assert!(kvo[0].is_nan() && sig[0].is_nan());
+///     // fast == slow: the oscillator is identically 0
+///     assert!(kvo[1 ..].iter().all(|&x| x == 0f64));
+///
+///     // Volume force with cm resets (dm = 2 on every bar)
+///     // bar 1: up,   cm = 2 + 2 = 4 -> vf = 100 * |4 / 4 - 1| * 100 = 0
+///     // bar 2: up,   cm = 4 + 2 = 6 -> vf = 100 * |4 / 6 - 1| * 100
+///     // bar 3: down, cm = 2 + 2 = 4 (reset) -> vf = 0
+///     // bar 4: down, cm = 4 + 2 = 6 -> vf = -100 * |4 / 6 - 1| * 100
+///     let third = 10_000f64 / 3f64;
+///     let vf = vec![0f64, third, 0f64, -third];
+///     let (kvo, _) = klinger(&high, &low, &close, &volume, 1, 3, 1);
+///     let slow = ema(&vf, 3);
+///     for i in 1 .. 5 {
+///         assert!((kvo[i] - (vf[i-1] - slow[i-1])).abs() < 1e-9);
+///     }
+/// }
+/// ```
+pub fn klinger(high: &[f64], low: &[f64], close: &[f64], volume: &[f64], fast: usize, slow: usize, signal: usize) -> (Vec<f64>, Vec<f64>) {
+    klinger_checked(high, low, close, volume, fast, slow, signal).expect("klinger: invalid input")
+}
+
+/// Klinger Volume Oscillator (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `fast`, `slow` or `signal` is 0
+/// * `LengthMismatch` if the inputs differ in length
+///
+/// # Examples
+/// ```
+/// use quantauri::base::klinger_checked;
+///
+/// fn main() {
+///     let x = vec![1f64, 2f64, 3f64];
+///     assert!(klinger_checked(&x, &x, &x, &x, 34, 55, 13).is_ok());
+///     assert!(klinger_checked(&x, &x, &x, &x, 34, 55, 0).is_err());
+///     assert!(klinger_checked(&x, &x, &x, &x[.. 2], 34, 55, 13).is_err());
+/// }
+/// ```
+pub fn klinger_checked(high: &[f64], low: &[f64], close: &[f64], volume: &[f64], fast: usize, slow: usize, signal: usize) -> Result<(Vec<f64>, Vec<f64>), QuantError> {
+    if fast == 0 || slow == 0 || signal == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    let n = close.len();
+    check_len(n, high.len())?;
+    check_len(n, low.len())?;
+    check_len(n, volume.len())?;
+    let mut kvo = vec![f64::NAN; n];
+    let mut sig = vec![f64::NAN; n];
+    if n < 2 {
+        return Ok((kvo, sig));
+    }
+    let hlc = (0 .. n).map(|i| high[i] + low[i] + close[i]).collect::<Vec<f64>>();
+    let dm = (0 .. n).map(|i| high[i] - low[i]).collect::<Vec<f64>>();
+    let mut vf = vec![0f64; n - 1];
+    let mut trend = 0f64;
+    let mut cm = dm[0];
+    for i in 1 .. n {
+        let new_trend = if hlc[i] > hlc[i-1] {
+            1f64
+        } else if hlc[i] < hlc[i-1] {
+            -1f64
+        } else if trend == 0f64 {
+            1f64
+        } else {
+            trend
+        };
+        cm = if trend == 0f64 || new_trend == trend { cm + dm[i] } else { dm[i-1] + dm[i] };
+        trend = new_trend;
+        vf[i-1] = if cm == 0f64 {
+            0f64
+        } else {
+            volume[i] * (2f64 * dm[i] / cm - 1f64).abs() * trend * 100f64
+        };
+    }
+    let fast_ema = ema(&vf, fast);
+    let slow_ema = ema(&vf, slow);
+    let osc = (0 .. n - 1).map(|i| fast_ema[i] - slow_ema[i]).collect::<Vec<f64>>();
+    kvo[1 ..].copy_from_slice(&osc);
+    sig[1 ..].copy_from_slice(&ema(&osc, signal));
+    Ok((kvo, sig))
+}
+
+// =============================================================================
+// Savitzky-Golay Filter
+// =============================================================================
+/// Savitzky-Golay convolution coefficients
+///
+/// `coef[p][j]` estimates the `deriv`-th derivative at position `p` of a window
+/// from the window values `v[j]`, by least squares fit of a `poly_order` polynomial.
+/// Positions are rescaled to [-1, 1] before the fit to keep the normal equations well conditioned.
+fn savgol_coefficients(window: usize, poly_order: usize, deriv: usize) -> Vec<Vec<f64>> {
+    let half = (window / 2) as f64;
+    let scale = half.max(1f64);
+    let k = poly_order + 1;
+    let factorial = (1 ..= deriv).map(|x| x as f64).product::<f64>();
+    let mut coef = vec![vec![0f64; window]; window];
+    for p in 0 .. window {
+        // Vandermonde rows a[j] = ((j - p) / scale)^m
+        let a = (0 .. window).map(|j| {
+            let x = (j as f64 - p as f64) / scale;
+            (0 .. k).map(|m| x.powi(m as i32)).collect::<Vec<f64>>()
+        }).collect::<Vec<Vec<f64>>>();
+        let mut ata = vec![0f64; k * k];
+        for r in 0 .. k {
+            for c in 0 .. k {
+                ata[r * k + c] = (0 .. window).map(|j| a[j][r] * a[j][c]).sum();
+            }
+        }
+        let inv = matrix(ata, k, k, Row).inv();
+        for j in 0 .. window {
+            let beta = (0 .. k).map(|m| inv[(deriv, m)] * a[j][m]).sum::<f64>();
+            coef[p][j] = factorial * beta / scale.powi(deriv as i32);
+        }
+    }
+    coef
+}
+
+/// Savitzky-Golay Smoothing
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize (odd)
+/// * `poly_order` - usize (< window)
+///
+/// # Returns
+/// * Vec<f64>
+///   * Value at each bar of a `poly_order` polynomial fitted by least squares to the
+///     `window` bars centered on it
+///   * Polynomials up to `poly_order` pass through unchanged
+///   * Edges are refitted: the first and last `window / 2` bars are evaluated on the
+///     polynomial fitted to the first (last) `window` bars, so the output has no warm-up
+///   * NAN propagates to every output that uses it
+///
+/// # Caution
+/// * The centered window looks `window / 2` bars ahead (the edge refit as well).
+///   Use it for analysis, not for signals evaluated in real time.
+///
+/// # Examples
+/// ```
+/// use quantauri::base::savgol;
+///
+/// fn main() {
+///     // A quadratic is reproduced exactly, edges included
+///     let v = (0 .. 20).map(|x| (x * x) as f64 - 3f64 * x as f64).collect::<Vec<f64>>();
+///     let s = savgol(&v, 7, 2);
+///     for i in 0 .. 20 {
+///         assert!((s[i] - v[i]).abs() < 1e-8);
+///     }
+///
+///     // Order 0 in the interior is a centered moving average
+///     let v = vec![1f64, 4f64, 2f64, 8f64, 5f64, 7f64];
+///     let s = savgol(&v, 3, 0);
+///     assert!((s[2] - (4f64 + 2f64 + 8f64) / 3f64).abs() < 1e-12);
+/// }
+/// ```
+pub fn savgol(v: &[f64], window: usize, poly_order: usize) -> Vec<f64> {
+    savgol_checked(v, window, poly_order).expect("savgol: invalid input")
+}
+
+/// Savitzky-Golay Smoothing (fallible)
+///
+/// # Errors
+/// * Same as `savgol_deriv_checked`
+pub fn savgol_checked(v: &[f64], window: usize, poly_order: usize) -> Result<Vec<f64>, QuantError> {
+    savgol_deriv_checked(v, window, poly_order, 0)
+}
+
+/// Savitzky-Golay Derivative
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize (odd)
+/// * `poly_order` - usize (< window)
+/// * `deriv` - usize (<= poly_order, 0 is plain smoothing)
+///
+/// # Returns
+/// * Vec<f64>
+///   * `deriv`-th derivative per bar of the local polynomial fit (see `savgol`)
+///   * A smoothed slope (`deriv = 1`) without the lag of a long moving average
+///
+/// # Examples
+/// ```
+/// use quantauri::base::savgol_deriv;
+///
+/// fn main() {
+///     // d/dx (x^2 - 3x) = 2x - 3, d2/dx2 = 2
+///     let v = (0 .. 20).map(|x| (x * x) as f64 - 3f64 * x as f64).collect::<Vec<f64>>();
+///     let d1 = savgol_deriv(&v, 5, 2, 1);
+///     let d2 = savgol_deriv(&v, 5, 2, 2);
+///     for i in 0 .. 20 {
+///         assert!((d1[i] - (2f64 * i as f64 - 3f64)).abs() < 1e-8);
+///         assert!((d2[i] - 2f64).abs() < 1e-8);
+///     }
+/// }
+/// ```
+pub fn savgol_deriv(v: &[f64], window: usize, poly_order: usize, deriv: usize) -> Vec<f64> {
+    savgol_deriv_checked(v, window, poly_order, deriv).expect("savgol_deriv: invalid input")
+}
+
+/// Savitzky-Golay Derivative (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+/// * `InvalidPeriod` if `window` is zero
+/// * `WindowTooLarge` if `window` exceeds the input length
+/// * `InvalidParameter` if `window` is even, `poly_order >= window` or `deriv > poly_order`
+pub fn savgol_deriv_checked(v: &[f64], window: usize, poly_order: usize, deriv: usize) -> Result<Vec<f64>, QuantError> {
+    check_nonempty(v)?;
+    check_window(window, v.len())?;
+    if window.is_multiple_of(2) {
+        return Err(QuantError::InvalidParameter(format!("savgol window must be odd, got {}", window)));
+    }
+    if poly_order >= window {
+        return Err(QuantError::InvalidParameter(format!("poly_order {} must be less than window {}", poly_order, window)));
+    }
+    if deriv > poly_order {
+        return Err(QuantError::InvalidParameter(format!("deriv {} exceeds poly_order {}", deriv, poly_order)));
+    }
+    let n = v.len();
+    let half = window / 2;
+    let coef = savgol_coefficients(window, poly_order, deriv);
+    let mut result = vec![0f64; n];
+    for i in 0 .. n {
+        // Window start and position of bar i within the window
+        let (start, p) = if i < half {
+            (0, i)
+        } else if i + half >= n {
+            (n - window, i + window - n)
+        } else {
+            (i - half, half)
+        };
+        result[i] = (0 .. window).map(|j| coef[p][j] * v[start + j]).sum();
+    }
+    Ok(result)
+}
+
+// =============================================================================
+// Guppy Multiple Moving Averages
+// =============================================================================
+/// Default short-term (trader) periods of GMMA
+pub const GMMA_SHORT: [usize; 6] = [3, 5, 8, 10, 12, 15];
+
+/// Default long-term (investor) periods of GMMA
+pub const GMMA_LONG: [usize; 6] = [30, 35, 40, 45, 50, 60];
+
+/// A group of moving averages, one series per period
+pub type MaGroup = Vec<Vec<f64>>;
+
+/// Guppy Multiple Moving Averages
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `short_periods` - &[usize] (default: `GMMA_SHORT`)
+/// * `long_periods` - &[usize] (default: `GMMA_LONG`)
+///
+/// # Returns
+/// * (MaGroup, MaGroup)
+///   * (short group, long group): `ema(v, period)` for each period, in the given order
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{gmma, ema, GMMA_SHORT, GMMA_LONG};
+///
+/// fn main() {
+///     let v = (0 .. 100).map(|x| x as f64).collect::<Vec<f64>>();
+///     let (short, long) = gmma(&v, &GMMA_SHORT, &GMMA_LONG);
+///     assert_eq!(short.len(), 6);
+///     assert_eq!(long.len(), 6);
+///     assert_eq!(short[2], ema(&v, 8));
+///     assert_eq!(long[5], ema(&v, 60));
+/// }
+/// ```
+pub fn gmma(v: &[f64], short_periods: &[usize], long_periods: &[usize]) -> (MaGroup, MaGroup) {
+    gmma_checked(v, short_periods, long_periods).expect("gmma: invalid input")
+}
+
+/// Guppy Multiple Moving Averages (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+/// * `InvalidPeriod` if a period is zero
+/// * `InvalidParameter` if a group has no period
+pub fn gmma_checked(v: &[f64], short_periods: &[usize], long_periods: &[usize]) -> Result<(MaGroup, MaGroup), QuantError> {
+    if short_periods.is_empty() || long_periods.is_empty() {
+        return Err(QuantError::InvalidParameter("gmma groups need at least one period".to_string()));
+    }
+    let short = short_periods.iter().map(|&p| ema_checked(v, p)).collect::<Result<Vec<_>, _>>()?;
+    let long = long_periods.iter().map(|&p| ema_checked(v, p)).collect::<Result<Vec<_>, _>>()?;
+    Ok((short, long))
+}
+
+/// Relative spread of a group of moving averages
+fn group_spread(group: &[Vec<f64>], i: usize) -> f64 {
+    let mut max = f64::NEG_INFINITY;
+    let mut min = f64::INFINITY;
+    let mut sum = 0f64;
+    for ma in group.iter() {
+        if ma[i].is_nan() {
+            return f64::NAN;
+        }
+        max = max.max(ma[i]);
+        min = min.min(ma[i]);
+        sum += ma[i];
+    }
+    let mean = sum / group.len() as f64;
+    if mean == 0f64 { f64::NAN } else { (max - min) / mean.abs() }
+}
+
+/// GMMA Compression
+///
+/// # Arguments
+/// * `short` - &[Vec<f64>] (short group of `gmma`)
+/// * `long` - &[Vec<f64>] (long group of `gmma`)
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (short spread, long spread): (max - min) / |mean| across the group at each bar
+///   * Small values mean a compressed ribbon, rising values an expanding one
+///   * NAN where a member is NAN or the group mean is 0
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{gmma, gmma_compression, GMMA_SHORT, GMMA_LONG};
+///
+/// fn main() {
+///     // Flat series: ribbon fully compressed
+///     let flat = vec![100f64; 100];
+///     let (short, long) = gmma(&flat, &GMMA_SHORT, &GMMA_LONG);
+///     let (s, l) = gmma_compression(&short, &long);
+///     assert!(s.iter().chain(l.iter()).all(|&x| x.abs() < 1e-12));
+///
+///     // A breakout expands the short ribbon before the long one
+///     let mut v = vec![100f64; 100];
+///     v.extend((1 ..= 20).map(|x| 100f64 + x as f64));
+///     let (short, long) = gmma(&v, &GMMA_SHORT, &GMMA_LONG);
+///     let (s, l) = gmma_compression(&short, &long);
+///     assert!(s[105] > s[100] && s[105] > 0f64);
+///     assert!(l[119] > l[100]);
+/// }
+/// ```
+pub fn gmma_compression(short: &[Vec<f64>], long: &[Vec<f64>]) -> (Vec<f64>, Vec<f64>) {
+    gmma_compression_checked(short, long).expect("gmma_compression: invalid input")
+}
+
+/// GMMA Compression (fallible)
+///
+/// # Errors
+/// * `InvalidParameter` if a group is empty
+/// * `LengthMismatch` if the series differ in length
+pub fn gmma_compression_checked(short: &[Vec<f64>], long: &[Vec<f64>]) -> Result<(Vec<f64>, Vec<f64>), QuantError> {
+    if short.is_empty() || long.is_empty() {
+        return Err(QuantError::InvalidParameter("gmma groups need at least one series".to_string()));
+    }
+    let n = short[0].len();
+    for ma in short.iter().chain(long.iter()) {
+        check_len(n, ma.len())?;
+    }
+    let s = (0 .. n).map(|i| group_spread(short, i)).collect();
+    let l = (0 .. n).map(|i| group_spread(long, i)).collect();
+    Ok((s, l))
+}
+
+// =============================================================================
+// Linear Regression
+// =============================================================================
+/// Rolling least-squares line over the trailing `period` bars
+///
+/// x runs 0, 1, .., period - 1 inside each window (x = period - 1 at the current bar).
+/// Sum(y) and Sum(x y) are updated in O(1) per bar and recomputed every `period` bars
+/// (and after a NAN leaves the window) to stop rounding drift.
+/// Returns (endpoint, slope), NAN for warm-up bars and windows containing NAN.
+fn rolling_linreg(v: &[f64], period: usize) -> (Vec<f64>, Vec<f64>) {
+    let n = v.len();
+    let mut endpoint = vec![f64::NAN; n];
+    let mut slope = vec![f64::NAN; n];
+    let p = period as f64;
+    let sx = p * (p - 1f64) / 2f64;
+    let sxx = (p - 1f64) * p * (2f64 * p - 1f64) / 6f64;
+    let denom = p * sxx - sx * sx;
+
+    let mut sy = 0f64;
+    let mut sxy = 0f64;
+    let mut fresh = false;
+    let mut last_nan = None;
+    for i in 0 .. n {
+        if v[i].is_nan() {
+            last_nan = Some(i);
+        }
+        if i + 1 < period || last_nan.is_some_and(|j| j + period > i) {
+            fresh = false;
+            continue;
+        }
+        let start = i + 1 - period;
+        if !fresh || i % period == 0 {
+            sy = v[start ..= i].iter().sum();
+            sxy = (0 .. period).map(|k| k as f64 * v[start + k]).sum();
+            fresh = true;
+        } else {
+            // Shift x by one: every kept value loses 1 from its x, the new value enters at p - 1
+            sxy = sxy - (sy - v[start - 1]) + (p - 1f64) * v[i];
+            sy += v[i] - v[start - 1];
+        }
+        let b = if denom == 0f64 { 0f64 } else { (p * sxy - sx * sy) / denom };
+        let a = (sy - b * sx) / p;
+        slope[i] = b;
+        endpoint[i] = a + b * (p - 1f64);
+    }
+    (endpoint, slope)
+}
+
+/// Linear Regression (Least Squares Moving Average)
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * Value at the current bar of the least-squares line fitted to the trailing
+///     `period` bars (a.k.a. LSMA, or "time series forecast" shifted by one bar)
+///   * x is the bar offset inside the window (0 ..= period - 1), so the result is in
+///     price units and independent of the absolute bar index
+///   * First `period - 1` values are NAN, NAN whenever the window contains NAN
+///   * `period = 1` returns `v`
+///
+/// # Examples
+/// ```
+/// use quantauri::base::linreg;
+///
+/// fn main() {
+///     // A line is reproduced exactly (no lag, unlike sma)
+///     let v = (0 .. 30).map(|x| 2f64 * x as f64 + 1f64).collect::<Vec<f64>>();
+///     let lr = linreg(&v, 10);
+///     assert!(lr[8].is_nan());
+///     for i in 9 .. 30 {
+///         assert!((lr[i] - v[i]).abs() < 1e-9);
+///     }
+///
+///     // Matches a direct fit on a noisy series
+///     let v = (0 .. 30).map(|x| (x as f64 * 0.9).sin() * 3f64 + x as f64).collect::<Vec<f64>>();
+///     let lr = linreg(&v, 5);
+///     let y = &v[20 ..= 24];
+///     let (mx, my) = (2f64, y.iter().sum::<f64>() / 5f64);
+///     let b = (0 .. 5).map(|k| (k as f64 - mx) * (y[k] - my)).sum::<f64>() / 10f64;
+///     assert!((lr[24] - (my + b * 2f64)).abs() < 1e-9);
+/// }
+/// ```
+pub fn linreg(v: &[f64], period: usize) -> Vec<f64> {
+    linreg_checked(v, period).expect("linreg: invalid input")
+}
+
+/// Linear Regression (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+/// * `InvalidPeriod` if `period == 0`
+pub fn linreg_checked(v: &[f64], period: usize) -> Result<Vec<f64>, QuantError> {
+    check_nonempty(v)?;
+    if period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    Ok(rolling_linreg(v, period).0)
+}
+
+/// Linear Regression Slope
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * Slope of the least-squares line over the trailing `period` bars, in price units per bar
+///   * Warm-up and NAN handling as `linreg`, 0 for `period = 1`
+///
+/// # Examples
+/// ```
+/// use quantauri::base::linreg_slope;
+///
+/// fn main() {
+///     let v = (0 .. 30).map(|x| 0.5 * x as f64 + if x % 2 == 0 { 1f64 } else { -1f64 }).collect::<Vec<f64>>();
+///     let s = linreg_slope(&v, 11);
+///     assert!(s[9].is_nan());
+///     // Odd window over an alternating wiggle: the slope stays near 0.5
+///     assert!(s[10 ..].iter().all(|&b| (b - 0.5).abs() < 0.25));
+///
+///     let mut v = vec![1f64, 2f64, 3f64, f64::NAN, 5f64, 6f64, 7f64];
+///     let s = linreg_slope(&v, 3);
+///     assert_eq!(s[2], 1f64);
+///     assert!(s[3 ..= 5].iter().all(|x| x.is_nan()));
+///     assert_eq!(s[6], 1f64);
+///     v[3] = 4f64;
+///     assert!(linreg_slope(&v, 3)[2 ..].iter().all(|&b| (b - 1f64).abs() < 1e-12));
+/// }
+/// ```
+pub fn linreg_slope(v: &[f64], period: usize) -> Vec<f64> {
+    linreg_slope_checked(v, period).expect("linreg_slope: invalid input")
+}
+
+/// Linear Regression Slope (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+/// * `InvalidPeriod` if `period == 0`
+pub fn linreg_slope_checked(v: &[f64], period: usize) -> Result<Vec<f64>, QuantError> {
+    check_nonempty(v)?;
+    if period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    Ok(rolling_linreg(v, period).1)
+}
+
+// =============================================================================
+// TTM Squeeze
+// =============================================================================
+/// Output of `squeeze`
+///
+/// # Fields
+/// * `on` - Bollinger bands inside the Keltner channel (`BollingerBand::squeeze`)
+/// * `momentum` - linreg(close - midline, kc_period), the histogram
+/// * `fired` - bars where the squeeze turned off (`on[i-1] && !on[i]`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqueezeOutput {
+    pub on: Vec<bool>,
+    pub momentum: Vec<f64>,
+    pub fired: Vec<usize>,
+}
+
+/// TTM Squeeze
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `bb_period` - usize (default: 20)
+/// * `bb_mult` - f64 (default: 2)
+/// * `kc_period` - usize (default: 20)
+/// * `kc_mult` - f64 (default: 1.5)
+///
+/// # Returns
+/// * SqueezeOutput
+///   * on: `BollingerBand::new(bb_period, bb_mult)` inside `KeltnerChannel::new(kc_period, kc_mult)`,
+///     `false` while either is warming up
+///   * momentum: midline = (donchian mid + sma(close)) / 2 over `kc_period`, with
+///     donchian mid = (highest high + lowest low) / 2; the histogram is
+///     `linreg(close - midline, kc_period)`, NAN for the first `kc_period - 1` bars
+///   * fired: the first bar after a squeeze; the sign of `momentum` there gives the direction
+///
+/// # Examples
+/// ```
+/// use quantauri::base::squeeze;
+///
+/// fn main() {
+///     // Tight closes inside wide bars, then a breakout
+///     let mut close = (0 .. 40).map(|x| 100f64 + if x % 2 == 0 { 0.2 } else { -0.2 }).collect::<Vec<f64>>();
+///     close.extend((1 ..= 20).map(|x| 100f64 + 3f64 * x as f64));
+///     let high = close.iter().map(|x| x + 1f64).collect::<Vec<f64>>();
+///     let low = close.iter().map(|x| x - 1f64).collect::<Vec<f64>>();
+///
+///     let sq = squeeze(&close, &high, &low, 20, 2f64, 20, 1.5);
+///     assert!(!sq.on[18]);
+///     assert!(sq.on[19 .. 40].iter().all(|&x| x));
+///     assert!(!sq.on[59]);
+///     // One fire, right after the consolidation, with upward momentum
+///     assert_eq!(sq.fired.len(), 1);
+///     let fire = sq.fired[0];
+///     assert!(fire >= 40 && fire < 45);
+///     assert!(sq.momentum[fire] > 0f64);
+///     assert!(sq.momentum[30].abs() < 0.5);
+/// }
+/// ```
+pub fn squeeze(close: &[f64], high: &[f64], low: &[f64], bb_period: usize, bb_mult: f64, kc_period: usize, kc_mult: f64) -> SqueezeOutput {
+    squeeze_checked(close, high, low, bb_period, bb_mult, kc_period, kc_mult).expect("squeeze: invalid input")
+}
+
+/// TTM Squeeze (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if the inputs differ in length
+/// * `InvalidPeriod` if `bb_period` or `kc_period` is 0
+/// * `WindowTooLarge` if `bb_period` or `kc_period` is longer than the input
+pub fn squeeze_checked(close: &[f64], high: &[f64], low: &[f64], bb_period: usize, bb_mult: f64, kc_period: usize, kc_mult: f64) -> Result<SqueezeOutput, QuantError> {
+    let n = close.len();
+    check_len(n, high.len())?;
+    check_len(n, low.len())?;
+    check_window(bb_period, n)?;
+    check_window(kc_period, n)?;
+
+    let mut bb = BollingerBand::new(bb_period, bb_mult);
+    bb.bb_mut(close);
+    let mut kc = KeltnerChannel::new(kc_period, kc_mult);
+    kc.kc_mut(high, low, close);
+    let on = bb.squeeze_checked(&kc)?;
+
+    let hh = rolling_max(high, kc_period);
+    let ll = rolling_min(low, kc_period);
+    let mid = sma_checked(close, kc_period)?;
+    let delta = (0 .. n).map(|i| close[i] - ((hh[i] + ll[i]) / 2f64 + mid[i]) / 2f64).collect::<Vec<f64>>();
+    let momentum = linreg_checked(&delta, kc_period)?;
+
+    let fired = (1 .. n).filter(|&i| on[i-1] && !on[i]).collect();
+    Ok(SqueezeOutput { on, momentum, fired })
+}
+
+// =============================================================================
+// Relative Vigor Index
+// =============================================================================
+/// Symmetric Weighted Moving Average (4 bars)
+///
+/// # Arguments
+/// * `v` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * swma[i] = (v[i] + 2 v[i-1] + 2 v[i-2] + v[i-3]) / 6
+///   * First 3 values are NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::base::swma;
+///
+/// fn main() {
+///     let s = swma(&[1f64, 2f64, 3f64, 4f64, 6f64]);
+///     assert!(s[.. 3].iter().all(|x| x.is_nan()));
+///     assert_eq!(s[3], (4f64 + 6f64 + 4f64 + 1f64) / 6f64);
+///     assert_eq!(s[4], (6f64 + 8f64 + 6f64 + 2f64) / 6f64);
+/// }
+/// ```
+pub fn swma(v: &[f64]) -> Vec<f64> {
+    let mut result = vec![f64::NAN; v.len()];
+    for i in 3 .. v.len() {
+        result[i] = (v[i] + 2f64 * v[i-1] + 2f64 * v[i-2] + v[i-3]) / 6f64;
+    }
+    result
+}
+
+/// Relative Vigor Index
+///
+/// # Arguments
+/// * `open` - &[f64]
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `period` - usize (default: 10)
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (rvi, signal)
+///   * rvi = sum(swma(close - open), period) / sum(swma(high - low), period), 0 if the denominator is 0
+///   * signal = swma(rvi)
+///   * First `period + 2` rvi values and `period + 5` signal values are NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{rvi, swma};
+///
+/// fn main() {
+///     // Every bar closes at its high: maximal vigor
+///     let open = vec![10f64; 20];
+///     let high = vec![12f64; 20];
+///     let low = vec![9f64; 20];
+///     let close = vec![12f64; 20];
+///     let (r, s) = rvi(&open, &high, &low, &close, 4);
+///     assert!(r[5].is_nan() && s[8].is_nan());
+///     assert!((r[6] - 2f64 / 3f64).abs() < 1e-12);
+///     assert!((s[9] - 2f64 / 3f64).abs() < 1e-12);
+///
+///     // Zero range everywhere: 0 instead of 0 / 0
+///     let flat = vec![10f64; 20];
+///     let (r, _) = rvi(&flat, &flat, &flat, &flat, 4);
+///     assert!(r[6 ..].iter().all(|&x| x == 0f64));
+///
+///     // Worked example: a single +6 body on bar 3, range 10 on every bar, period 1.
+///     // The body enters the numerator with weights 1, 2, 2, 1 (/ 6) on bars 3 ..= 6,
+///     // the denominator is (10 + 20 + 20 + 10) / 6 = 10
+///     let open = vec![0f64; 10];
+///     let mut close = vec![0f64; 10];
+///     close[3] = 6f64;
+///     let high = vec![5f64; 10];
+///     let low = vec![-5f64; 10];
+///     let (r, s) = rvi(&open, &high, &low, &close, 1);
+///     let expect = [0.1, 0.2, 0.2, 0.1, 0f64];
+///     assert!(r[3 ..= 7].iter().zip(expect.iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+///     // Signal: the same weights on the rvi line
+///     assert!((s[6] - (0.1 + 2f64 * 0.2 + 2f64 * 0.2 + 0.1) / 6f64).abs() < 1e-12);
+///     assert!((s[7] - (0.2 + 2f64 * 0.2 + 2f64 * 0.1 + 0f64) / 6f64).abs() < 1e-12);
+/// }
+/// ```
+pub fn rvi(open: &[f64], high: &[f64], low: &[f64], close: &[f64], period: usize) -> (Vec<f64>, Vec<f64>) {
+    rvi_checked(open, high, low, close, period).expect("rvi: invalid input")
+}
+
+/// Relative Vigor Index (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `period == 0`
+/// * `LengthMismatch` if the inputs differ in length
+pub fn rvi_checked(open: &[f64], high: &[f64], low: &[f64], close: &[f64], period: usize) -> Result<(Vec<f64>, Vec<f64>), QuantError> {
+    if period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    let n = close.len();
+    check_len(n, open.len())?;
+    check_len(n, high.len())?;
+    check_len(n, low.len())?;
+    let num = swma(&(0 .. n).map(|i| close[i] - open[i]).collect::<Vec<f64>>());
+    let den = swma(&(0 .. n).map(|i| high[i] - low[i]).collect::<Vec<f64>>());
+    let mut result = vec![f64::NAN; n];
+    for i in period + 2 .. n {
+        let a = num[i + 1 - period ..= i].iter().sum::<f64>();
+        let b = den[i + 1 - period ..= i].iter().sum::<f64>();
+        result[i] = if b == 0f64 { 0f64 } else { a / b };
+    }
+    let signal = swma(&result);
+    Ok((result, signal))
+}
+
+// =============================================================================
+// Moving Average Selection
+// =============================================================================
+/// Moving average used by indicators with a selectable smoothing
+///
+/// Each kind keeps the warm-up of its function: `Sma` averages partial windows,
+/// `Ema` and `Rma` are seeded at bar 0, `Wma` and `Hma` start with NAN, `Tma` averages partial windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaKind {
+    /// `sma`
+    Sma,
+    /// `ema`
+    Ema,
+    /// `wma`
+    Wma,
+    /// `rma` (Wilder)
+    Rma,
+    /// `hma` (Hull)
+    Hma,
+    /// `tma` (triangular)
+    Tma,
+}
+
+/// Moving Average of a selectable kind
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+/// * `kind` - MaKind
+///
+/// # Returns
+/// * Vec<f64>
+///   * `sma`, `ema`, `wma`, `rma`, `hma` or `tma` of `v` over `period`
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{moving_average, MaKind, sma, ema, wma, rma};
+///
+/// fn main() {
+///     let v = vec![1f64, 3f64, 2f64, 5f64, 4f64];
+///     assert_eq!(moving_average(&v, 3, MaKind::Sma), sma(&v, 3));
+///     assert_eq!(moving_average(&v, 3, MaKind::Ema), ema(&v, 3));
+///     assert_eq!(moving_average(&v, 3, MaKind::Rma), rma(&v, 3));
+///     let w = moving_average(&v, 3, MaKind::Wma);
+///     assert!(w[1].is_nan() && w[4] == wma(&v, 3)[4]);
+/// }
+/// ```
+pub fn moving_average(v: &[f64], period: usize, kind: MaKind) -> Vec<f64> {
+    moving_average_checked(v, period, kind).expect("moving_average: invalid input")
+}
+
+/// Moving Average of a selectable kind (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+/// * `InvalidPeriod` if `period == 0`
+/// * `WindowTooLarge` if `period > v.len()` for `Sma`
+pub fn moving_average_checked(v: &[f64], period: usize, kind: MaKind) -> Result<Vec<f64>, QuantError> {
+    check_nonempty(v)?;
+    if period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    match kind {
+        MaKind::Sma => sma_checked(v, period),
+        MaKind::Ema => ema_checked(v, period),
+        MaKind::Wma => Ok(wma(v, period)),
+        MaKind::Rma => wilder_ema_checked(v, period),
+        MaKind::Hma => Ok(hma(v, period)),
+        MaKind::Tma => tma_checked(v, period),
+    }
+}
+
+/// Qstick
+///
+/// # Arguments
+/// * `open` - &[f64]
+/// * `close` - &[f64]
+/// * `period` - usize (default: 8)
+/// * `kind` - MaKind (`Sma` in the original definition)
+///
+/// # Returns
+/// * Vec<f64>
+///   * moving_average(close - open, period, kind): positive when candle bodies are mostly white
+///   * Warm-up of the chosen `MaKind`
+///
+/// # Examples
+/// ```
+/// use quantauri::base::{qstick, MaKind};
+///
+/// fn main() {
+///     let open = vec![10f64, 11f64, 12f64, 12f64, 13f64];
+///     let close = vec![11f64, 12f64, 11f64, 13f64, 15f64];
+///     let q = qstick(&open, &close, 3, MaKind::Sma);
+///     // bodies: 1, 1, -1, 1, 2
+///     assert_eq!(q[2], 1f64 / 3f64);
+///     assert_eq!(q[4], 2f64 / 3f64);
+///     let q = qstick(&open, &close, 3, MaKind::Ema);
+///     assert_eq!(q[0], 1f64);
+/// }
+/// ```
+pub fn qstick(open: &[f64], close: &[f64], period: usize, kind: MaKind) -> Vec<f64> {
+    qstick_checked(open, close, period, kind).expect("qstick: invalid input")
+}
+
+/// Qstick (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `open` and `close` differ in length
+/// * Errors of `moving_average_checked`
+pub fn qstick_checked(open: &[f64], close: &[f64], period: usize, kind: MaKind) -> Result<Vec<f64>, QuantError> {
+    check_len(open.len(), close.len())?;
+    let body = (0 .. close.len()).map(|i| close[i] - open[i]).collect::<Vec<f64>>();
+    moving_average_checked(&body, period, kind)
 }
@@ -0,0 +1,126 @@
+use chrono::{Datelike, NaiveDate};
+use crate::error::{QuantError, check_len};
+
+// =============================================================================
+// Dates
+// =============================================================================
+/// Parse a date column
+///
+/// # Arguments
+/// * `dates` - &[String] (ISO `YYYY-MM-DD`)
+///
+/// # Returns
+/// * `Result<Vec<NaiveDate>, QuantError>`
+///   * Surrounding whitespace is ignored, and so is a time part after `T` or a space
+///     (`2024-01-02T09:30:00`, `2024-01-02 09:30`)
+///
+/// # Errors
+/// * `InvalidDate` with the row and the raw value of the first entry that doesn't parse
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use quantauri::calendar::parse_dates;
+/// use quantauri::error::QuantError;
+///
+/// fn main() {
+///     let dates = vec!["2024-01-02".to_string(), "2024-01-03T16:00:00".to_string()];
+///     let parsed = parse_dates(&dates).unwrap();
+///     assert_eq!(parsed[1], NaiveDate::from_ymd_opt(2024, 1, 3).unwrap());
+///
+///     let bad = vec!["2024-01-02".to_string(), "01/03/2024".to_string()];
+///     assert_eq!(parse_dates(&bad), Err(QuantError::InvalidDate { row: 1, value: "01/03/2024".to_string() }));
+/// }
+/// ```
+pub fn parse_dates(dates: &[String]) -> Result<Vec<NaiveDate>, QuantError> {
+    dates.iter().enumerate().map(|(row, s)| {
+        let day = s.trim().split(['T', ' ']).next().unwrap_or("");
+        NaiveDate::parse_from_str(day, "%Y-%m-%d").map_err(|_| QuantError::InvalidDate { row, value: s.clone() })
+    }).collect()
+}
+
+// =============================================================================
+// Seasonality
+// =============================================================================
+/// Calendar bucket for `seasonality`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeasonBucket {
+    /// "Mon" ..= "Sun"
+    DayOfWeek,
+    /// "Jan" ..= "Dec"
+    MonthOfYear,
+}
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// Mean return per calendar bucket
+///
+/// # Arguments
+/// * `dates` - &[String] (ISO `YYYY-MM-DD`, see `parse_dates`)
+/// * `returns` - &[f64] (returns[i] is the return realized on dates[i])
+/// * `bucket` - SeasonBucket
+///
+/// # Returns
+/// * Vec<(String, f64)>
+///   * Every bucket in calendar order (7 weekdays from Monday, or 12 months), with the
+///     mean of its valid returns
+///   * NAN for a bucket without data (e.g. weekends for exchange data); NAN returns are skipped
+///
+/// # Examples
+/// ```
+/// use quantauri::calendar::{seasonality, SeasonBucket};
+///
+/// fn main() {
+///     // 2024-01-01 is a Monday
+///     let dates = ["2024-01-01", "2024-01-02", "2024-01-08", "2024-02-05"]
+///         .iter().map(|s| s.to_string()).collect::<Vec<String>>();
+///     let returns = vec![0.01, -0.02, 0.03, f64::NAN];
+///
+///     let dow = seasonality(&dates, &returns, SeasonBucket::DayOfWeek);
+///     assert_eq!(dow.len(), 7);
+///     assert_eq!(dow[0].0, "Mon");
+///     assert!((dow[0].1 - 0.02).abs() < 1e-12);
+///     assert_eq!(dow[1], ("Tue".to_string(), -0.02));
+///     assert!(dow[5].1.is_nan());
+///
+///     let moy = seasonality(&dates, &returns, SeasonBucket::MonthOfYear);
+///     assert_eq!(moy.len(), 12);
+///     assert!((moy[0].1 - 0.02 / 3f64).abs() < 1e-12);
+///     assert!(moy[1].1.is_nan()); // only a NAN return in February
+/// }
+/// ```
+pub fn seasonality(dates: &[String], returns: &[f64], bucket: SeasonBucket) -> Vec<(String, f64)> {
+    seasonality_checked(dates, returns, bucket).expect("seasonality: invalid input")
+}
+
+/// Mean return per calendar bucket (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `dates` and `returns` differ in length
+/// * `InvalidDate` for the first date that doesn't parse
+pub fn seasonality_checked(dates: &[String], returns: &[f64], bucket: SeasonBucket) -> Result<Vec<(String, f64)>, QuantError> {
+    check_len(dates.len(), returns.len())?;
+    let parsed = parse_dates(dates)?;
+    let labels: &[&str] = match bucket {
+        SeasonBucket::DayOfWeek => &WEEKDAYS,
+        SeasonBucket::MonthOfYear => &MONTHS,
+    };
+    let mut sum = vec![0f64; labels.len()];
+    let mut count = vec![0usize; labels.len()];
+    for (date, &r) in parsed.iter().zip(returns.iter()) {
+        if r.is_nan() {
+            continue;
+        }
+        let k = match bucket {
+            SeasonBucket::DayOfWeek => date.weekday().num_days_from_monday() as usize,
+            SeasonBucket::MonthOfYear => date.month0() as usize,
+        };
+        sum[k] += r;
+        count[k] += 1;
+    }
+    Ok(labels.iter().enumerate().map(|(k, label)| {
+        let mean = if count[k] == 0 { f64::NAN } else { sum[k] / count[k] as f64 };
+        (label.to_string(), mean)
+    }).collect())
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+
+/// Error type for fallible indicator functions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuantError {
+    WindowTooLarge { window: usize, len: usize },
+    EmptyInput,
+    LengthMismatch { a: usize, b: usize },
+    InvalidPeriod,
+    InvalidParameter(String),
+    NoConvergence { iterations: usize },
+    Io(String),
+    InvalidDate { row: usize, value: String },
+}
+
+impl fmt::Display for QuantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuantError::WindowTooLarge { window, len } => {
+                write!(f, "window size {} is larger than input length {}", window, len)
+            }
+            QuantError::EmptyInput => write!(f, "input is empty"),
+            QuantError::LengthMismatch { a, b } => {
+                write!(f, "input lengths do not match ({} != {})", a, b)
+            }
+            QuantError::InvalidPeriod => write!(f, "period must be positive"),
+            QuantError::InvalidParameter(msg) => write!(f, "invalid parameter: {}", msg),
+            QuantError::NoConvergence { iterations } => {
+                write!(f, "optimizer did not converge after {} iterations", iterations)
+            }
+            QuantError::Io(msg) => write!(f, "io error: {}", msg),
+            QuantError::InvalidDate { row, value } => {
+                write!(f, "invalid date {:?} at row {} (expected YYYY-MM-DD)", value, row)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QuantError {}
+
+// =============================================================================
+// Validation helpers
+// =============================================================================
+/// Check that `v` is not empty
+pub fn check_nonempty(v: &[f64]) -> Result<(), QuantError> {
+    if v.is_empty() {
+        Err(QuantError::EmptyInput)
+    } else {
+        Ok(())
+    }
+}
+
+/// Check that `window` is positive and fits in `len`
+pub fn check_window(window: usize, len: usize) -> Result<(), QuantError> {
+    if window == 0 {
+        Err(QuantError::InvalidPeriod)
+    } else if window > len {
+        Err(QuantError::WindowTooLarge { window, len })
+    } else {
+        Ok(())
+    }
+}
+
+/// Check that two lengths are equal
+pub fn check_len(a: usize, b: usize) -> Result<(), QuantError> {
+    if a != b {
+        Err(QuantError::LengthMismatch { a, b })
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+use peroxide::fuga::*;
+use crate::error::QuantError;
+use crate::base::{qstick_checked, MaKind};
+
+// =============================================================================
+// DataFrame Helpers
+// =============================================================================
+/// Attach indicator columns to a DataFrame
+///
+/// # Arguments
+/// * `df` - &mut DataFrame
+/// * `columns` - Vec<(&str, Vec<f64>)> (header, values)
+///
+/// # Description
+/// Pushes every column onto `df`, so existing columns (e.g. `date`) are kept.
+/// Nothing is pushed unless every column is valid.
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::frame::{attach_indicators, attach_indicators_checked};
+///
+/// fn main() {
+///     let date = vec!["2024-01-01".to_string(), "2024-01-02".to_string()];
+///     let mut df = DataFrame::new(vec![]);
+///     df.push("date", Series::new(date));
+///     df.push("close", Series::new(vec![1f64, 2f64]));
+///
+///     attach_indicators(&mut df, vec![("double", vec![2f64, 4f64])]);
+///     assert_eq!(df.header(), &vec!["date", "close", "double"]);
+///
+///     // Length mismatch: frame is left untouched
+///     assert!(attach_indicators_checked(&mut df, vec![("bad", vec![1f64])]).is_err());
+///     assert_eq!(df.header().len(), 3);
+/// }
+/// ```
+pub fn attach_indicators(df: &mut DataFrame, columns: Vec<(&str, Vec<f64>)>) {
+    attach_indicators_checked(df, columns).expect("attach_indicators: invalid input")
+}
+
+/// Attach indicator columns to a DataFrame (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if a column length differs from the frame's row count
+///   (or from the first new column when `df` has no column)
+/// * `InvalidParameter` if a header already exists or is repeated
+pub fn attach_indicators_checked(df: &mut DataFrame, columns: Vec<(&str, Vec<f64>)>) -> Result<(), QuantError> {
+    let nrow = if df.header().is_empty() {
+        columns.first().map(|(_, v)| v.len())
+    } else {
+        Some(df[0].len())
+    };
+    let mut headers = df.header().clone();
+    for (name, v) in columns.iter() {
+        if let Some(nrow) = nrow {
+            if v.len() != nrow {
+                return Err(QuantError::LengthMismatch { a: nrow, b: v.len() });
+            }
+        }
+        if headers.iter().any(|h| h == name) {
+            return Err(QuantError::InvalidParameter(format!("column {} already exists", name)));
+        }
+        headers.push(name.to_string());
+    }
+    for (name, v) in columns {
+        df.push(name, Series::new(v));
+    }
+    Ok(())
+}
+
+/// Build an indicator frame that carries columns of the source frame
+///
+/// # Arguments
+/// * `source` - &DataFrame
+/// * `keep` - &[&str] (columns copied from `source`, e.g. `["date"]`)
+/// * `columns` - Vec<(&str, Vec<f64>)> (header, values)
+///
+/// # Returns
+/// * `Result<DataFrame, QuantError>`
+///   * `keep` columns in order, followed by `columns`
+///
+/// # Errors
+/// * `InvalidParameter` if a `keep` column is missing from `source`
+/// * Errors of `attach_indicators_checked`
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::frame::indicator_frame;
+///
+/// fn main() {
+///     let mut df = DataFrame::new(vec![]);
+///     df.push("date", Series::new(vec!["d1".to_string(), "d2".to_string()]));
+///     df.push("close", Series::new(vec![1f64, 3f64]));
+///
+///     let out = indicator_frame(&df, &["date"], vec![("sma", vec![1f64, 2f64])]).unwrap();
+///     assert_eq!(out.header(), &vec!["date", "sma"]);
+///     assert!(indicator_frame(&df, &["time"], vec![]).is_err());
+/// }
+/// ```
+pub fn indicator_frame(source: &DataFrame, keep: &[&str], columns: Vec<(&str, Vec<f64>)>) -> Result<DataFrame, QuantError> {
+    let mut df = DataFrame::new(vec![]);
+    for &name in keep {
+        if !source.header().iter().any(|h| h == name) {
+            return Err(QuantError::InvalidParameter(format!("column {} not found", name)));
+        }
+        df.push(name, source[name].clone());
+    }
+    attach_indicators_checked(&mut df, columns)?;
+    Ok(df)
+}
+
+/// Numeric column of a DataFrame as `Vec<f64>`
+///
+/// # Errors
+/// * `InvalidParameter` if the column is missing or not numeric
+pub fn column_f64(df: &DataFrame, name: &str) -> Result<Vec<f64>, QuantError> {
+    if !df.header().iter().any(|h| h == name) {
+        return Err(QuantError::InvalidParameter(format!("column {} not found", name)));
+    }
+    let series = &df[name];
+    match series.dtype {
+        DType::Str | DType::Bool | DType::Char => {
+            Err(QuantError::InvalidParameter(format!("column {} is not numeric", name)))
+        }
+        DType::F64 => Ok(series.to_vec()),
+        _ => Ok(series.to_type(DType::F64).to_vec()),
+    }
+}
+
+// =============================================================================
+// DataFrame Extension
+// =============================================================================
+/// Indicators computed straight from DataFrame columns
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::frame::IndicatorExt;
+/// use quantauri::base::{qstick, MaKind};
+///
+/// fn main() {
+///     let mut df = DataFrame::new(vec![]);
+///     df.push("open", Series::new(vec![10f64, 11f64, 12f64, 12f64]));
+///     df.push("close", Series::new(vec![11f64, 12f64, 11f64, 13f64]));
+///
+///     let q = df.qstick("open", "close", 2).unwrap();
+///     assert_eq!(q, qstick(&[10f64, 11f64, 12f64, 12f64], &[11f64, 12f64, 11f64, 13f64], 2, MaKind::Sma));
+///     assert!(df.qstick("open", "volume", 2).is_err());
+/// }
+/// ```
+pub trait IndicatorExt {
+    /// `qstick` with `MaKind::Sma`
+    fn qstick(&self, open: &str, close: &str, period: usize) -> Result<Vec<f64>, QuantError>;
+}
+
+impl IndicatorExt for DataFrame {
+    fn qstick(&self, open: &str, close: &str, period: usize) -> Result<Vec<f64>, QuantError> {
+        qstick_checked(&column_f64(self, open)?, &column_f64(self, close)?, period, MaKind::Sma)
+    }
+}
+
+// =============================================================================
+// JSON Output
+// =============================================================================
+/// Write a DataFrame as a JSON object of column arrays
+///
+/// # Arguments
+/// * `df` - &DataFrame
+/// * `path` - &str
+///
+/// # Description
+/// Emits `{ "column": [values...], ... }` in header order.
+/// Non-finite floats (NAN, ±inf) are written as `null`; string, char and bool
+/// columns are written as JSON strings and booleans.
+///
+/// # Errors
+/// * `Io` if the file can't be written
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::frame::write_json;
+///
+/// fn main() {
+///     let mut df = DataFrame::new(vec![]);
+///     df.push("date", Series::new(vec!["2024-01-01".to_string(), "2024-01-02".to_string()]));
+///     df.push("sma", Series::new(vec![f64::NAN, 1.5]));
+///
+///     let path = std::env::temp_dir().join("quantauri_write_json.json");
+///     let path = path.to_str().unwrap();
+///     write_json(&df, path).unwrap();
+///
+///     let text = std::fs::read_to_string(path).unwrap();
+///     assert_eq!(text, r#"{"date":["2024-01-01","2024-01-02"],"sma":[null,1.5]}"#);
+///
+///     // Round trip: null reads back as NAN
+///     let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+///     let sma: Vec<f64> = json["sma"].as_array().unwrap().iter()
+///         .map(|x| x.as_f64().unwrap_or(f64::NAN))
+///         .collect();
+///     assert!(sma[0].is_nan());
+///     assert_eq!(sma[1], 1.5);
+/// }
+/// ```
+#[cfg(feature = "json")]
+pub fn write_json(df: &DataFrame, path: &str) -> Result<(), QuantError> {
+    use serde_json::{Map, Value};
+
+    fn floats<T: Copy + Into<f64>>(v: &[T]) -> Vec<Value> {
+        v.iter().map(|&x| {
+            serde_json::Number::from_f64(x.into()).map_or(Value::Null, Value::Number)
+        }).collect()
+    }
+
+    fn values<T: Clone + Into<Value>>(v: &[T]) -> Vec<Value> {
+        v.iter().cloned().map(Into::into).collect()
+    }
+
+    let mut obj = Map::new();
+    for (i, header) in df.header().iter().enumerate() {
+        let column = match &df[i].values {
+            DTypeArray::F64(v) => floats(v),
+            DTypeArray::F32(v) => floats(v),
+            DTypeArray::USIZE(v) => values(&v.iter().map(|&x| x as u64).collect::<Vec<u64>>()),
+            DTypeArray::U8(v) => values(v),
+            DTypeArray::U16(v) => values(v),
+            DTypeArray::U32(v) => values(v),
+            DTypeArray::U64(v) => values(v),
+            DTypeArray::ISIZE(v) => values(&v.iter().map(|&x| x as i64).collect::<Vec<i64>>()),
+            DTypeArray::I8(v) => values(v),
+            DTypeArray::I16(v) => values(v),
+            DTypeArray::I32(v) => values(v),
+            DTypeArray::I64(v) => values(v),
+            DTypeArray::Bool(v) => values(v),
+            DTypeArray::Str(v) => values(v),
+            DTypeArray::Char(v) => v.iter().map(|c| Value::String(c.to_string())).collect(),
+        };
+        obj.insert(header.clone(), Value::Array(column));
+    }
+    let text = serde_json::to_string(&Value::Object(obj)).map_err(|e| QuantError::Io(e.to_string()))?;
+    std::fs::write(path, text).map_err(|e| QuantError::Io(e.to_string()))
+}
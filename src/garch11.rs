@@ -0,0 +1,225 @@
+use crate::error::QuantError;
+
+// =============================================================================
+// GARCH(1,1)
+// =============================================================================
+/// GARCH(1,1) parameters
+///
+/// sigma2[t] = omega + alpha * r[t-1]^2 + beta * sigma2[t-1], for zero-mean returns
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Garch11Params {
+    pub omega: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    /// Conditional variance of the bar after the fitted sample (forecast origin)
+    pub next_variance: f64,
+}
+
+impl Garch11Params {
+    /// Long-run variance omega / (1 - alpha - beta)
+    pub fn unconditional_variance(&self) -> f64 {
+        self.omega / (1f64 - self.alpha - self.beta)
+    }
+
+    /// Conditional volatility
+    ///
+    /// # Arguments
+    /// * `returns` - &[f64]
+    ///
+    /// # Returns
+    /// * Vec<f64>
+    ///   * sqrt(sigma2[t]), the volatility of r[t] given r[.. t]
+    ///   * sigma2[0] is the mean of `returns`², the unconditional second moment (the same seed as `fit`)
+    pub fn conditional_vol(&self, returns: &[f64]) -> Vec<f64> {
+        conditional_variance(returns, self.omega, self.alpha, self.beta).iter().map(|v| v.sqrt()).collect()
+    }
+
+    /// Volatility forecast
+    ///
+    /// # Arguments
+    /// * `horizon` - usize
+    ///
+    /// # Returns
+    /// * Vec<f64> of length `horizon`
+    ///   * k-step-ahead volatility from the end of the fitted sample:
+    ///     sigma2[n+k] = V + (alpha + beta)^(k-1) (next_variance - V), V = unconditional variance
+    ///   * Decays toward sqrt(V)
+    pub fn forecast(&self, horizon: usize) -> Vec<f64> {
+        let long_run = self.unconditional_variance();
+        let persistence = self.alpha + self.beta;
+        (0 .. horizon).map(|k| {
+            (long_run + persistence.powi(k as i32) * (self.next_variance - long_run)).sqrt()
+        }).collect()
+    }
+}
+
+/// Conditional variance recursion seeded with the unconditional second moment (`mean_square`)
+fn conditional_variance(returns: &[f64], omega: f64, alpha: f64, beta: f64) -> Vec<f64> {
+    let n = returns.len();
+    let mut var = vec![0f64; n];
+    if n == 0 {
+        return var;
+    }
+    var[0] = mean_square(returns);
+    for t in 1 .. n {
+        var[t] = omega + alpha * returns[t-1].powi(2) + beta * var[t-1];
+    }
+    var
+}
+
+/// Mean of r², the uncentered second moment (GARCH returns are taken as zero-mean)
+fn mean_square(returns: &[f64]) -> f64 {
+    returns.iter().map(|r| r * r).sum::<f64>() / returns.len() as f64
+}
+
+/// Maximum persistence alpha + beta allowed by the parametrization
+const MAX_PERSISTENCE: f64 = 0.9999;
+
+fn logistic(x: f64) -> f64 {
+    1f64 / (1f64 + (-x).exp())
+}
+
+fn logit(p: f64) -> f64 {
+    (p / (1f64 - p)).ln()
+}
+
+/// Map unconstrained optimizer coordinates to (omega, alpha, beta) with
+/// omega > 0, alpha > 0, beta > 0 and alpha + beta < 1
+fn to_params(x: &[f64; 3]) -> (f64, f64, f64) {
+    let omega = x[0].exp();
+    let persistence = MAX_PERSISTENCE * logistic(x[1]);
+    let alpha = persistence * logistic(x[2]);
+    (omega, alpha, persistence - alpha)
+}
+
+/// Gaussian negative log-likelihood (up to a constant)
+fn neg_log_likelihood(returns: &[f64], x: &[f64; 3]) -> f64 {
+    let (omega, alpha, beta) = to_params(x);
+    let var = conditional_variance(returns, omega, alpha, beta);
+    let nll = (0 .. returns.len()).map(|t| var[t].ln() + returns[t].powi(2) / var[t]).sum::<f64>() / 2f64;
+    if nll.is_finite() { nll } else { f64::INFINITY }
+}
+
+/// Nelder-Mead simplex minimization in 3 dimensions
+fn nelder_mead<F: Fn(&[f64; 3]) -> f64>(f: F, start: [f64; 3], max_iter: usize, tol: f64) -> Result<[f64; 3], QuantError> {
+    let mut simplex = vec![start];
+    for i in 0 .. 3 {
+        let mut p = start;
+        p[i] += 0.5;
+        simplex.push(p);
+    }
+    let mut values = simplex.iter().map(&f).collect::<Vec<f64>>();
+    let combine = |a: &[f64; 3], b: &[f64; 3], t: f64| {
+        [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1]), a[2] + t * (b[2] - a[2])]
+    };
+    for _ in 0 .. max_iter {
+        let mut order = (0 .. 4).collect::<Vec<usize>>();
+        order.sort_by(|&i, &j| values[i].partial_cmp(&values[j]).unwrap());
+        simplex = order.iter().map(|&i| simplex[i]).collect();
+        values = order.iter().map(|&i| values[i]).collect();
+        if (values[3] - values[0]).abs() <= tol * (1f64 + values[0].abs()) {
+            return Ok(simplex[0]);
+        }
+        let mut centroid = [0f64; 3];
+        for p in simplex[.. 3].iter() {
+            for k in 0 .. 3 {
+                centroid[k] += p[k] / 3f64;
+            }
+        }
+        let reflected = combine(&centroid, &simplex[3], -1f64);
+        let f_r = f(&reflected);
+        if f_r < values[0] {
+            let expanded = combine(&centroid, &simplex[3], -2f64);
+            let f_e = f(&expanded);
+            if f_e < f_r {
+                simplex[3] = expanded;
+                values[3] = f_e;
+            } else {
+                simplex[3] = reflected;
+                values[3] = f_r;
+            }
+        } else if f_r < values[2] {
+            simplex[3] = reflected;
+            values[3] = f_r;
+        } else {
+            let contracted = combine(&centroid, &simplex[3], 0.5);
+            let f_c = f(&contracted);
+            if f_c < values[3] {
+                simplex[3] = contracted;
+                values[3] = f_c;
+            } else {
+                for i in 1 .. 4 {
+                    simplex[i] = combine(&simplex[0], &simplex[i], 0.5);
+                    values[i] = f(&simplex[i]);
+                }
+            }
+        }
+    }
+    Err(QuantError::NoConvergence { iterations: max_iter })
+}
+
+/// Fit GARCH(1,1) by maximum likelihood
+///
+/// # Arguments
+/// * `returns` - &[f64] (zero-mean returns, e.g. demeaned log returns)
+///
+/// # Returns
+/// * `Result<Garch11Params, QuantError>`
+///   * Gaussian maximum likelihood with a Nelder-Mead simplex
+///   * The search runs on a parametrization that enforces omega > 0, alpha > 0, beta > 0
+///     and stationarity alpha + beta < 1 (capped at 0.9999)
+///   * sigma2[0] is seeded with the mean of r², the unconditional second moment
+///
+/// # Errors
+/// * `InvalidParameter` for fewer than 10 returns, a NAN return, or a zero series
+/// * `NoConvergence` if the simplex has not converged after 5000 iterations
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::garch11::fit;
+///
+/// fn main() {
+///     let (omega, alpha, beta) = (0.05, 0.1, 0.85);
+///     let n = 10_000;
+///     let z = Normal(0, 1).sample(n);
+///     let mut r = vec![0f64; n];
+///     let mut var = omega / (1f64 - alpha - beta);
+///     for t in 0 .. n {
+///         if t > 0 {
+///             var = omega + alpha * r[t-1].powi(2) + beta * var;
+///         }
+///         r[t] = var.sqrt() * z[t];
+///     }
+///     let p = fit(&r).unwrap();
+///     assert!((p.alpha - alpha).abs() < 0.05);
+///     assert!((p.beta - beta).abs() < 0.07);
+///     assert!(p.alpha + p.beta < 1f64);
+///
+///     let vol = p.conditional_vol(&r);
+///     assert_eq!(vol.len(), n);
+///     let f = p.forecast(500);
+///     assert!((f[0] - p.next_variance.sqrt()).abs() < 1e-12);
+///     assert!((f[499] - p.unconditional_variance().sqrt()).abs() < 1e-3);
+/// }
+/// ```
+pub fn fit(returns: &[f64]) -> Result<Garch11Params, QuantError> {
+    if returns.len() < 10 {
+        return Err(QuantError::InvalidParameter("GARCH(1,1) needs at least 10 returns".to_string()));
+    }
+    if returns.iter().any(|r| r.is_nan()) {
+        return Err(QuantError::InvalidParameter("returns contain NAN".to_string()));
+    }
+    let var = mean_square(returns);
+    if var == 0f64 {
+        return Err(QuantError::InvalidParameter("returns are all zero".to_string()));
+    }
+    // Start from alpha = 0.05, beta = 0.9 with the matching long-run variance
+    let start = [(0.05 * var).ln(), logit(0.95 / MAX_PERSISTENCE), logit(0.05 / 0.95)];
+    let x = nelder_mead(|x| neg_log_likelihood(returns, x), start, 5000, 1e-10)?;
+    let (omega, alpha, beta) = to_params(&x);
+    let n = returns.len();
+    let last = conditional_variance(returns, omega, alpha, beta)[n - 1];
+    let next_variance = omega + alpha * returns[n - 1].powi(2) + beta * last;
+    Ok(Garch11Params { omega, alpha, beta, next_variance })
+}
@@ -0,0 +1,305 @@
+use crate::error::{QuantError, check_nonempty};
+
+// =============================================================================
+// Kalman Smoother
+// =============================================================================
+/// State space model for `KalmanSmoother`
+///
+/// * `LocalLevel`: x[t] = x[t-1] + w[t], v[t] = x[t] + e[t]
+/// * `LocalLinearTrend`: x[t] = x[t-1] + b[t-1] + w[t], b[t] = b[t-1] + u[t], v[t] = x[t] + e[t]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KalmanModel {
+    LocalLevel,
+    LocalLinearTrend,
+}
+
+/// Kalman filter & Rauch-Tung-Striebel smoother for the level of a series
+///
+/// # Fields
+/// * `model` - KalmanModel
+/// * `level_var` - variance of the level noise w
+/// * `slope_var` - variance of the slope noise u (ignored for `LocalLevel`)
+/// * `observation_var` - variance of the observation noise e
+///
+/// # Description
+/// The ratio of the noise variances sets the smoothness: a small `level_var / observation_var`
+/// gives a smooth, slowly adapting level. Unlike `ema`, the local linear trend model carries a
+/// slope estimate, so it has no steady-state lag on a ramp.
+///
+/// NAN observations are skipped (prediction only).
+/// The first state is diffuse, so the output starts at `v[0]`.
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::base::ema;
+/// use quantauri::kalman::KalmanSmoother;
+///
+/// fn main() {
+///     // Noisy ramp
+///     let n = 400;
+///     let truth = (0 .. n).map(|t| 0.1 * t as f64).collect::<Vec<f64>>();
+///     let noise = Normal(0f64, 1f64).sample(n);
+///     let v = truth.add_v(&noise);
+///
+///     let kf = KalmanSmoother::local_linear_trend(0f64, 1e-5, 1f64).filter(&v);
+///     let e = ema(&v, 20);
+///
+///     // Smoothness: std of first differences (noise in the output)
+///     let roughness = |x: &[f64]| {
+///         let d = (n/2 .. n).map(|t| x[t] - x[t-1]).collect::<Vec<f64>>();
+///         d.sd()
+///     };
+///     assert!(roughness(&kf) < roughness(&e));
+///
+///     // Lag: mean shortfall against the truth (EMA lags by ~0.1 * 19 / 2)
+///     let lag = |x: &[f64]| (n/2 .. n).map(|t| truth[t] - x[t]).sum::<f64>() / (n/2) as f64;
+///     assert!(lag(&e) > 0.6);
+///     assert!(lag(&kf).abs() < 0.4);
+///
+///     // The smoother uses future data too, so it's closer to the truth than the filter
+///     let sm = KalmanSmoother::local_linear_trend(0f64, 1e-5, 1f64).smooth(&v);
+///     let rmse = |x: &[f64]| ((0 .. n).map(|t| (truth[t] - x[t]).powi(2)).sum::<f64>() / n as f64).sqrt();
+///     assert!(rmse(&sm) < rmse(&kf));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KalmanSmoother {
+    pub model: KalmanModel,
+    pub level_var: f64,
+    pub slope_var: f64,
+    pub observation_var: f64,
+}
+
+/// Filtered or predicted state (level, slope) with its covariance [p00, p01, p11]
+#[derive(Debug, Clone, Copy)]
+struct State {
+    x: [f64; 2],
+    p: [f64; 3],
+}
+
+/// Output of a forward pass
+struct FilterPass {
+    predicted: Vec<State>,
+    filtered: Vec<State>,
+    /// Sum of ln(F) and of innovation^2 / F over the non-diffuse observations, and their count
+    log_det: f64,
+    scaled_sse: f64,
+    count: usize,
+}
+
+impl KalmanSmoother {
+    /// Local level model
+    pub fn local_level(level_var: f64, observation_var: f64) -> Self {
+        KalmanSmoother {
+            model: KalmanModel::LocalLevel,
+            level_var,
+            slope_var: 0f64,
+            observation_var,
+        }
+    }
+
+    /// Local linear trend model
+    pub fn local_linear_trend(level_var: f64, slope_var: f64, observation_var: f64) -> Self {
+        KalmanSmoother {
+            model: KalmanModel::LocalLinearTrend,
+            level_var,
+            slope_var,
+            observation_var,
+        }
+    }
+
+    /// Number of diffuse states (observations excluded from the likelihood)
+    fn diffuse_states(&self) -> usize {
+        match self.model {
+            KalmanModel::LocalLevel => 1,
+            KalmanModel::LocalLinearTrend => 2,
+        }
+    }
+
+    fn forward(&self, v: &[f64]) -> FilterPass {
+        let n = v.len();
+        let scale = v.iter().filter(|x| x.is_finite()).map(|x| x * x).fold(0f64, f64::max);
+        let kappa = 1e7 * (1f64 + scale + self.observation_var);
+        let slope_var = match self.model {
+            KalmanModel::LocalLevel => 0f64,
+            KalmanModel::LocalLinearTrend => self.slope_var,
+        };
+        let has_slope = self.model == KalmanModel::LocalLinearTrend;
+
+        let mut predicted = Vec::with_capacity(n);
+        let mut filtered = Vec::with_capacity(n);
+        let mut log_det = 0f64;
+        let mut scaled_sse = 0f64;
+        let mut count = 0usize;
+        let mut observed = 0usize;
+
+        // Diffuse prior
+        let mut state = State {
+            x: [0f64, 0f64],
+            p: [kappa, 0f64, if has_slope { kappa } else { 0f64 }],
+        };
+        for t in 0 .. n {
+            if t > 0 {
+                // Predict: F = [[1, 1], [0, 1]], Q = diag(level_var, slope_var)
+                let [x0, x1] = state.x;
+                let [p00, p01, p11] = state.p;
+                state = State {
+                    x: [x0 + x1, x1],
+                    p: [p00 + 2f64 * p01 + p11 + self.level_var, p01 + p11, p11 + slope_var],
+                };
+            }
+            predicted.push(state);
+            if !v[t].is_nan() {
+                // Update: H = [1, 0]
+                let [x0, x1] = state.x;
+                let [p00, p01, p11] = state.p;
+                let f = p00 + self.observation_var;
+                let innovation = v[t] - x0;
+                let (k0, k1) = (p00 / f, p01 / f);
+                // (1 - k0) = observation_var / f avoids cancellation under the diffuse prior
+                let shrink = self.observation_var / f;
+                state = State {
+                    x: [x0 + k0 * innovation, x1 + k1 * innovation],
+                    p: [p00 * shrink, p01 * shrink, p11 - k1 * p01],
+                };
+                if observed >= self.diffuse_states() {
+                    log_det += f.ln();
+                    scaled_sse += innovation * innovation / f;
+                    count += 1;
+                }
+                observed += 1;
+            }
+            filtered.push(state);
+        }
+        FilterPass { predicted, filtered, log_det, scaled_sse, count }
+    }
+
+    /// Filtered level (uses data up to t)
+    ///
+    /// # Arguments
+    /// * `v` - &[f64]
+    ///
+    /// # Returns
+    /// * Vec<f64> - E[x[t] | v[0 ..= t]]
+    pub fn filter(&self, v: &[f64]) -> Vec<f64> {
+        self.forward(v).filtered.iter().map(|s| s.x[0]).collect()
+    }
+
+    /// Smoothed level (uses the whole sample)
+    ///
+    /// # Arguments
+    /// * `v` - &[f64]
+    ///
+    /// # Returns
+    /// * Vec<f64> - E[x[t] | v[0 .. n]] via the Rauch-Tung-Striebel backward pass
+    ///
+    /// # Caution
+    /// * Looks ahead: use `filter` for anything that feeds a trading decision
+    pub fn smooth(&self, v: &[f64]) -> Vec<f64> {
+        let n = v.len();
+        if n == 0 {
+            return vec![];
+        }
+        let FilterPass { predicted, filtered, .. } = self.forward(v);
+        let mut x = filtered[n - 1].x;
+        let mut result = vec![0f64; n];
+        result[n - 1] = x[0];
+        for t in (0 .. n - 1).rev() {
+            // Gain C = P_filt[t] F' P_pred[t+1]^-1
+            let [a00, a01, a11] = filtered[t].p;
+            let [b00, b01, b11] = predicted[t + 1].p;
+            // P_filt F' = [[a00 + a01, a01], [a01 + a11, a11]]
+            let m = [[a00 + a01, a01], [a01 + a11, a11]];
+            let c = if b11 > 0f64 {
+                let det = b00 * b11 - b01 * b01;
+                [
+                    [(m[0][0] * b11 - m[0][1] * b01) / det, (m[0][1] * b00 - m[0][0] * b01) / det],
+                    [(m[1][0] * b11 - m[1][1] * b01) / det, (m[1][1] * b00 - m[1][0] * b01) / det],
+                ]
+            } else {
+                // Local level: the slope is fixed at 0
+                [[m[0][0] / b00, 0f64], [0f64, 0f64]]
+            };
+            let d = [x[0] - predicted[t + 1].x[0], x[1] - predicted[t + 1].x[1]];
+            let xf = filtered[t].x;
+            x = [
+                xf[0] + c[0][0] * d[0] + c[0][1] * d[1],
+                xf[1] + c[1][0] * d[0] + c[1][1] * d[1],
+            ];
+            result[t] = x[0];
+        }
+        result
+    }
+
+    /// Estimate the noise variances by a likelihood grid
+    ///
+    /// # Arguments
+    /// * `v` - &[f64]
+    /// * `model` - KalmanModel
+    ///
+    /// # Returns
+    /// * `Result<KalmanSmoother, QuantError>`
+    ///   * Searches the signal-to-noise ratios `level_var / observation_var` (and
+    ///     `slope_var / observation_var`) on a log grid over 1e-6 ..= 1e2
+    ///   * `observation_var` is concentrated out of the Gaussian likelihood
+    ///
+    /// # Errors
+    /// * `EmptyInput` if `v` is empty
+    /// * `InvalidParameter` if `v` has too few observations for the model
+    ///
+    /// # Examples
+    /// ```
+    /// use peroxide::fuga::*;
+    /// use quantauri::kalman::{KalmanSmoother, KalmanModel};
+    ///
+    /// fn main() {
+    ///     // Random walk (level_var = 1) observed with noise (observation_var = 4)
+    ///     let n = 2000;
+    ///     let w = Normal(0f64, 1f64).sample(n);
+    ///     let e = Normal(0f64, 2f64).sample(n);
+    ///     let mut level = 0f64;
+    ///     let mut v = vec![0f64; n];
+    ///     for t in 0 .. n {
+    ///         level += w[t];
+    ///         v[t] = level + e[t];
+    ///     }
+    ///     let kf = KalmanSmoother::fit(&v, KalmanModel::LocalLevel).unwrap();
+    ///     let ratio = kf.level_var / kf.observation_var;
+    ///     assert!(ratio > 0.1 && ratio < 0.6);
+    ///     assert!((kf.observation_var - 4f64).abs() < 1.5);
+    /// }
+    /// ```
+    pub fn fit(v: &[f64], model: KalmanModel) -> Result<Self, QuantError> {
+        check_nonempty(v)?;
+        let grid = (0 ..= 32).map(|i| 10f64.powf(-6f64 + 0.25 * i as f64)).collect::<Vec<f64>>();
+        let slope_grid = match model {
+            KalmanModel::LocalLevel => vec![0f64],
+            KalmanModel::LocalLinearTrend => grid.clone(),
+        };
+        let mut best: Option<(f64, KalmanSmoother)> = None;
+        for &q_level in grid.iter() {
+            for &q_slope in slope_grid.iter() {
+                let candidate = KalmanSmoother { model, level_var: q_level, slope_var: q_slope, observation_var: 1f64 };
+                let pass = candidate.forward(v);
+                if pass.count == 0 {
+                    return Err(QuantError::InvalidParameter("too few observations for the model".to_string()));
+                }
+                let r = pass.scaled_sse / pass.count as f64;
+                if r <= 0f64 {
+                    continue;
+                }
+                let nll = pass.log_det + pass.count as f64 * r.ln();
+                if best.is_none_or(|(b, _)| nll < b) {
+                    best = Some((nll, KalmanSmoother {
+                        model,
+                        level_var: q_level * r,
+                        slope_var: q_slope * r,
+                        observation_var: r,
+                    }));
+                }
+            }
+        }
+        best.map(|(_, kf)| kf).ok_or_else(|| QuantError::InvalidParameter("series has no variation".to_string()))
+    }
+}
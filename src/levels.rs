@@ -0,0 +1,631 @@
+use crate::base::atr;
+use crate::error::{QuantError, check_len};
+
+// =============================================================================
+// Fibonacci Levels
+// =============================================================================
+/// Fibonacci retracement ratios (0, 23.6, 38.2, 50, 61.8, 78.6, 100 %)
+pub const FIB_RETRACEMENT_RATIOS: [f64; 7] = [0.0, 0.236, 0.382, 0.5, 0.618, 0.786, 1.0];
+
+/// Fibonacci extension ratios (127.2, 161.8 %)
+pub const FIB_EXTENSION_RATIOS: [f64; 2] = [1.272, 1.618];
+
+/// Fibonacci Retracement Levels
+///
+/// # Arguments
+/// * `high` - f64 (swing high)
+/// * `low` - f64 (swing low)
+///
+/// # Returns
+/// * [f64; 7]
+///   * level = high - ratio * (high - low)
+///   * 0% level is `high`, 100% level is `low`
+///
+/// # Examples
+/// ```
+/// use quantauri::levels::fib_retracements;
+///
+/// fn main() {
+///     let levels = fib_retracements(200f64, 100f64);
+///     assert_eq!(levels[0], 200f64);
+///     assert_eq!(levels[3], 150f64);
+///     assert_eq!(levels[6], 100f64);
+/// }
+/// ```
+pub fn fib_retracements(high: f64, low: f64) -> [f64; 7] {
+    let range = high - low;
+    let mut result = [0f64; 7];
+    for i in 0 .. 7 {
+        result[i] = high - FIB_RETRACEMENT_RATIOS[i] * range;
+    }
+    result
+}
+
+/// Fibonacci Extension Levels
+///
+/// # Arguments
+/// * `high` - f64 (swing high)
+/// * `low` - f64 (swing low)
+///
+/// # Returns
+/// * [f64; 2]
+///   * level = high - ratio * (high - low) (127.2%, 161.8%)
+///   * Same direction as `fib_retracements`, so the levels lie beyond `low`.
+///     Swap the arguments to project above `high` instead.
+pub fn fib_extensions(high: f64, low: f64) -> [f64; 2] {
+    let range = high - low;
+    let mut result = [0f64; 2];
+    for i in 0 .. 2 {
+        result[i] = high - FIB_EXTENSION_RATIOS[i] * range;
+    }
+    result
+}
+
+// =============================================================================
+// Swing Points
+// =============================================================================
+/// Swing High / Swing Low detection
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `lookback` - usize
+///
+/// # Returns
+/// * (Vec<usize>, Vec<usize>)
+///   * (swing high indices, swing low indices)
+///
+/// # Description
+/// A bar `i` is a swing high if `v[i]` is the highest value in
+/// `v[i-lookback ..= i+lookback]` (swing low: lowest).
+///
+/// * Bars closer than `lookback` to either end of `v` do not have a full
+///   window on both sides, so they are never reported (an unconfirmed pivot
+///   could still be invalidated by future data).
+/// * On a plateau of equal values only the first bar is reported
+///   (strict on the left, non-strict on the right).
+///
+/// # Examples
+/// ```
+/// use quantauri::levels::{swing_points, fib_retracements};
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 5f64, 2f64, 1f64, 0f64, 1f64, 3f64, 3f64, 1f64];
+///     let (highs, lows) = swing_points(&v, 2);
+///     assert_eq!(highs, vec![2, 7]);
+///     assert_eq!(lows, vec![5]);
+///
+///     let levels = fib_retracements(v[highs[0]], v[lows[0]]);
+///     assert_eq!(levels[3], 2.5);
+/// }
+/// ```
+pub fn swing_points(v: &[f64], lookback: usize) -> (Vec<usize>, Vec<usize>) {
+    let mut highs = vec![];
+    let mut lows = vec![];
+    if v.len() < 2 * lookback + 1 {
+        return (highs, lows);
+    }
+    for i in lookback .. v.len() - lookback {
+        let mut is_high = true;
+        let mut is_low = true;
+        for j in i - lookback .. i {
+            is_high &= v[j] < v[i];
+            is_low &= v[j] > v[i];
+        }
+        for j in i + 1 ..= i + lookback {
+            is_high &= v[j] <= v[i];
+            is_low &= v[j] >= v[i];
+        }
+        if is_high {
+            highs.push(i);
+        }
+        if is_low {
+            lows.push(i);
+        }
+    }
+    (highs, lows)
+}
+
+// =============================================================================
+// Pivot Points
+// =============================================================================
+/// Pivot Point formula family
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PivotMode {
+    Classic,
+    Fibonacci,
+    Camarilla,
+}
+
+/// Pivot Point levels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PivotLevels {
+    pub pivot: f64,
+    pub r1: f64,
+    pub r2: f64,
+    pub r3: f64,
+    pub s1: f64,
+    pub s2: f64,
+    pub s3: f64,
+}
+
+impl PivotLevels {
+    fn nan() -> Self {
+        PivotLevels {
+            pivot: f64::NAN,
+            r1: f64::NAN,
+            r2: f64::NAN,
+            r3: f64::NAN,
+            s1: f64::NAN,
+            s2: f64::NAN,
+            s3: f64::NAN,
+        }
+    }
+}
+
+/// Pivot Points from the prior session
+///
+/// # Arguments
+/// * `high` - f64 (prior session high)
+/// * `low` - f64 (prior session low)
+/// * `close` - f64 (prior session close)
+/// * `mode` - PivotMode
+///
+/// # Returns
+/// * PivotLevels
+///   * pivot = (high + low + close) / 3 (all modes)
+///   * `Classic`
+///     * r1 = 2p - low, r2 = p + (high - low), r3 = high + 2(p - low)
+///     * s1 = 2p - high, s2 = p - (high - low), s3 = low - 2(high - p)
+///   * `Fibonacci`
+///     * r_k = p + f_k (high - low), s_k = p - f_k (high - low), f = (0.382, 0.618, 1.0)
+///   * `Camarilla`
+///     * r_k = close + c_k (high - low), s_k = close - c_k (high - low), c = 1.1 * (1/12, 1/6, 1/4)
+///
+/// # Examples
+/// ```
+/// use quantauri::levels::{pivot_points, PivotMode};
+///
+/// fn main() {
+///     let p = pivot_points(110f64, 90f64, 100f64, PivotMode::Classic);
+///     assert_eq!(p.pivot, 100f64);
+///     assert_eq!((p.r1, p.r2, p.r3), (110f64, 120f64, 130f64));
+///     assert_eq!((p.s1, p.s2, p.s3), (90f64, 80f64, 70f64));
+/// }
+/// ```
+pub fn pivot_points(high: f64, low: f64, close: f64, mode: PivotMode) -> PivotLevels {
+    let pivot = (high + low + close) / 3f64;
+    let range = high - low;
+    match mode {
+        PivotMode::Classic => PivotLevels {
+            pivot,
+            r1: 2f64 * pivot - low,
+            r2: pivot + range,
+            r3: high + 2f64 * (pivot - low),
+            s1: 2f64 * pivot - high,
+            s2: pivot - range,
+            s3: low - 2f64 * (high - pivot),
+        },
+        PivotMode::Fibonacci => PivotLevels {
+            pivot,
+            r1: pivot + 0.382 * range,
+            r2: pivot + 0.618 * range,
+            r3: pivot + range,
+            s1: pivot - 0.382 * range,
+            s2: pivot - 0.618 * range,
+            s3: pivot - range,
+        },
+        PivotMode::Camarilla => PivotLevels {
+            pivot,
+            r1: close + 1.1 * range / 12f64,
+            r2: close + 1.1 * range / 6f64,
+            r3: close + 1.1 * range / 4f64,
+            s1: close - 1.1 * range / 12f64,
+            s2: close - 1.1 * range / 6f64,
+            s3: close - 1.1 * range / 4f64,
+        },
+    }
+}
+
+/// Pivot Points over a full history
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `session_starts` - &[usize] (sorted index of the first bar of each session)
+/// * `mode` - PivotMode
+///
+/// # Returns
+/// * Vec<PivotLevels> (one per bar)
+///   * Every bar of session `k` gets the pivots of session `k-1`
+///     (session high/low and the close of its last bar)
+///   * Bars of the first session (and bars before `session_starts[0]`) are NAN
+///   * For daily bars use `session_starts = 0 .. n`
+///
+/// # Examples
+/// ```
+/// use quantauri::levels::{pivot_series, PivotMode};
+///
+/// fn main() {
+///     let high  = vec![11f64, 12f64, 13f64, 20f64, 21f64];
+///     let low   = vec![ 9f64, 10f64,  8f64, 18f64, 19f64];
+///     let close = vec![10f64, 11f64,  9f64, 19f64, 20f64];
+///     let pivots = pivot_series(&high, &low, &close, &[0, 3], PivotMode::Classic);
+///     assert!(pivots[2].pivot.is_nan());
+///     // Prior session: high 13, low 8, close 9
+///     assert_eq!(pivots[3].pivot, 10f64);
+///     assert_eq!(pivots[4], pivots[3]);
+/// }
+/// ```
+pub fn pivot_series(high: &[f64], low: &[f64], close: &[f64], session_starts: &[usize], mode: PivotMode) -> Vec<PivotLevels> {
+    let n = close.len();
+    let mut result = vec![PivotLevels::nan(); n];
+    for k in 1 .. session_starts.len() {
+        let prev_start = session_starts[k-1];
+        let start = session_starts[k].min(n);
+        let end = if k + 1 < session_starts.len() { session_starts[k+1].min(n) } else { n };
+        if prev_start >= start {
+            continue;
+        }
+        let h = high[prev_start .. start].iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        let l = low[prev_start .. start].iter().fold(f64::INFINITY, |a, &b| a.min(b));
+        let levels = pivot_points(h, l, close[start - 1], mode);
+        for i in start .. end {
+            result[i] = levels;
+        }
+    }
+    result
+}
+
+// =============================================================================
+// Renko
+// =============================================================================
+/// Renko brick
+///
+/// # Fields
+/// * `index` - bar index of the close that completed the brick
+/// * `open`, `close` - brick boundaries
+/// * `direction` - +1 (up) or -1 (down)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenkoBrick {
+    pub index: usize,
+    pub open: f64,
+    pub close: f64,
+    pub direction: i8,
+}
+
+/// Renko bricks with a fixed brick size
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `brick_size` - f64
+///
+/// # Returns
+/// * Vec<RenkoBrick>
+///
+/// # Description
+/// * The first close is the reference level.
+/// * A new brick is added each time price closes at least one full `brick_size`
+///   beyond the last brick's close, in either direction. Each brick opens at the
+///   previous brick's close, so a reversal brick covers part of the last brick's body.
+/// * One bar can emit several bricks; a partial move never emits a half-brick.
+/// * NAN closes are skipped; a non-positive `brick_size` gives no bricks.
+///
+/// # Examples
+/// ```
+/// use quantauri::levels::renko;
+///
+/// fn main() {
+///     // Gap up of 35 then a drop
+///     let close = vec![100f64, 135f64, 125f64, 105f64];
+///     let bricks = renko(&close, 10f64);
+///     let bounds = bricks.iter().map(|b| (b.open, b.close, b.direction)).collect::<Vec<_>>();
+///     assert_eq!(bounds, vec![
+///         (100f64, 110f64, 1),
+///         (110f64, 120f64, 1),
+///         (120f64, 130f64, 1),
+///         (130f64, 120f64, -1),
+///         (120f64, 110f64, -1),
+///     ]);
+///     assert_eq!(bricks[2].index, 1);
+///     assert_eq!(bricks[3].index, 3);
+/// }
+/// ```
+pub fn renko(close: &[f64], brick_size: f64) -> Vec<RenkoBrick> {
+    build_renko(close, |_| brick_size)
+}
+
+/// Renko bricks sized by the Average True Range
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `atr_period` - usize
+///
+/// # Returns
+/// * Vec<RenkoBrick>
+///   * As `renko`, with the brick size at bar i = `atr(high, low, close, atr_period)[i]`
+///   * Bricks emitted on one bar share that bar's size; earlier bricks keep the size
+///     they were formed with, so no brick depends on later data
+///
+/// # Examples
+/// ```
+/// use quantauri::levels::renko_atr;
+/// use quantauri::base::atr;
+///
+/// fn main() {
+///     let close = (0 .. 60).map(|x| 100f64 + x as f64 + 3f64 * (x as f64 * 0.5).sin()).collect::<Vec<f64>>();
+///     let high = close.iter().map(|x| x + 1f64).collect::<Vec<f64>>();
+///     let low = close.iter().map(|x| x - 1f64).collect::<Vec<f64>>();
+///     let bricks = renko_atr(&close, &high, &low, 14);
+///     let a = atr(&high, &low, &close, 14);
+///     assert!(!bricks.is_empty());
+///     // Sized by the ATR known when each brick formed
+///     assert!(bricks.iter().all(|b| ((b.close - b.open).abs() - a[b.index]).abs() < 1e-9));
+///
+///     // Adding future bars does not change past bricks
+///     let past = renko_atr(&close[.. 30], &high[.. 30], &low[.. 30], 14);
+///     assert_eq!(past[..], bricks[.. past.len()]);
+/// }
+/// ```
+pub fn renko_atr(close: &[f64], high: &[f64], low: &[f64], atr_period: usize) -> Vec<RenkoBrick> {
+    let sizes = atr(high, low, close, atr_period);
+    build_renko(close, |i| sizes[i])
+}
+
+/// Renko bricks with the brick size of each bar given by `size`
+fn build_renko(close: &[f64], size: impl Fn(usize) -> f64) -> Vec<RenkoBrick> {
+    let mut bricks: Vec<RenkoBrick> = vec![];
+    let mut level: Option<f64> = None;
+    for (i, &p) in close.iter().enumerate() {
+        if p.is_nan() {
+            continue;
+        }
+        let mut last = match level {
+            Some(l) => l,
+            None => {
+                level = Some(p);
+                continue;
+            }
+        };
+        let brick_size = size(i);
+        if brick_size.is_nan() || brick_size <= 0f64 {
+            continue;
+        }
+        while p >= last + brick_size {
+            bricks.push(RenkoBrick { index: i, open: last, close: last + brick_size, direction: 1 });
+            last += brick_size;
+        }
+        while p <= last - brick_size {
+            bricks.push(RenkoBrick { index: i, open: last, close: last - brick_size, direction: -1 });
+            last -= brick_size;
+        }
+        level = Some(last);
+    }
+    bricks
+}
+
+// =============================================================================
+// Volume Profile
+// =============================================================================
+/// Default share of volume inside the value area
+pub const DEFAULT_VALUE_AREA: f64 = 0.7;
+
+/// Price-by-volume histogram
+///
+/// # Fields
+/// * `edges` - bin edges (`bins + 1`, ascending)
+/// * `volume` - volume traded in each bin
+/// * `poc` - point of control: center of the highest-volume bin
+/// * `value_area_high`, `value_area_low` - edges of the value area around the POC
+///
+/// The price levels are NAN when there is no volume.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeProfile {
+    pub edges: Vec<f64>,
+    pub volume: Vec<f64>,
+    pub poc: f64,
+    pub value_area_high: f64,
+    pub value_area_low: f64,
+}
+
+/// Point of control and value area per bar (see `rolling_volume_profile`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeProfileSeries {
+    pub poc: Vec<f64>,
+    pub value_area_high: Vec<f64>,
+    pub value_area_low: Vec<f64>,
+}
+
+/// Volume Profile
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `volume` - &[f64]
+/// * `bins` - usize
+/// * `range` - Option<(f64, f64)> (`None`: min and max of `close`)
+///
+/// # Returns
+/// * VolumeProfile with a `DEFAULT_VALUE_AREA` (70%) value area
+///   * Bins are `[lo, hi)` of equal width, the last one is closed (`close == hi` counts)
+///   * Closes outside `range` and bars with a NAN close or volume are ignored
+///   * A zero-width range (single price) puts all the volume in bin 0,
+///     so every edge, the POC and the value area equal that price
+///
+/// # Description
+/// The value area starts at the POC bin and repeatedly adds the neighbouring bin with
+/// more volume (the upper one on ties) until it holds `value_area` of the total volume.
+///
+/// # Examples
+/// ```
+/// use quantauri::levels::volume_profile;
+///
+/// fn main() {
+///     let close = vec![0.5, 1.5, 2.5, 3.5, 4.5, 2.2];
+///     let volume = vec![10f64, 20f64, 30f64, 20f64, 10f64, 10f64];
+///     let vp = volume_profile(&close, &volume, 5, Some((0f64, 5f64)));
+///     assert_eq!(vp.edges, vec![0f64, 1f64, 2f64, 3f64, 4f64, 5f64]);
+///     assert_eq!(vp.volume, vec![10f64, 20f64, 40f64, 20f64, 10f64]);
+///     assert_eq!(vp.poc, 2.5);
+///     // 40 -> +20 above (tie) -> +20 below = 80 >= 70
+///     assert_eq!((vp.value_area_low, vp.value_area_high), (1f64, 4f64));
+///
+///     // Single price
+///     let vp = volume_profile(&[7f64; 3], &[1f64; 3], 10, None);
+///     assert_eq!(vp.volume[0], 3f64);
+///     assert_eq!((vp.poc, vp.value_area_low, vp.value_area_high), (7f64, 7f64, 7f64));
+/// }
+/// ```
+pub fn volume_profile(close: &[f64], volume: &[f64], bins: usize, range: Option<(f64, f64)>) -> VolumeProfile {
+    volume_profile_with(close, volume, bins, range, DEFAULT_VALUE_AREA)
+}
+
+/// Volume Profile with a custom value area
+///
+/// # Arguments
+/// * `value_area` - f64 in (0, 1] (share of the total volume)
+pub fn volume_profile_with(close: &[f64], volume: &[f64], bins: usize, range: Option<(f64, f64)>, value_area: f64) -> VolumeProfile {
+    volume_profile_checked(close, volume, bins, range, value_area).expect("volume_profile: invalid input")
+}
+
+/// Volume Profile (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `close` and `volume` differ in length
+/// * `InvalidParameter` if `bins == 0`, `value_area` is not in (0, 1],
+///   or `range` is not finite or has `lo > hi`
+pub fn volume_profile_checked(close: &[f64], volume: &[f64], bins: usize, range: Option<(f64, f64)>, value_area: f64) -> Result<VolumeProfile, QuantError> {
+    check_len(close.len(), volume.len())?;
+    check_profile_params(bins, value_area)?;
+    let (lo, hi) = match range {
+        Some((lo, hi)) => {
+            if !(lo.is_finite() && hi.is_finite()) || lo > hi {
+                return Err(QuantError::InvalidParameter(format!("invalid price range ({}, {})", lo, hi)));
+            }
+            (lo, hi)
+        }
+        None => {
+            let (lo, hi) = close.iter().filter(|x| x.is_finite())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| (lo.min(x), hi.max(x)));
+            if lo > hi {
+                // No valid close: the range is empty
+                (f64::NAN, f64::NAN)
+            } else {
+                (lo, hi)
+            }
+        }
+    };
+    Ok(build_profile(close, volume, bins, lo, hi, value_area))
+}
+
+/// Rolling Volume Profile
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `volume` - &[f64]
+/// * `bins` - usize
+/// * `window` - usize
+///
+/// # Returns
+/// * VolumeProfileSeries (one value per bar)
+///   * Bar i: `volume_profile` of the trailing window `[i + 1 - window, i]` over its own
+///     min-max range, with the default 70% value area
+///   * First `window - 1` bars are NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::levels::{rolling_volume_profile, volume_profile};
+///
+/// fn main() {
+///     let close = vec![10f64, 11f64, 10f64, 12f64, 13f64, 12f64];
+///     let volume = vec![5f64, 1f64, 5f64, 2f64, 1f64, 6f64];
+///     let series = rolling_volume_profile(&close, &volume, 4, 3);
+///     assert!(series.poc[1].is_nan());
+///     let last = volume_profile(&close[3 ..], &volume[3 ..], 4, None);
+///     assert_eq!(series.poc[5], last.poc);
+///     assert_eq!(series.value_area_high[5], last.value_area_high);
+///     assert_eq!(series.value_area_low[5], last.value_area_low);
+/// }
+/// ```
+pub fn rolling_volume_profile(close: &[f64], volume: &[f64], bins: usize, window: usize) -> VolumeProfileSeries {
+    rolling_volume_profile_checked(close, volume, bins, window).expect("rolling_volume_profile: invalid input")
+}
+
+/// Rolling Volume Profile (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `window == 0`
+/// * `LengthMismatch` if `close` and `volume` differ in length
+/// * `InvalidParameter` if `bins == 0`
+pub fn rolling_volume_profile_checked(close: &[f64], volume: &[f64], bins: usize, window: usize) -> Result<VolumeProfileSeries, QuantError> {
+    if window == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    check_len(close.len(), volume.len())?;
+    check_profile_params(bins, DEFAULT_VALUE_AREA)?;
+    let n = close.len();
+    let mut series = VolumeProfileSeries {
+        poc: vec![f64::NAN; n],
+        value_area_high: vec![f64::NAN; n],
+        value_area_low: vec![f64::NAN; n],
+    };
+    for i in window.saturating_sub(1) .. n {
+        let start = i + 1 - window;
+        let vp = volume_profile_checked(&close[start ..= i], &volume[start ..= i], bins, None, DEFAULT_VALUE_AREA)?;
+        series.poc[i] = vp.poc;
+        series.value_area_high[i] = vp.value_area_high;
+        series.value_area_low[i] = vp.value_area_low;
+    }
+    Ok(series)
+}
+
+fn check_profile_params(bins: usize, value_area: f64) -> Result<(), QuantError> {
+    if bins == 0 {
+        return Err(QuantError::InvalidParameter("bins must be positive".to_string()));
+    }
+    if value_area.is_nan() || value_area <= 0f64 || value_area > 1f64 {
+        return Err(QuantError::InvalidParameter(format!("value area {} is not in (0, 1]", value_area)));
+    }
+    Ok(())
+}
+
+/// Histogram over `[lo, hi]` (NAN bounds: empty range) and its POC / value area
+fn build_profile(close: &[f64], volume: &[f64], bins: usize, lo: f64, hi: f64, value_area: f64) -> VolumeProfile {
+    let width = (hi - lo) / bins as f64;
+    let edges = (0 ..= bins).map(|k| lo + width * k as f64).collect::<Vec<f64>>();
+    let mut hist = vec![0f64; bins];
+    for (&c, &v) in close.iter().zip(volume.iter()) {
+        if c.is_nan() || v.is_nan() || !(c >= lo && c <= hi) {
+            continue;
+        }
+        let k = if width > 0f64 { (((c - lo) / width) as usize).min(bins - 1) } else { 0 };
+        hist[k] += v;
+    }
+
+    let total = hist.iter().sum::<f64>();
+    if total <= 0f64 {
+        return VolumeProfile { edges, volume: hist, poc: f64::NAN, value_area_high: f64::NAN, value_area_low: f64::NAN };
+    }
+    let poc_bin = (0 .. bins).fold(0, |best, k| if hist[k] > hist[best] { k } else { best });
+    let (mut below, mut above) = (poc_bin, poc_bin);
+    let mut covered = hist[poc_bin];
+    while covered < value_area * total && (below > 0 || above + 1 < bins) {
+        let down = if below > 0 { hist[below - 1] } else { f64::NEG_INFINITY };
+        let up = if above + 1 < bins { hist[above + 1] } else { f64::NEG_INFINITY };
+        if up >= down {
+            above += 1;
+            covered += up;
+        } else {
+            below -= 1;
+            covered += down;
+        }
+    }
+    VolumeProfile {
+        poc: (edges[poc_bin] + edges[poc_bin + 1]) / 2f64,
+        value_area_high: edges[above + 1],
+        value_area_low: edges[below],
+        edges,
+        volume: hist,
+    }
+}
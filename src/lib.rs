@@ -1 +1,17 @@
+#![allow(clippy::needless_range_loop)]
 pub mod base;
+pub mod calendar;
+pub mod error;
+pub mod frame;
+pub mod garch11;
+pub mod kalman;
+pub mod levels;
+pub mod price;
+pub mod registry;
+pub mod rolling;
+pub mod smoothing;
+pub mod stats;
+pub mod strategy;
+pub mod volatility;
+
+pub use base::{MaKind, NanPolicy, Warmup};
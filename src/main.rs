@@ -1,41 +1,237 @@
 use peroxide::fuga::*;
-use quantauri::base::{sma, ema, mstd, bollinger_band, macd, BollingerBand};
+use quantauri::base::{sma_checked, rolling_zscore_checked, balance_of_power_checked, volume_oscillator_checked, BollingerBand, MaKind};
+use quantauri::error::QuantError;
+use quantauri::price::typical_price_checked;
+use quantauri::frame::indicator_frame;
+#[cfg(feature = "json")]
+use quantauri::frame::write_json;
+use std::error::Error;
+use std::process;
+
+const USAGE: &str = "\
+Usage: quantauri [OPTIONS]
+
+Options:
+  --input <PATH>          Input file (default: data/close.parquet)
+  --output <PATH>         Output file (default: data/bollinger.parquet)
+  --format <FORMAT>       parquet | csv | json, for both input and output (default: parquet)
+                          json is output-only (requires the `json` feature)
+  --input-format <FMT>    Override the input format
+  --output-format <FMT>   Override the output format
+  --indicators <LIST>     Comma-separated subset of: sma,bb,perb,bw,zscore,bop,vol_osc (default: all)
+                          bop needs an open column and vol_osc a volume column: listed explicitly,
+                          a missing column is an error; in the default set they are skipped with a warning
+  -h, --help              Print this help
+
+Example with open and volume columns:
+  quantauri --input data/ohlcv.csv --format csv --output data/ohlcv_indicators.csv";
+
+const INDICATORS: [&str; 7] = ["sma", "bb", "perb", "bw", "zscore", "bop", "vol_osc"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Parquet,
+    Csv,
+    #[cfg(feature = "json")]
+    Json,
+}
+
+impl Format {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "parquet" => Ok(Format::Parquet),
+            "csv" => Ok(Format::Csv),
+            #[cfg(feature = "json")]
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format: {} (expected parquet, csv or json)", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Args {
+    input: String,
+    output: String,
+    input_format: Format,
+    output_format: Format,
+    indicators: Vec<String>,
+    /// `--indicators` was given (otherwise `indicators` is the default set)
+    explicit: bool,
+}
+
+impl Args {
+    /// Parse `--key value` pairs; returns `Ok(None)` for `--help`
+    fn parse(mut raw: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut input = "data/close.parquet".to_string();
+        let mut output = "data/bollinger.parquet".to_string();
+        let mut format = None;
+        let mut input_format = None;
+        let mut output_format = None;
+        let mut indicators = INDICATORS.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let mut explicit = false;
+        while let Some(flag) = raw.next() {
+            if flag == "-h" || flag == "--help" {
+                return Ok(None);
+            }
+            let value = raw.next().ok_or_else(|| format!("missing value for {}", flag))?;
+            match flag.as_str() {
+                "--input" => input = value,
+                "--output" => output = value,
+                "--format" => format = Some(Format::parse(&value)?),
+                "--input-format" => input_format = Some(Format::parse(&value)?),
+                "--output-format" => output_format = Some(Format::parse(&value)?),
+                "--indicators" => {
+                    explicit = true;
+                    indicators = value.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+                    if let Some(bad) = indicators.iter().find(|s| !INDICATORS.contains(&s.as_str())) {
+                        return Err(format!("unknown indicator: {}", bad));
+                    }
+                }
+                _ => return Err(format!("unknown option: {}", flag)),
+            }
+        }
+        // JSON is output-only, so `--format json` leaves the input format at its default
+        #[cfg(feature = "json")]
+        let input_default = format.filter(|&f| f != Format::Json);
+        #[cfg(not(feature = "json"))]
+        let input_default = format;
+        let input_format = input_format.or(input_default).unwrap_or(Format::Parquet);
+        #[cfg(feature = "json")]
+        if input_format == Format::Json {
+            return Err("json is only supported as an output format".to_string());
+        }
+        Ok(Some(Args {
+            input,
+            output,
+            input_format,
+            output_format: output_format.or(format).unwrap_or(Format::Parquet),
+            indicators,
+            explicit,
+        }))
+    }
+
+    fn wants(&self, indicator: &str) -> bool {
+        self.indicators.iter().any(|s| s == indicator)
+    }
+
+    /// Whether to compute an indicator that reads an optional input column
+    ///
+    /// A missing column is an error if the indicator was requested explicitly,
+    /// and a warning if it only comes from the default set.
+    fn wants_with(&self, indicator: &str, column: &str, available: bool) -> Result<bool, String> {
+        if !self.wants(indicator) || available {
+            return Ok(self.wants(indicator));
+        }
+        if self.explicit {
+            Err(format!("{} needs the {} column, which {} does not have", indicator, column, self.input))
+        } else {
+            eprintln!("warning: skipping {}: {} has no {} column", indicator, self.input, column);
+            Ok(false)
+        }
+    }
+}
+
+fn has_column(df: &DataFrame, col: &str) -> bool {
+    df.header().iter().any(|h| h == col)
+}
+
+/// Prefix an indicator error with the indicator name
+fn context<T>(name: &str, result: Result<T, QuantError>) -> Result<T, String> {
+    result.map_err(|e| format!("{}: {}", name, e))
+}
 
 fn main() {
-    let mut df = DataFrame::read_parquet("data/close.parquet").expect("Can't read parquet");
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    if let Err(e) = run(&args) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let mut df = match args.input_format {
+        Format::Parquet => DataFrame::read_parquet(&args.input),
+        Format::Csv => DataFrame::read_csv(&args.input, ','),
+        #[cfg(feature = "json")]
+        Format::Json => unreachable!("json input is rejected while parsing arguments"),
+    }.map_err(|e| format!("can't read {}: {}", args.input, e))?;
     df.print();
-    df.as_types(vec![Str, F64, F64, F64]);
+    let has_open = has_column(&df, "open");
+    let has_volume = has_column(&df, "volume");
+    for col in ["close", "high", "low"] {
+        if !has_column(&df, col) {
+            return Err(format!("{} has no {} column", args.input, col).into());
+        }
+    }
+    for col in ["close", "high", "low"] {
+        df[col].as_type(F64);
+    }
 
     let close: Vec<f64> = df["close"].to_vec();
     let high: Vec<f64> = df["high"].to_vec();
     let low: Vec<f64> = df["low"].to_vec();
-
-    let tp = {
-        let mut tp = vec![0f64; close.len()];
-        for i in 0 .. close.len() {
-            tp[i] = (high[i] + low[i] + close[i]) / 3f64;
-        }
-        tp
+    let open: Option<Vec<f64>> = if has_open {
+        df["open"].as_type(F64);
+        Some(df["open"].to_vec())
+    } else {
+        None
+    };
+    let volume: Option<Vec<f64>> = if has_volume {
+        df["volume"].as_type(F64);
+        Some(df["volume"].to_vec())
+    } else {
+        None
     };
 
-    let sma_5 = sma(&tp, 5);
-    let mut bollinger = BollingerBand::new(20, 2f64);
-    bollinger.bb_mut(&tp);
-    let ubb     = bollinger.get_ubb();
-    let mbb     = bollinger.get_mbb();
-    let lbb     = bollinger.get_lbb();
-    let perb    = bollinger.per_b(&tp);
-    let bw      = bollinger.bw(&tp);
-
-    let mut df = DataFrame::new(vec![]);
-    df.push("tp", Series::new(tp));
-    df.push("sma_5", Series::new(sma_5));
-    df.push("ubb", Series::new(ubb.clone()));
-    df.push("mbb", Series::new(mbb.clone()));
-    df.push("lbb", Series::new(lbb.clone()));
-    df.push("perb", Series::new(perb));
-    df.push("bw", Series::new(bw));
+    let tp = context("tp", typical_price_checked(&high, &low, &close))?;
+
+    let mut columns = vec![("tp", tp.clone())];
+    if args.wants("sma") {
+        columns.push(("sma_5", context("sma", sma_checked(&tp, 5))?));
+    }
+    if args.wants("bb") || args.wants("perb") || args.wants("bw") {
+        let mut bollinger = BollingerBand::new(20, 2f64);
+        context("bb", bollinger.bb_mut_checked(&tp))?;
+        if args.wants("bb") {
+            columns.push(("ubb", bollinger.get_ubb().clone()));
+            columns.push(("mbb", bollinger.get_mbb().clone()));
+            columns.push(("lbb", bollinger.get_lbb().clone()));
+        }
+        if args.wants("perb") {
+            columns.push(("perb", bollinger.per_b(&tp)));
+        }
+        if args.wants("bw") {
+            columns.push(("bw", bollinger.bw(&tp)));
+        }
+    }
+    if args.wants("zscore") {
+        columns.push(("zscore", context("zscore", rolling_zscore_checked(&tp, 20))?));
+    }
+    if let (true, Some(open)) = (args.wants_with("bop", "open", has_open)?, open) {
+        columns.push(("bop", context("bop", balance_of_power_checked(&open, &high, &low, &close, 14))?));
+    }
+    if let (true, Some(volume)) = (args.wants_with("vol_osc", "volume", has_volume)?, volume) {
+        columns.push(("vol_osc", context("vol_osc", volume_oscillator_checked(&volume, 5, 10, MaKind::Sma, true))?));
+    }
+    let keep = if has_column(&df, "date") { vec!["date"] } else { vec![] };
+    let df = indicator_frame(&df, &keep, columns)?;
     df.print();
 
-    df.write_parquet("data/bollinger.parquet", CompressionOptions::Uncompressed).expect("Can't write parquet");
+    match args.output_format {
+        Format::Parquet => df.write_parquet(&args.output, CompressionOptions::Uncompressed),
+        Format::Csv => df.write_csv(&args.output),
+        #[cfg(feature = "json")]
+        Format::Json => write_json(&df, &args.output).map_err(|e| e.into()),
+    }.map_err(|e| format!("can't write {}: {}", args.output, e))?;
+    Ok(())
 }
@@ -0,0 +1,444 @@
+use crate::base::Warmup;
+use crate::error::{QuantError, check_len};
+
+// =============================================================================
+// Price Transforms
+// =============================================================================
+/// Typical Price
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * (high + low + close) / 3
+///
+/// # Examples
+/// ```
+/// use quantauri::price::typical_price;
+///
+/// fn main() {
+///     let tp = typical_price(&[12f64, 9f64], &[6f64, 3f64], &[9f64, 3f64]);
+///     assert_eq!(tp, vec![9f64, 5f64]);
+/// }
+/// ```
+pub fn typical_price(high: &[f64], low: &[f64], close: &[f64]) -> Vec<f64> {
+    typical_price_checked(high, low, close).expect("typical_price: invalid input")
+}
+
+/// Typical Price (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if the inputs differ in length
+pub fn typical_price_checked(high: &[f64], low: &[f64], close: &[f64]) -> Result<Vec<f64>, QuantError> {
+    check_len(high.len(), low.len())?;
+    check_len(high.len(), close.len())?;
+    Ok((0 .. high.len()).map(|i| (high[i] + low[i] + close[i]) / 3f64).collect())
+}
+
+/// Median Price
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * (high + low) / 2
+pub fn median_price(high: &[f64], low: &[f64]) -> Vec<f64> {
+    median_price_checked(high, low).expect("median_price: invalid input")
+}
+
+/// Median Price (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if the inputs differ in length
+pub fn median_price_checked(high: &[f64], low: &[f64]) -> Result<Vec<f64>, QuantError> {
+    check_len(high.len(), low.len())?;
+    Ok((0 .. high.len()).map(|i| (high[i] + low[i]) / 2f64).collect())
+}
+
+/// Weighted Close
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * (high + low + 2 * close) / 4
+///
+/// # Examples
+/// ```
+/// use quantauri::price::weighted_close;
+///
+/// fn main() {
+///     assert_eq!(weighted_close(&[12f64], &[4f64], &[10f64]), vec![9f64]);
+///     assert!(quantauri::price::weighted_close_checked(&[12f64], &[4f64], &[]).is_err());
+/// }
+/// ```
+pub fn weighted_close(high: &[f64], low: &[f64], close: &[f64]) -> Vec<f64> {
+    weighted_close_checked(high, low, close).expect("weighted_close: invalid input")
+}
+
+/// Weighted Close (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if the inputs differ in length
+pub fn weighted_close_checked(high: &[f64], low: &[f64], close: &[f64]) -> Result<Vec<f64>, QuantError> {
+    check_len(high.len(), low.len())?;
+    check_len(high.len(), close.len())?;
+    Ok((0 .. high.len()).map(|i| (high[i] + low[i] + 2f64 * close[i]) / 4f64).collect())
+}
+
+/// OHLC Average
+///
+/// # Arguments
+/// * `open` - &[f64]
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * (open + high + low + close) / 4
+pub fn ohlc4(open: &[f64], high: &[f64], low: &[f64], close: &[f64]) -> Vec<f64> {
+    ohlc4_checked(open, high, low, close).expect("ohlc4: invalid input")
+}
+
+/// OHLC Average (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if the inputs differ in length
+pub fn ohlc4_checked(open: &[f64], high: &[f64], low: &[f64], close: &[f64]) -> Result<Vec<f64>, QuantError> {
+    check_len(open.len(), high.len())?;
+    check_len(open.len(), low.len())?;
+    check_len(open.len(), close.len())?;
+    Ok((0 .. open.len()).map(|i| (open[i] + high[i] + low[i] + close[i]) / 4f64).collect())
+}
+
+// =============================================================================
+// Returns
+// =============================================================================
+/// Simple Returns
+///
+/// # Arguments
+/// * `v` - &[f64] (prices)
+///
+/// # Returns
+/// * Vec<f64>
+///   * v[i] / v[i-1] - 1, returns[0] is NAN (see `returns_with` for other conventions)
+///   * NAN where the previous price is 0
+///
+/// # Examples
+/// ```
+/// use quantauri::price::returns;
+///
+/// fn main() {
+///     let r = returns(&[100f64, 110f64, 99f64]);
+///     assert!(r[0].is_nan());
+///     assert!((r[1] - 0.1).abs() < 1e-12 && (r[2] + 0.1).abs() < 1e-12);
+/// }
+/// ```
+pub fn returns(v: &[f64]) -> Vec<f64> {
+    returns_with(v, 1, Warmup::Nan)
+}
+
+/// n-bar Simple Returns
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `n` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * v[i] / v[i-n] - 1, first `n` values are NAN
+pub fn returns_n(v: &[f64], n: usize) -> Vec<f64> {
+    returns_with(v, n, Warmup::Nan)
+}
+
+/// n-bar Simple Returns with a warm-up convention
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `n` - usize
+/// * `warmup` - Warmup (applies to the first `n` values)
+///
+/// # Returns
+/// * Vec<f64>
+///   * v[i] / v[i-n] - 1
+///   * `Nan`: first `n` values are NAN, `Zero`: they are 0,
+///     `Partial`: v[i] / v[0] - 1 (return since the first bar, so index 0 is 0)
+///   * NAN where the base price is 0
+///
+/// # Examples
+/// ```
+/// use quantauri::Warmup;
+/// use quantauri::price::returns_with;
+///
+/// fn main() {
+///     let v = vec![100f64, 110f64, 121f64, 133.1];
+///     assert_eq!(returns_with(&v, 1, Warmup::Zero)[0], 0f64);
+///     let r2 = returns_with(&v, 2, Warmup::Partial);
+///     assert_eq!(r2[0], 0f64);
+///     assert!((r2[1] - 0.1).abs() < 1e-12);
+///     assert!((r2[3] - 0.21).abs() < 1e-12);
+/// }
+/// ```
+pub fn returns_with(v: &[f64], n: usize, warmup: Warmup) -> Vec<f64> {
+    let ratio = |cur: f64, base: f64| if base == 0f64 { f64::NAN } else { cur / base - 1f64 };
+    (0 .. v.len()).map(|i| {
+        if i >= n {
+            ratio(v[i], v[i-n])
+        } else {
+            match warmup {
+                Warmup::Nan => f64::NAN,
+                Warmup::Zero => 0f64,
+                Warmup::Partial => ratio(v[i], v[0]),
+            }
+        }
+    }).collect()
+}
+
+/// Log Returns
+///
+/// # Arguments
+/// * `v` - &[f64] (prices)
+///
+/// # Returns
+/// * Vec<f64>
+///   * ln(v[i] / v[i-1]), log_returns[0] is NAN (see `log_returns_with`)
+///   * NAN (no panic) where either price is zero or negative
+///
+/// # Examples
+/// ```
+/// use quantauri::price::log_returns;
+///
+/// fn main() {
+///     let r = log_returns(&[100f64, 110f64, 0f64, 5f64]);
+///     assert!(r[0].is_nan());
+///     assert!((r[1] - 1.1f64.ln()).abs() < 1e-12);
+///     assert!(r[2].is_nan() && r[3].is_nan());
+/// }
+/// ```
+pub fn log_returns(v: &[f64]) -> Vec<f64> {
+    log_returns_with(v, 1, Warmup::Nan)
+}
+
+/// n-bar Log Returns with a warm-up convention
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `n` - usize
+/// * `warmup` - Warmup (same conventions as `returns_with`)
+///
+/// # Returns
+/// * Vec<f64>
+///   * ln(v[i] / v[i-n]), NAN where either price is not positive
+pub fn log_returns_with(v: &[f64], n: usize, warmup: Warmup) -> Vec<f64> {
+    let log_ratio = |cur: f64, base: f64| if cur > 0f64 && base > 0f64 { (cur / base).ln() } else { f64::NAN };
+    (0 .. v.len()).map(|i| {
+        if i >= n {
+            log_ratio(v[i], v[i-n])
+        } else {
+            match warmup {
+                Warmup::Nan => f64::NAN,
+                Warmup::Zero => 0f64,
+                Warmup::Partial => log_ratio(v[i], v[0]),
+            }
+        }
+    }).collect()
+}
+
+/// Cumulative Returns (equity curve)
+///
+/// # Arguments
+/// * `returns` - &[f64] (simple returns, e.g. from `returns`)
+/// * `initial` - f64 (starting equity)
+///
+/// # Returns
+/// * Vec<f64>
+///   * equity[i] = initial * prod_{j <= i} (1 + returns[j])
+///   * NAN returns (e.g. the warm-up of `returns`) count as 0, so the curve starts flat
+///
+/// # Examples
+/// ```
+/// use quantauri::price::{returns, cum_returns};
+///
+/// fn main() {
+///     let prices = vec![50f64, 55f64, 49.5, 60f64, 58f64];
+///     let equity = cum_returns(&returns(&prices), 1f64);
+///     // Round trip: the curve reproduces prices up to scaling
+///     for i in 0 .. prices.len() {
+///         assert!((equity[i] * prices[0] - prices[i]).abs() < 1e-9);
+///     }
+/// }
+/// ```
+pub fn cum_returns(returns: &[f64], initial: f64) -> Vec<f64> {
+    let mut equity = initial;
+    returns.iter().map(|&r| {
+        if !r.is_nan() {
+            equity *= 1f64 + r;
+        }
+        equity
+    }).collect()
+}
+
+/// Cumulative Log Returns (equity curve)
+///
+/// # Arguments
+/// * `log_returns` - &[f64] (e.g. from `log_returns`)
+/// * `initial` - f64
+///
+/// # Returns
+/// * Vec<f64>
+///   * equity[i] = initial * exp(sum_{j <= i} log_returns[j])
+///   * NAN log returns count as 0
+///
+/// # Examples
+/// ```
+/// use quantauri::price::{log_returns, cum_log_returns};
+///
+/// fn main() {
+///     let prices = vec![50f64, 55f64, 49.5, 60f64, 58f64];
+///     let equity = cum_log_returns(&log_returns(&prices), 50f64);
+///     for i in 0 .. prices.len() {
+///         assert!((equity[i] - prices[i]).abs() < 1e-9);
+///     }
+/// }
+/// ```
+pub fn cum_log_returns(log_returns: &[f64], initial: f64) -> Vec<f64> {
+    let mut total = 0f64;
+    log_returns.iter().map(|&r| {
+        if !r.is_nan() {
+            total += r;
+        }
+        initial * total.exp()
+    }).collect()
+}
+
+// =============================================================================
+// OHLCV Bars
+// =============================================================================
+/// OHLCV bar series (one vector per field, equal lengths)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ohlcv {
+    pub open: Vec<f64>,
+    pub high: Vec<f64>,
+    pub low: Vec<f64>,
+    pub close: Vec<f64>,
+    pub volume: Vec<f64>,
+}
+
+impl Ohlcv {
+    pub fn new(open: Vec<f64>, high: Vec<f64>, low: Vec<f64>, close: Vec<f64>, volume: Vec<f64>) -> Self {
+        Self::new_checked(open, high, low, close, volume).expect("Ohlcv::new: invalid input")
+    }
+
+    /// # Errors
+    /// * `LengthMismatch` if the fields differ in length
+    pub fn new_checked(open: Vec<f64>, high: Vec<f64>, low: Vec<f64>, close: Vec<f64>, volume: Vec<f64>) -> Result<Self, QuantError> {
+        for field in [&high, &low, &close, &volume] {
+            check_len(open.len(), field.len())?;
+        }
+        Ok(Ohlcv { open, high, low, close, volume })
+    }
+
+    pub fn len(&self) -> usize {
+        self.close.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.close.is_empty()
+    }
+}
+
+/// Handling of a trailing bucket with fewer than `factor` bars
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartialBucket {
+    /// Leave it out (every output bar covers exactly `factor` input bars)
+    Drop,
+    /// Aggregate the remaining bars into a last, shorter bar
+    Aggregate,
+}
+
+/// Resample OHLCV bars to a coarser timeframe
+///
+/// # Arguments
+/// * `ohlcv` - &Ohlcv
+/// * `factor` - usize (e.g. 5 for 1-minute -> 5-minute bars)
+/// * `partial` - PartialBucket
+///
+/// # Returns
+/// * Ohlcv
+///   * Every `factor` consecutive bars, starting at bar 0, become one bar:
+///     open = first open, high = max high, low = min low, close = last close, volume = sum
+///   * high / low skip NAN (NAN only if the whole bucket is NAN), volume propagates NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::price::{Ohlcv, PartialBucket, resample_ohlcv};
+///
+/// fn main() {
+///     let bars = Ohlcv::new(
+///         vec![10f64, 11f64, 12f64, 11f64, 13f64],
+///         vec![11f64, 13f64, 12.5, 12f64, 14f64],
+///         vec![9f64, 10.5, 11f64, 10f64, 12.5],
+///         vec![11f64, 12f64, 11f64, 12f64, 13.5],
+///         vec![100f64, 200f64, 150f64, 50f64, 80f64],
+///     );
+///     let r = resample_ohlcv(&bars, 2, PartialBucket::Drop);
+///     assert_eq!(r.len(), 2);
+///     assert_eq!(r.open, vec![10f64, 12f64]);
+///     assert_eq!(r.high, vec![13f64, 12.5]);
+///     assert_eq!(r.low, vec![9f64, 10f64]);
+///     assert_eq!(r.close, vec![12f64, 12f64]);
+///     assert_eq!(r.volume, vec![300f64, 200f64]);
+///
+///     let r = resample_ohlcv(&bars, 2, PartialBucket::Aggregate);
+///     assert_eq!(r.len(), 3);
+///     assert_eq!((r.open[2], r.close[2], r.volume[2]), (13f64, 13.5, 80f64));
+/// }
+/// ```
+pub fn resample_ohlcv(ohlcv: &Ohlcv, factor: usize, partial: PartialBucket) -> Ohlcv {
+    resample_ohlcv_checked(ohlcv, factor, partial).expect("resample_ohlcv: invalid input")
+}
+
+/// Resample OHLCV bars (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `factor == 0`
+/// * `LengthMismatch` if the fields of `ohlcv` differ in length
+pub fn resample_ohlcv_checked(ohlcv: &Ohlcv, factor: usize, partial: PartialBucket) -> Result<Ohlcv, QuantError> {
+    if factor == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    let n = ohlcv.len();
+    for field in [&ohlcv.open, &ohlcv.high, &ohlcv.low, &ohlcv.volume] {
+        check_len(n, field.len())?;
+    }
+    let buckets = match partial {
+        PartialBucket::Drop => n / factor,
+        PartialBucket::Aggregate => n.div_ceil(factor),
+    };
+    let mut out = Ohlcv {
+        open: Vec::with_capacity(buckets),
+        high: Vec::with_capacity(buckets),
+        low: Vec::with_capacity(buckets),
+        close: Vec::with_capacity(buckets),
+        volume: Vec::with_capacity(buckets),
+    };
+    for b in 0 .. buckets {
+        let start = b * factor;
+        let end = (start + factor).min(n);
+        out.open.push(ohlcv.open[start]);
+        out.high.push(ohlcv.high[start .. end].iter().fold(f64::NAN, |a, &x| a.max(x)));
+        out.low.push(ohlcv.low[start .. end].iter().fold(f64::NAN, |a, &x| a.min(x)));
+        out.close.push(ohlcv.close[end - 1]);
+        out.volume.push(ohlcv.volume[start .. end].iter().sum());
+    }
+    Ok(out)
+}
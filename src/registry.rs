@@ -0,0 +1,399 @@
+use crate::base::{
+    sma_checked, ema_checked, wilder_ema_checked, moving_average_checked, mstd_checked, bollinger_band,
+    macd_with_checked, klinger_checked, rsi, cmo, roc, atr, qstick_checked, rvi_checked, rolling_zscore_checked, MaKind,
+    pvt_checked, pvi, nvi, force_index_checked, mfi_bw_checked,
+};
+use crate::error::{QuantError, check_len};
+use crate::price::Ohlcv;
+use std::sync::OnceLock;
+
+// =============================================================================
+// Indicator Registry
+// =============================================================================
+/// Field of an `Ohlcv` series
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Open,
+    High,
+    Low,
+    Close,
+    Volume,
+}
+
+impl Field {
+    /// Column name (as in the input files)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::Open => "open",
+            Field::High => "high",
+            Field::Low => "low",
+            Field::Close => "close",
+            Field::Volume => "volume",
+        }
+    }
+
+    fn get<'a>(&self, ohlcv: &'a Ohlcv) -> &'a [f64] {
+        match self {
+            Field::Open => &ohlcv.open,
+            Field::High => &ohlcv.high,
+            Field::Low => &ohlcv.low,
+            Field::Close => &ohlcv.close,
+            Field::Volume => &ohlcv.volume,
+        }
+    }
+}
+
+/// Parameter of a registered indicator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub default: f64,
+}
+
+/// Indicator computed from bars and a list of numeric parameters (`Send + Sync`, so a
+/// registry can be shared between threads)
+pub type IndicatorFn = Box<dyn Fn(&Ohlcv, &[f64]) -> Result<Vec<f64>, QuantError> + Send + Sync>;
+
+/// Registered indicator
+pub struct IndicatorEntry {
+    pub name: &'static str,
+    /// Fields the indicator reads (the only ones `Registry::compute` checks)
+    pub inputs: &'static [Field],
+    pub params: Vec<ParamSpec>,
+    pub compute: IndicatorFn,
+}
+
+/// Name -> indicator map for pipelines configured at runtime
+///
+/// # Description
+/// `Registry::default()` holds the built-in indicators (computed on `close` unless
+/// they need other fields). Each entry lists the fields it reads, so bars loaded without
+/// e.g. a volume column still work for price-only indicators. Parameters are positional;
+/// missing trailing ones take their defaults, and periods must be positive integers.
+///
+/// | name | params (defaults) | output |
+/// |------|-------------------|--------|
+/// | `sma`, `ema`, `wma`, `hma`, `tma`, `rma` | period (20) | moving average |
+/// | `rsi` | period (14) | `rsi` |
+/// | `cmo` | period (14) | `cmo` |
+/// | `roc` | period (12) | `roc` |
+/// | `macd`, `macd_signal` | fast (12), slow (26), signal (9) | MACD line / signal |
+/// | `atr` | period (14) | `atr` |
+/// | `bb_upper`, `bb_middle`, `bb_lower` | period (20), amplitude (2) | Bollinger bands |
+/// | `zscore` | period (20) | `rolling_zscore` |
+/// | `qstick` | period (8) | `qstick` (SMA) |
+/// | `klinger`, `klinger_signal` | fast (34), slow (55), signal (13) | Klinger oscillator / signal |
+/// | `rvi`, `rvi_signal` | period (10) | Relative Vigor Index / signal |
+/// | `pvt`, `pvi`, `nvi` | - | price-volume trend / positive / negative volume index |
+/// | `force_index` | period (13) | `force_index` |
+/// | `mfi_bw` | - | Market Facilitation Index value |
+///
+/// # Examples
+/// ```
+/// use quantauri::registry::{Registry, Field};
+/// use quantauri::price::Ohlcv;
+/// use quantauri::base::sma;
+///
+/// fn main() {
+///     let close = (0 .. 30).map(|x| 100f64 + x as f64).collect::<Vec<f64>>();
+///     let bars = Ohlcv::new(close.clone(), close.clone(), close.clone(), close.clone(), vec![1f64; 30]);
+///
+///     let mut registry = Registry::default();
+///     assert_eq!(registry.compute("sma", &bars, &[5f64]).unwrap(), sma(&close, 5));
+///
+///     // Custom entry
+///     registry.register("range", &[Field::High, Field::Low], vec![], Box::new(|bars, _| {
+///         Ok((0 .. bars.len()).map(|i| bars.high[i] - bars.low[i]).collect())
+///     }));
+///     assert_eq!(registry.compute("range", &bars, &[]).unwrap(), vec![0f64; 30]);
+///     assert!(registry.names().contains(&"range"));
+///
+///     // Close-only bars: fine for `sma`, a clear error for `atr`
+///     let bars = Ohlcv { open: vec![], high: vec![], low: vec![], close: close.clone(), volume: vec![] };
+///     assert_eq!(registry.compute("sma", &bars, &[5f64]).unwrap(), sma(&close, 5));
+///     let err = registry.compute("atr", &bars, &[]).unwrap_err().to_string();
+///     assert!(err.contains("atr requires the high column"));
+/// }
+/// ```
+pub struct Registry {
+    entries: Vec<IndicatorEntry>,
+}
+
+impl Registry {
+    /// Empty registry
+    pub fn new() -> Self {
+        Registry { entries: vec![] }
+    }
+
+    /// Register (or replace) an indicator
+    pub fn register(&mut self, name: &'static str, inputs: &'static [Field], params: Vec<ParamSpec>, compute: IndicatorFn) {
+        self.entries.retain(|e| e.name != name);
+        self.entries.push(IndicatorEntry { name, inputs, params, compute });
+    }
+
+    pub fn get(&self, name: &str) -> Option<&IndicatorEntry> {
+        self.entries.iter().find(|e| e.name == name)
+    }
+
+    /// Registered names in registration order
+    pub fn names(&self) -> Vec<&'static str> {
+        self.entries.iter().map(|e| e.name).collect()
+    }
+
+    /// Compute an indicator by name
+    ///
+    /// # Errors
+    /// * `InvalidParameter` for an unknown name (the message lists the available ones),
+    ///   more parameters than the indicator takes, or a period that is not a positive integer
+    /// * `InvalidParameter` naming the first field in `inputs` that is empty
+    /// * `LengthMismatch` if the fields in `inputs` differ in length
+    /// * Errors of the indicator itself
+    pub fn compute(&self, name: &str, ohlcv: &Ohlcv, params: &[f64]) -> Result<Vec<f64>, QuantError> {
+        let entry = self.get(name).ok_or_else(|| QuantError::InvalidParameter(format!(
+            "unknown indicator {} (available: {})", name, self.names().join(", ")
+        )))?;
+        if params.len() > entry.params.len() {
+            return Err(QuantError::InvalidParameter(format!(
+                "{} takes {} parameters, got {}", name, entry.params.len(), params.len()
+            )));
+        }
+        for field in entry.inputs {
+            if field.get(ohlcv).is_empty() {
+                return Err(QuantError::InvalidParameter(format!(
+                    "{} requires the {} column, which is empty", name, field.name()
+                )));
+            }
+        }
+        if let Some((first, rest)) = entry.inputs.split_first() {
+            let n = first.get(ohlcv).len();
+            for field in rest {
+                check_len(n, field.get(ohlcv).len())?;
+            }
+        }
+        let full = entry.params.iter().enumerate()
+            .map(|(i, spec)| params.get(i).copied().unwrap_or(spec.default))
+            .collect::<Vec<f64>>();
+        (entry.compute)(ohlcv, &full)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        use Field::*;
+        let mut r = Registry::new();
+        let period = |default: f64| vec![ParamSpec { name: "period", default }];
+        let macd_params = || vec![
+            ParamSpec { name: "fast", default: 12f64 },
+            ParamSpec { name: "slow", default: 26f64 },
+            ParamSpec { name: "signal", default: 9f64 },
+        ];
+        let klinger_params = || vec![
+            ParamSpec { name: "fast", default: 34f64 },
+            ParamSpec { name: "slow", default: 55f64 },
+            ParamSpec { name: "signal", default: 13f64 },
+        ];
+        let bb_params = || vec![
+            ParamSpec { name: "period", default: 20f64 },
+            ParamSpec { name: "amplitude", default: 2f64 },
+        ];
+
+        r.register("sma", &[Close], period(20f64), Box::new(|b, p| sma_checked(&b.close, as_period(p[0])?)));
+        r.register("ema", &[Close], period(20f64), Box::new(|b, p| ema_checked(&b.close, as_period(p[0])?)));
+        r.register("wma", &[Close], period(20f64), Box::new(|b, p| moving_average_checked(&b.close, as_period(p[0])?, MaKind::Wma)));
+        r.register("hma", &[Close], period(20f64), Box::new(|b, p| moving_average_checked(&b.close, as_period(p[0])?, MaKind::Hma)));
+        r.register("tma", &[Close], period(20f64), Box::new(|b, p| moving_average_checked(&b.close, as_period(p[0])?, MaKind::Tma)));
+        r.register("rma", &[Close], period(20f64), Box::new(|b, p| wilder_ema_checked(&b.close, as_period(p[0])?)));
+        r.register("rsi", &[Close], period(14f64), Box::new(|b, p| Ok(rsi(&b.close, as_period(p[0])?))));
+        r.register("cmo", &[Close], period(14f64), Box::new(|b, p| Ok(cmo(&b.close, as_period(p[0])?))));
+        r.register("roc", &[Close], period(12f64), Box::new(|b, p| Ok(roc(&b.close, as_period(p[0])?))));
+        r.register("macd", &[Close], macd_params(), Box::new(|b, p| {
+            Ok(macd_with_checked(&b.close, as_period(p[0])?, as_period(p[1])?, as_period(p[2])?)?.0)
+        }));
+        r.register("macd_signal", &[Close], macd_params(), Box::new(|b, p| {
+            Ok(macd_with_checked(&b.close, as_period(p[0])?, as_period(p[1])?, as_period(p[2])?)?.1)
+        }));
+        r.register("atr", &[High, Low, Close], period(14f64), Box::new(|b, p| Ok(atr(&b.high, &b.low, &b.close, as_period(p[0])?))));
+        r.register("bb_upper", &[Close], bb_params(), Box::new(|b, p| Ok(bands(&b.close, p)?[0].clone())));
+        r.register("bb_middle", &[Close], bb_params(), Box::new(|b, p| Ok(bands(&b.close, p)?[1].clone())));
+        r.register("bb_lower", &[Close], bb_params(), Box::new(|b, p| Ok(bands(&b.close, p)?[2].clone())));
+        r.register("zscore", &[Close], period(20f64), Box::new(|b, p| rolling_zscore_checked(&b.close, as_period(p[0])?)));
+        r.register("qstick", &[Open, Close], period(8f64), Box::new(|b, p| qstick_checked(&b.open, &b.close, as_period(p[0])?, MaKind::Sma)));
+        r.register("klinger", &[High, Low, Close, Volume], klinger_params(), Box::new(|b, p| {
+            Ok(klinger_checked(&b.high, &b.low, &b.close, &b.volume, as_period(p[0])?, as_period(p[1])?, as_period(p[2])?)?.0)
+        }));
+        r.register("klinger_signal", &[High, Low, Close, Volume], klinger_params(), Box::new(|b, p| {
+            Ok(klinger_checked(&b.high, &b.low, &b.close, &b.volume, as_period(p[0])?, as_period(p[1])?, as_period(p[2])?)?.1)
+        }));
+        r.register("rvi", &[Open, High, Low, Close], period(10f64), Box::new(|b, p| Ok(rvi_checked(&b.open, &b.high, &b.low, &b.close, as_period(p[0])?)?.0)));
+        r.register("rvi_signal", &[Open, High, Low, Close], period(10f64), Box::new(|b, p| Ok(rvi_checked(&b.open, &b.high, &b.low, &b.close, as_period(p[0])?)?.1)));
+        r.register("pvt", &[Close, Volume], vec![], Box::new(|b, _| pvt_checked(&b.close, &b.volume)));
+        r.register("pvi", &[Close, Volume], vec![], Box::new(|b, _| Ok(pvi(&b.close, &b.volume))));
+        r.register("nvi", &[Close, Volume], vec![], Box::new(|b, _| Ok(nvi(&b.close, &b.volume))));
+        r.register("force_index", &[Close, Volume], period(13f64), Box::new(|b, p| force_index_checked(&b.close, &b.volume, as_period(p[0])?)));
+        r.register("mfi_bw", &[High, Low, Volume], vec![], Box::new(|b, _| Ok(mfi_bw_checked(&b.high, &b.low, &b.volume)?.0)));
+        r
+    }
+}
+
+/// Positive integer period from a numeric parameter
+fn as_period(x: f64) -> Result<usize, QuantError> {
+    if x >= 1f64 && x.fract() == 0f64 && x.is_finite() {
+        Ok(x as usize)
+    } else {
+        Err(QuantError::InvalidParameter(format!("period must be a positive integer, got {}", x)))
+    }
+}
+
+/// Bollinger bands (upper, middle, lower) from [period, amplitude]
+fn bands(v: &[f64], p: &[f64]) -> Result<[Vec<f64>; 3], QuantError> {
+    let period = as_period(p[0])?;
+    let mid = sma_checked(v, period)?;
+    let std = mstd_checked(v, period, &mid)?;
+    let (upper, lower) = bollinger_band(v, p[1], &mid, &std);
+    Ok([upper, mid, lower])
+}
+
+/// Compute a built-in indicator by name
+///
+/// # Arguments
+/// * `name` - &str (see `Registry`)
+/// * `ohlcv` - &Ohlcv
+/// * `params` - &[f64] (positional, missing trailing ones take their defaults)
+///
+/// # Returns
+/// * `Result<Vec<f64>, QuantError>`
+///
+/// # Examples
+/// ```
+/// use quantauri::registry::compute_by_name;
+/// use quantauri::price::Ohlcv;
+/// use quantauri::base::{rsi, macd};
+///
+/// fn main() {
+///     let close = (0 .. 60).map(|x| 100f64 + (x as f64 * 0.4).sin() * 3f64).collect::<Vec<f64>>();
+///     let bars = Ohlcv::new(close.clone(), close.clone(), close.clone(), close.clone(), vec![1f64; 60]);
+///
+///     // Compare past the NAN warm-up
+///     assert_eq!(compute_by_name("rsi", &bars, &[]).unwrap()[20 ..], rsi(&close, 14)[20 ..]);
+///     assert_eq!(compute_by_name("macd_signal", &bars, &[]).unwrap()[40 ..], macd(&close).1[40 ..]);
+///
+///     let err = compute_by_name("foo", &bars, &[]).unwrap_err().to_string();
+///     assert!(err.contains("unknown indicator foo") && err.contains("sma"));
+///     assert!(compute_by_name("sma", &bars, &[2.5]).is_err());
+///     assert!(compute_by_name("sma", &bars, &[5f64, 1f64]).is_err());
+/// }
+/// ```
+pub fn compute_by_name(name: &str, ohlcv: &Ohlcv, params: &[f64]) -> Result<Vec<f64>, QuantError> {
+    builtin().compute(name, ohlcv, params)
+}
+
+/// `Registry::default()`, built on first use and shared afterwards
+fn builtin() -> &'static Registry {
+    static BUILTIN: OnceLock<Registry> = OnceLock::new();
+    BUILTIN.get_or_init(Registry::default)
+}
+
+// =============================================================================
+// Typed Dispatch
+// =============================================================================
+/// Built-in indicator with its parameters (a typed handle on a `Registry` entry)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndicatorKind {
+    Sma(usize),
+    Ema(usize),
+    Rsi(usize),
+    Cmo(usize),
+    Roc(usize),
+    /// MACD line
+    Macd { fast: usize, slow: usize, signal: usize },
+    Atr(usize),
+    Qstick(usize),
+    Pvt,
+    Pvi,
+    Nvi,
+    ForceIndex(usize),
+    /// Klinger oscillator line
+    Klinger { fast: usize, slow: usize, signal: usize },
+    /// Market Facilitation Index value (`mfi_bw`)
+    MarketFacilitation,
+}
+
+impl IndicatorKind {
+    /// Fields the indicator reads (those of its `Registry` entry)
+    pub fn inputs(&self) -> &'static [Field] {
+        builtin().get(self.name()).map_or(&[], |e| e.inputs)
+    }
+
+    /// Name of the `Registry` entry
+    pub fn name(&self) -> &'static str {
+        match self {
+            IndicatorKind::Sma(_) => "sma",
+            IndicatorKind::Ema(_) => "ema",
+            IndicatorKind::Rsi(_) => "rsi",
+            IndicatorKind::Cmo(_) => "cmo",
+            IndicatorKind::Roc(_) => "roc",
+            IndicatorKind::Macd { .. } => "macd",
+            IndicatorKind::Atr(_) => "atr",
+            IndicatorKind::Qstick(_) => "qstick",
+            IndicatorKind::Pvt => "pvt",
+            IndicatorKind::Pvi => "pvi",
+            IndicatorKind::Nvi => "nvi",
+            IndicatorKind::ForceIndex(_) => "force_index",
+            IndicatorKind::Klinger { .. } => "klinger",
+            IndicatorKind::MarketFacilitation => "mfi_bw",
+        }
+    }
+
+    /// Positional parameters of the `Registry` entry
+    pub fn params(&self) -> Vec<f64> {
+        match *self {
+            IndicatorKind::Sma(p) | IndicatorKind::Ema(p) | IndicatorKind::Rsi(p) | IndicatorKind::Cmo(p)
+            | IndicatorKind::Roc(p) | IndicatorKind::Atr(p) | IndicatorKind::Qstick(p)
+            | IndicatorKind::ForceIndex(p) => vec![p as f64],
+            IndicatorKind::Macd { fast, slow, signal } | IndicatorKind::Klinger { fast, slow, signal } => {
+                vec![fast as f64, slow as f64, signal as f64]
+            }
+            IndicatorKind::Pvt | IndicatorKind::Pvi | IndicatorKind::Nvi | IndicatorKind::MarketFacilitation => vec![],
+        }
+    }
+}
+
+/// Compute a typed indicator
+///
+/// # Arguments
+/// * `kind` - IndicatorKind
+/// * `ohlcv` - &Ohlcv
+///
+/// # Returns
+/// * `Result<Vec<f64>, QuantError>`
+///   * `compute_by_name(kind.name(), ohlcv, &kind.params())`
+///
+/// # Description
+/// Only the fields in `kind.inputs()` are looked at, so bars loaded without a volume
+/// column still work for price-only indicators.
+///
+/// # Errors
+/// * Errors of `Registry::compute` (a period of 0 is an `InvalidParameter`)
+///
+/// # Examples
+/// ```
+/// use quantauri::registry::{compute, compute_by_name, IndicatorKind};
+/// use quantauri::price::Ohlcv;
+/// use quantauri::base::sma;
+///
+/// fn main() {
+///     // No volume column
+///     let close = vec![10f64, 11f64, 12f64, 11f64];
+///     let bars = Ohlcv { open: vec![], high: vec![], low: vec![], close: close.clone(), volume: vec![] };
+///
+///     assert_eq!(compute(IndicatorKind::Sma(2), &bars).unwrap(), sma(&close, 2));
+///     assert_eq!(compute_by_name("sma", &bars, &[2f64]).unwrap(), sma(&close, 2));
+///     let err = compute(IndicatorKind::Pvt, &bars).unwrap_err().to_string();
+///     assert!(err.contains("pvt") && err.contains("volume"));
+///     assert!(compute(IndicatorKind::Atr(2), &bars).unwrap_err().to_string().contains("high"));
+///     assert!(compute(IndicatorKind::Rsi(0), &bars).is_err());
+/// }
+/// ```
+pub fn compute(kind: IndicatorKind, ohlcv: &Ohlcv) -> Result<Vec<f64>, QuantError> {
+    compute_by_name(kind.name(), ohlcv, &kind.params())
+}
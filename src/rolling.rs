@@ -0,0 +1,1043 @@
+use std::collections::VecDeque;
+use crate::error::{QuantError, check_len};
+use crate::stats::lagged_difference_hurst;
+
+// =============================================================================
+// Rolling Window Primitives
+// =============================================================================
+// Every function here uses the trailing window `v[i+1-window ..= i]`.
+// For `i < window - 1` the window is truncated to `v[0 ..= i]` (same warm-up
+// convention as `sma`).
+
+/// Rolling Sum
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///
+/// # Examples
+/// ```
+/// use quantauri::rolling::rolling_sum;
+///
+/// fn main() {
+///     let v = vec![1f64, 2f64, 3f64, 4f64];
+///     assert_eq!(rolling_sum(&v, 2), vec![1f64, 3f64, 5f64, 7f64]);
+/// }
+/// ```
+pub fn rolling_sum(v: &[f64], window: usize) -> Vec<f64> {
+    let mut result = vec![0f64; v.len()];
+    let mut sum = 0f64;
+    for i in 0 .. v.len() {
+        sum += v[i];
+        if i >= window {
+            sum -= v[i - window];
+        }
+        result[i] = sum;
+    }
+    result
+}
+
+/// Monotonic deque of bar indices whose values are decreasing (max) or
+/// increasing (min) from front to back, so the front is the window extremum
+///
+/// NAN values never enter the deque, so they are ignored. Ties resolve to the
+/// most recent bar.
+struct MonotonicDeque {
+    deque: VecDeque<usize>,
+    is_max: bool,
+}
+
+impl MonotonicDeque {
+    fn new(is_max: bool) -> Self {
+        MonotonicDeque { deque: VecDeque::new(), is_max }
+    }
+
+    /// Push bar `i` and drop bars that left the trailing `window`
+    fn advance(&mut self, v: &[f64], i: usize, window: usize) -> Option<usize> {
+        if !v[i].is_nan() {
+            while let Some(&j) = self.deque.back() {
+                let dominated = if self.is_max { v[j] <= v[i] } else { v[j] >= v[i] };
+                if !dominated {
+                    break;
+                }
+                self.deque.pop_back();
+            }
+            self.deque.push_back(i);
+        }
+        while let Some(&j) = self.deque.front() {
+            if j + window > i {
+                break;
+            }
+            self.deque.pop_front();
+        }
+        self.deque.front().copied()
+    }
+}
+
+/// Index of the window extremum for every bar (monotonic deque, O(n) total)
+///
+/// `None` means the window holds no valid value.
+fn monotonic_extrema(v: &[f64], window: usize, is_max: bool) -> Vec<Option<usize>> {
+    let mut result = vec![None; v.len()];
+    if window == 0 {
+        return result;
+    }
+    let mut deque = MonotonicDeque::new(is_max);
+    for i in 0 .. v.len() {
+        result[i] = deque.advance(v, i, window);
+    }
+    result
+}
+
+/// Rolling Maximum
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * O(n) regardless of `window` (monotonic deque)
+///   * NAN values are ignored, NAN if the window has no valid value
+///   * `window > v.len()` is fine (every window is partial)
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::rolling::{rolling_max, rolling_min};
+///
+/// fn main() {
+///     let v = Uniform(0, 1).sample(500);
+///     let (mx, mn) = (rolling_max(&v, 7), rolling_min(&v, 7));
+///     for i in 0 .. v.len() {
+///         let w = &v[i.saturating_sub(6) ..= i];
+///         assert_eq!(mx[i], w.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b)));
+///         assert_eq!(mn[i], w.iter().fold(f64::INFINITY, |a, &b| a.min(b)));
+///     }
+///     assert_eq!(rolling_max(&[1f64, 3f64, 2f64], 10), vec![1f64, 3f64, 3f64]);
+/// }
+/// ```
+pub fn rolling_max(v: &[f64], window: usize) -> Vec<f64> {
+    monotonic_extrema(v, window, true).iter().map(|j| j.map_or(f64::NAN, |j| v[j])).collect()
+}
+
+/// Rolling Minimum
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * Same conventions as `rolling_max`
+pub fn rolling_min(v: &[f64], window: usize) -> Vec<f64> {
+    monotonic_extrema(v, window, false).iter().map(|j| j.map_or(f64::NAN, |j| v[j])).collect()
+}
+
+/// Rolling Minimum and Maximum
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (min, max) computed in a single pass with two monotonic deques, O(n)
+///   * Same conventions as `rolling_max`
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::rolling::rolling_minmax;
+///
+/// fn main() {
+///     let mut v = Normal(0, 1).sample(1000);
+///     v[10] = f64::NAN;
+///     for &w in [1usize, 5, 50].iter() {
+///         let (mn, mx) = rolling_minmax(&v, w);
+///         for i in 0 .. v.len() {
+///             // Naive O(n * w) reference
+///             let win = &v[i.saturating_sub(w - 1) ..= i];
+///             let valid = win.iter().filter(|x| !x.is_nan());
+///             let naive_max = valid.clone().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+///             let naive_min = valid.fold(f64::INFINITY, |a, &b| a.min(b));
+///             if w == 1 && i == 10 {
+///                 assert!(mn[i].is_nan() && mx[i].is_nan());
+///             } else {
+///                 assert_eq!((mn[i], mx[i]), (naive_min, naive_max));
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn rolling_minmax(v: &[f64], window: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut min = vec![f64::NAN; v.len()];
+    let mut max = vec![f64::NAN; v.len()];
+    if window == 0 {
+        return (min, max);
+    }
+    let mut min_deque = MonotonicDeque::new(false);
+    let mut max_deque = MonotonicDeque::new(true);
+    for i in 0 .. v.len() {
+        if let Some(j) = min_deque.advance(v, i, window) {
+            min[i] = v[j];
+        }
+        if let Some(j) = max_deque.advance(v, i, window) {
+            max[i] = v[j];
+        }
+    }
+    (min, max)
+}
+
+/// Rolling Argmax
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * Bars since the window maximum (0 = current bar), most recent bar on ties
+///   * NAN if the window has no valid value
+///
+/// # Examples
+/// ```
+/// use quantauri::rolling::{rolling_argmax, rolling_argmin};
+///
+/// fn main() {
+///     let v = vec![1f64, 5f64, 2f64, 3f64, 0f64];
+///     assert_eq!(rolling_argmax(&v, 3), vec![0f64, 0f64, 1f64, 2f64, 1f64]);
+///     assert_eq!(rolling_argmin(&v, 3), vec![0f64, 1f64, 2f64, 1f64, 0f64]);
+/// }
+/// ```
+pub fn rolling_argmax(v: &[f64], window: usize) -> Vec<f64> {
+    monotonic_extrema(v, window, true).iter().enumerate().map(|(i, j)| j.map_or(f64::NAN, |j| (i - j) as f64)).collect()
+}
+
+/// Rolling Argmin
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * Bars since the window minimum (0 = current bar), most recent bar on ties
+///   * NAN if the window has no valid value
+pub fn rolling_argmin(v: &[f64], window: usize) -> Vec<f64> {
+    monotonic_extrema(v, window, false).iter().enumerate().map(|(i, j)| j.map_or(f64::NAN, |j| (i - j) as f64)).collect()
+}
+
+// =============================================================================
+// Rolling Order Statistics
+// =============================================================================
+/// Multiset of window values supporting O(log n) insert/remove/k-th smallest
+///
+/// Values are compressed to ranks over the whole series once, and a Fenwick
+/// tree of counts answers order-statistic queries. NAN values are never stored.
+struct OrderStatistics {
+    values: Vec<f64>,
+    tree: Vec<usize>,
+    count: usize,
+}
+
+impl OrderStatistics {
+    fn new(v: &[f64]) -> Self {
+        let mut values = v.iter().copied().filter(|x| !x.is_nan()).collect::<Vec<f64>>();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values.dedup();
+        let tree = vec![0usize; values.len() + 1];
+        OrderStatistics { values, tree, count: 0 }
+    }
+
+    fn update(&mut self, x: f64, insert: bool) {
+        if x.is_nan() {
+            return;
+        }
+        let mut i = self.values.partition_point(|&y| y < x) + 1;
+        while i < self.tree.len() {
+            if insert {
+                self.tree[i] += 1;
+            } else {
+                self.tree[i] -= 1;
+            }
+            i += i & i.wrapping_neg();
+        }
+        if insert {
+            self.count += 1;
+        } else {
+            self.count -= 1;
+        }
+    }
+
+    fn insert(&mut self, x: f64) {
+        self.update(x, true);
+    }
+
+    fn remove(&mut self, x: f64) {
+        self.update(x, false);
+    }
+
+    /// Number of stored values strictly below `x` and equal to `x`
+    fn rank_counts(&self, x: f64) -> (usize, usize) {
+        let prefix = |mut i: usize| {
+            let mut total = 0usize;
+            while i > 0 {
+                total += self.tree[i];
+                i -= i & i.wrapping_neg();
+            }
+            total
+        };
+        let below = prefix(self.values.partition_point(|&y| y < x));
+        let not_above = prefix(self.values.partition_point(|&y| y <= x));
+        (below, not_above - below)
+    }
+
+    /// k-th smallest stored value (0-based, `k < count`)
+    fn kth(&self, k: usize) -> f64 {
+        let mut pos = 0usize;
+        let mut remaining = k + 1;
+        let mut step = (self.tree.len() - 1).next_power_of_two();
+        while step > 0 {
+            let next = pos + step;
+            if next < self.tree.len() && self.tree[next] < remaining {
+                pos = next;
+                remaining -= self.tree[next];
+            }
+            step >>= 1;
+        }
+        self.values[pos]
+    }
+}
+
+/// Rolling Median
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * Median of the valid (non-NAN) values of each trailing window
+///   * Even number of values: mean of the two middle values
+///   * NAN if the window has no valid value
+///   * O(log n) per bar instead of sorting every window
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::rolling::rolling_median;
+///
+/// fn main() {
+///     let mut v = Uniform(0, 100).sample(300);
+///     v[50] = f64::NAN;
+///     for &w in [4usize, 5].iter() {
+///         let med = rolling_median(&v, w);
+///         for i in 0 .. v.len() {
+///             let mut win = v[i.saturating_sub(w - 1) ..= i].iter().copied().filter(|x| !x.is_nan()).collect::<Vec<f64>>();
+///             win.sort_by(|a, b| a.partial_cmp(b).unwrap());
+///             let m = win.len();
+///             let expected = if m % 2 == 1 { win[m / 2] } else { (win[m / 2 - 1] + win[m / 2]) / 2f64 };
+///             assert_eq!(med[i], expected);
+///         }
+///     }
+/// }
+/// ```
+pub fn rolling_median(v: &[f64], window: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; v.len()];
+    if window == 0 {
+        return result;
+    }
+    let mut stats = OrderStatistics::new(v);
+    for i in 0 .. v.len() {
+        stats.insert(v[i]);
+        if i >= window {
+            stats.remove(v[i - window]);
+        }
+        let m = stats.count;
+        if m == 0 {
+            continue;
+        }
+        result[i] = if m % 2 == 1 {
+            stats.kth(m / 2)
+        } else {
+            (stats.kth(m / 2 - 1) + stats.kth(m / 2)) / 2f64
+        };
+    }
+    result
+}
+
+/// Rolling Quantile
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+/// * `q` - f64 in [0, 1]
+///
+/// # Returns
+/// * Vec<f64>
+///   * Linear interpolation between order statistics of the valid values
+///     (h = (m - 1) q, x[floor(h)] + (h - floor(h)) (x[floor(h) + 1] - x[floor(h)]),
+///     same as numpy's default / R type 7)
+///   * NAN if the window has no valid value
+///
+/// # Examples
+/// ```
+/// use quantauri::rolling::rolling_quantile;
+///
+/// fn main() {
+///     let v = vec![4f64, 1f64, 3f64, 2f64];
+///     let q = rolling_quantile(&v, 4, 0.25);
+///     assert_eq!(q[3], 1.75);
+///     assert_eq!(rolling_quantile(&v, 4, 1f64)[3], 4f64);
+/// }
+/// ```
+pub fn rolling_quantile(v: &[f64], window: usize, q: f64) -> Vec<f64> {
+    rolling_quantile_checked(v, window, q).expect("rolling_quantile: invalid input")
+}
+
+/// Rolling Quantile (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `window == 0`
+/// * `InvalidParameter` if `q` is not in [0, 1]
+pub fn rolling_quantile_checked(v: &[f64], window: usize, q: f64) -> Result<Vec<f64>, QuantError> {
+    if window == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    if !(0f64 ..= 1f64).contains(&q) {
+        return Err(QuantError::InvalidParameter(format!("quantile {} is not in [0, 1]", q)));
+    }
+    let mut result = vec![f64::NAN; v.len()];
+    let mut stats = OrderStatistics::new(v);
+    for i in 0 .. v.len() {
+        stats.insert(v[i]);
+        if i >= window {
+            stats.remove(v[i - window]);
+        }
+        let m = stats.count;
+        if m == 0 {
+            continue;
+        }
+        let h = (m - 1) as f64 * q;
+        let lo = h.floor() as usize;
+        let x_lo = stats.kth(lo);
+        result[i] = if lo + 1 < m {
+            x_lo + (h - lo as f64) * (stats.kth(lo + 1) - x_lo)
+        } else {
+            x_lo
+        };
+    }
+    Ok(result)
+}
+
+/// Rolling Percentile
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+/// * `percentile` - f64 in [0, 100]
+///
+/// # Returns
+/// * Vec<f64>
+///   * `rolling_quantile(v, period, percentile / 100)`
+///   * Linear interpolation between the closest ranks (numpy default / R type 7)
+///   * Partial windows at the start, NAN values are ignored
+///   * O(n log n) overall: the window is kept in an order-statistics tree, not re-sorted
+///
+/// # Examples
+/// ```
+/// use quantauri::rolling::rolling_percentile;
+///
+/// fn main() {
+///     let v = vec![4f64, 1f64, 3f64, 2f64, 10f64];
+///     let p = rolling_percentile(&v, 4, 25f64);
+///     assert_eq!(p[3], 1.75);
+///     assert_eq!(p[4], 1.75);
+///     assert_eq!(rolling_percentile(&v, 4, 50f64)[4], 2.5);
+/// }
+/// ```
+pub fn rolling_percentile(v: &[f64], period: usize, percentile: f64) -> Vec<f64> {
+    rolling_percentile_checked(v, period, percentile).expect("rolling_percentile: invalid input")
+}
+
+/// Rolling Percentile (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `period == 0`
+/// * `InvalidParameter` if `percentile` is not in [0, 100]
+pub fn rolling_percentile_checked(v: &[f64], period: usize, percentile: f64) -> Result<Vec<f64>, QuantError> {
+    if !(0f64 ..= 100f64).contains(&percentile) {
+        return Err(QuantError::InvalidParameter(format!("percentile {} is not in [0, 100]", percentile)));
+    }
+    rolling_quantile_checked(v, period, percentile / 100f64)
+}
+
+/// Rolling Rank
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * Percent-rank (0 - 100) of v[i] among the valid values of its trailing window (v[i] included)
+///   * 100 * (below + (equal - 1) / 2) / (m - 1), where `m` is the number of valid values:
+///     0 for the window minimum, 100 for the maximum, ties share the mean rank
+///   * 50 if v[i] is the only valid value, NAN if v[i] is NAN
+///   * Partial windows at the start, O(log n) per bar
+///
+/// # Examples
+/// ```
+/// use quantauri::rolling::rolling_rank;
+///
+/// fn main() {
+///     let v = vec![3f64, 1f64, 2f64, 5f64, 2f64];
+///     let r = rolling_rank(&v, 3);
+///     assert_eq!(r, vec![50f64, 0f64, 50f64, 100f64, 25f64]);
+/// }
+/// ```
+pub fn rolling_rank(v: &[f64], period: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; v.len()];
+    if period == 0 {
+        return result;
+    }
+    let mut stats = OrderStatistics::new(v);
+    for i in 0 .. v.len() {
+        stats.insert(v[i]);
+        if i >= period {
+            stats.remove(v[i - period]);
+        }
+        if v[i].is_nan() {
+            continue;
+        }
+        let m = stats.count;
+        if m == 1 {
+            result[i] = 50f64;
+            continue;
+        }
+        let (below, equal) = stats.rank_counts(v[i]);
+        result[i] = 100f64 * (below as f64 + (equal - 1) as f64 / 2f64) / (m - 1) as f64;
+    }
+    result
+}
+
+/// Percent rank of each value against the `window` values before it
+///
+/// 100 * (below + equal / 2) / window over `v[i-window .. i]`, O(log n) per bar.
+/// NAN for the first `window` values, when v[i] is NAN or the previous window contains NAN.
+pub(crate) fn previous_percent_rank(v: &[f64], window: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; v.len()];
+    if window == 0 {
+        return result;
+    }
+    let mut stats = OrderStatistics::new(v);
+    let mut last_nan = None;
+    for i in 0 .. v.len() {
+        if i >= window {
+            if !v[i].is_nan() && last_nan.is_none_or(|j| j + window < i) {
+                let (below, equal) = stats.rank_counts(v[i]);
+                result[i] = 100f64 * (below as f64 + equal as f64 / 2f64) / window as f64;
+            }
+            stats.remove(v[i - window]);
+        }
+        if v[i].is_nan() {
+            last_nan = Some(i);
+        }
+        stats.insert(v[i]);
+    }
+    result
+}
+
+/// Percentile Channel
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+/// * `lower_q` - f64 in [0, 1]
+/// * `upper_q` - f64 in [0, 1]
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>, Vec<f64>)
+///   * (upper, middle, lower) like `BollingerBand::bb`
+///   * upper = rolling_quantile(upper_q), middle = rolling_median, lower = rolling_quantile(lower_q)
+///
+/// # Examples
+/// ```
+/// use quantauri::rolling::percentile_channel;
+///
+/// fn main() {
+///     let v = (0 .. 50).map(|x| (x as f64 * 0.5).sin()).collect::<Vec<f64>>();
+///     let (upper, mid, lower) = percentile_channel(&v, 20, 0.1, 0.9);
+///     assert!((0 .. 50).all(|i| lower[i] <= mid[i] && mid[i] <= upper[i]));
+/// }
+/// ```
+pub fn percentile_channel(v: &[f64], window: usize, lower_q: f64, upper_q: f64) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let upper = rolling_quantile(v, window, upper_q);
+    let middle = rolling_median(v, window);
+    let lower = rolling_quantile(v, window, lower_q);
+    (upper, middle, lower)
+}
+
+// =============================================================================
+// Rolling Higher Moments
+// =============================================================================
+/// Central moments (m2, m3, m4) of each full trailing window
+///
+/// Power sums of `x - origin` are updated incrementally as the window slides.
+/// Every `window` bars the origin is moved to the current window mean and the
+/// sums are recomputed, which keeps cancellation small on large-price series
+/// and bounds the drift of the running updates (amortized O(1) per bar).
+fn rolling_central_moments(v: &[f64], window: usize) -> Vec<(f64, f64, f64)> {
+    let mut result = vec![(f64::NAN, f64::NAN, f64::NAN); v.len()];
+    if window == 0 || window > v.len() {
+        return result;
+    }
+    let w = window as f64;
+    let mut origin = v[0];
+    let mut s = [0f64; 4];
+    let add = |s: &mut [f64; 4], d: f64, sign: f64| {
+        s[0] += sign * d;
+        s[1] += sign * d * d;
+        s[2] += sign * d * d * d;
+        s[3] += sign * d * d * d * d;
+    };
+    for i in 0 .. v.len() {
+        if i >= window && (i + 1) % window == 0 {
+            origin = v[i + 1 - window ..= i].iter().sum::<f64>() / w;
+            s = [0f64; 4];
+            for j in i + 1 - window ..= i {
+                add(&mut s, v[j] - origin, 1f64);
+            }
+        } else {
+            add(&mut s, v[i] - origin, 1f64);
+            if i >= window {
+                add(&mut s, v[i - window] - origin, -1f64);
+            }
+        }
+        if i + 1 >= window {
+            let mu = s[0] / w;
+            let r2 = s[1] / w;
+            let r3 = s[2] / w;
+            let r4 = s[3] / w;
+            let m2 = r2 - mu * mu;
+            let m3 = r3 - 3f64 * mu * r2 + 2f64 * mu.powi(3);
+            let m4 = r4 - 4f64 * mu * r3 + 6f64 * mu * mu * r2 - 3f64 * mu.powi(4);
+            result[i] = (m2, m3, m4);
+        }
+    }
+    result
+}
+
+/// Rolling Skewness
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * Population skewness m3 / m2^(3/2) of each trailing window
+///   * First `window - 1` values are 0 (same warm-up convention as `mstd`)
+///   * NAN for constant windows
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::rolling::{rolling_skew, rolling_kurtosis};
+///
+/// fn main() {
+///     // BTC-like price level: large mean, small dispersion
+///     let noise = Normal(0, 1).sample(600);
+///     let mut v = vec![30_000f64; 600];
+///     for i in 1 .. 600 {
+///         v[i] = v[i-1] + 50f64 * noise[i];
+///     }
+///     let w = 50;
+///     let skew = rolling_skew(&v, w);
+///     let kurt = rolling_kurtosis(&v, w);
+///     assert_eq!(skew[w - 2], 0f64);
+///     for i in w - 1 .. v.len() {
+///         let win = &v[i + 1 - w ..= i];
+///         let mean = win.iter().sum::<f64>() / w as f64;
+///         let m = |p: i32| win.iter().map(|x| (x - mean).powi(p)).sum::<f64>() / w as f64;
+///         let naive_skew = m(3) / m(2).powf(1.5);
+///         let naive_kurt = m(4) / m(2).powi(2) - 3f64;
+///         assert!((skew[i] - naive_skew).abs() <= 1e-9 * naive_skew.abs().max(1f64));
+///         assert!((kurt[i] - naive_kurt).abs() <= 1e-9 * naive_kurt.abs().max(1f64));
+///     }
+/// }
+/// ```
+pub fn rolling_skew(v: &[f64], window: usize) -> Vec<f64> {
+    let moments = rolling_central_moments(v, window);
+    let mut result = vec![0f64; v.len()];
+    for i in window.max(1) - 1 .. v.len() {
+        let (m2, m3, _) = moments[i];
+        result[i] = if m2 > 0f64 { m3 / m2.powf(1.5) } else { f64::NAN };
+    }
+    result
+}
+
+/// Rolling Excess Kurtosis
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * Population excess kurtosis m4 / m2^2 - 3 of each trailing window
+///   * First `window - 1` values are 0 (same warm-up convention as `mstd`)
+///   * NAN for constant windows
+pub fn rolling_kurtosis(v: &[f64], window: usize) -> Vec<f64> {
+    let moments = rolling_central_moments(v, window);
+    let mut result = vec![0f64; v.len()];
+    for i in window.max(1) - 1 .. v.len() {
+        let (m2, _, m4) = moments[i];
+        result[i] = if m2 > 0f64 { m4 / m2.powi(2) - 3f64 } else { f64::NAN };
+    }
+    result
+}
+
+/// Rolling Hurst Exponent
+///
+/// # Arguments
+/// * `v` - &[f64] (price or log-price levels, not returns)
+/// * `window` - usize
+/// * `lags` - &[usize] (e.g. `[2, 4, 8, 16]`)
+///
+/// # Returns
+/// * Vec<f64>
+///   * Variance-of-lagged-differences estimator: for each lag t, the standard
+///     deviation of v[j] - v[j-t] inside the window scales as t^H, and H is the
+///     least-squares slope of ln(std) against ln(t)
+///   * H > 0.5: trending (persistent), H < 0.5: mean-reverting, H = 0.5: random walk
+///   * First `window - 1` values are NAN; all NAN if `window <= max(lags)`
+///   * NAN if fewer than two lags have a positive spread, or the window has a NAN
+///   * O(window * lags.len()) per bar
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::rolling::rolling_hurst;
+///
+/// fn main() {
+///     let n = 2000;
+///     let e = Normal(0, 1).sample(n);
+///     // Trending: cumulated AR(1) increments with positive autocorrelation
+///     let mut inc = vec![0f64; n];
+///     let mut trend = vec![0f64; n];
+///     // Mean-reverting: AR(1) level
+///     let mut revert = vec![0f64; n];
+///     for i in 1 .. n {
+///         inc[i] = 0.8 * inc[i-1] + e[i];
+///         trend[i] = trend[i-1] + inc[i];
+///         revert[i] = 0.3 * revert[i-1] + e[i];
+///     }
+///     let lags = [2usize, 4, 8, 16];
+///     let mean = |h: &[f64]| h[499 ..].iter().sum::<f64>() / (n - 499) as f64;
+///     let h_trend = rolling_hurst(&trend, 500, &lags);
+///     let h_revert = rolling_hurst(&revert, 500, &lags);
+///     assert!(h_trend[498].is_nan());
+///     assert!(mean(&h_trend) > 0.6);
+///     assert!(mean(&h_revert) < 0.4);
+///     assert!(rolling_hurst(&trend, 16, &lags).iter().all(|h| h.is_nan()));
+/// }
+/// ```
+pub fn rolling_hurst(v: &[f64], window: usize, lags: &[usize]) -> Vec<f64> {
+    rolling_hurst_checked(v, window, lags).expect("rolling_hurst: invalid input")
+}
+
+/// Rolling Hurst Exponent (fallible)
+///
+/// # Errors
+/// * `InvalidParameter` if a lag is 0 or there are fewer than two distinct lags
+pub fn rolling_hurst_checked(v: &[f64], window: usize, lags: &[usize]) -> Result<Vec<f64>, QuantError> {
+    let mut lags = lags.to_vec();
+    lags.sort_unstable();
+    lags.dedup();
+    if lags.len() < 2 || lags[0] == 0 {
+        return Err(QuantError::InvalidParameter("rolling_hurst needs at least two positive lags".to_string()));
+    }
+    let mut result = vec![f64::NAN; v.len()];
+    let max_lag = lags[lags.len() - 1];
+    if window <= max_lag || window > v.len() {
+        return Ok(result);
+    }
+    for i in window - 1 .. v.len() {
+        result[i] = lagged_difference_hurst(&v[i + 1 - window ..= i], &lags);
+    }
+    Ok(result)
+}
+
+// =============================================================================
+// Rolling Dependence
+// =============================================================================
+/// `true` where the trailing window is constant (exact, unlike a variance
+/// threshold which is exposed to the `sma` running-sum drift)
+fn flat_windows(v: &[f64], period: usize) -> Vec<bool> {
+    let hi = rolling_max(v, period);
+    let lo = rolling_min(v, period);
+    (0 .. v.len()).map(|i| hi[i] == lo[i]).collect()
+}
+
+/// Window mean of `y` and population (co)variances
+#[derive(Debug, Clone, Copy)]
+struct CoMoments {
+    mean_y: f64,
+    var_x: f64,
+    var_y: f64,
+    cov: f64,
+}
+
+/// Rolling `CoMoments` of each full trailing window
+///
+/// Shifted-origin sums are updated incrementally and recomputed about the
+/// window means every `window` bars, as in `rolling_central_moments`.
+/// Pairs with a NAN are skipped; windows containing one are NAN.
+fn rolling_comoments(x: &[f64], y: &[f64], window: usize) -> Vec<CoMoments> {
+    let nan = CoMoments { mean_y: f64::NAN, var_x: f64::NAN, var_y: f64::NAN, cov: f64::NAN };
+    let mut result = vec![nan; x.len()];
+    if window == 0 || window > x.len() {
+        return result;
+    }
+    let w = window as f64;
+    let (mut ox, mut oy) = (0f64, 0f64);
+    let mut s = [0f64; 5];
+    let mut nan_count = 0usize;
+    let update = |s: &mut [f64; 5], dx: f64, dy: f64, sign: f64| {
+        s[0] += sign * dx;
+        s[1] += sign * dy;
+        s[2] += sign * dx * dx;
+        s[3] += sign * dy * dy;
+        s[4] += sign * dx * dy;
+    };
+    for i in 0 .. x.len() {
+        if (i + 1) % window == 0 && nan_count == 0 && !x[i].is_nan() && !y[i].is_nan()
+            && (i < window || (!x[i - window].is_nan() && !y[i - window].is_nan())) {
+            // Window [i + 1 - window, i] is NAN-free: recenter and recompute
+            let range = i + 1 - window ..= i;
+            ox = x[range.clone()].iter().sum::<f64>() / w;
+            oy = y[range.clone()].iter().sum::<f64>() / w;
+            s = [0f64; 5];
+            for j in range {
+                update(&mut s, x[j] - ox, y[j] - oy, 1f64);
+            }
+        } else {
+            if x[i].is_nan() || y[i].is_nan() {
+                nan_count += 1;
+            } else {
+                update(&mut s, x[i] - ox, y[i] - oy, 1f64);
+            }
+            if i >= window {
+                let (xo, yo) = (x[i - window], y[i - window]);
+                if xo.is_nan() || yo.is_nan() {
+                    nan_count -= 1;
+                } else {
+                    update(&mut s, xo - ox, yo - oy, -1f64);
+                }
+            }
+        }
+        if i + 1 >= window && nan_count == 0 {
+            let mx = s[0] / w;
+            let my = s[1] / w;
+            let var_x = (s[2] / w - mx * mx).max(0f64);
+            let var_y = (s[3] / w - my * my).max(0f64);
+            let cov = s[4] / w - mx * my;
+            result[i] = CoMoments { mean_y: oy + my, var_x, var_y, cov };
+        }
+    }
+    result
+}
+
+/// Rolling Correlation
+///
+/// # Arguments
+/// * `x` - &[f64]
+/// * `y` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * Pearson correlation cov(x, y) / (std(x) * std(y)) over each trailing window
+///   * Incremental sums: O(1) amortized per bar
+///   * First `window - 1` values are NAN
+///   * NAN (never +-inf) where either series is constant over the window,
+///     or where the window contains a NAN
+///
+/// # Description
+/// Not composed from `sma` / `mstd`: `mstd` re-sums each window (O(window) per bar) and
+/// covariance needs the cross term sum(x * y) of the same window anyway. The sums of
+/// x, y, x², y² and xy are kept together in one pass (`rolling_cov` and `rolling_beta`
+/// share it), recentered on the window mean every `window` bars to limit cancellation.
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::rolling::{rolling_corr, rolling_corr_checked};
+///
+/// fn main() {
+///     let steps = Normal(0, 1).sample(500);
+///     let noise = Normal(0f64, 0.1).sample(500);
+///     let mut x = vec![100f64; 500];
+///     for i in 1 .. 500 {
+///         x[i] = x[i-1] + steps[i];
+///     }
+///     let y = (0 .. 500).map(|i| 2f64 * x[i] + noise[i]).collect::<Vec<f64>>();
+///     let corr = rolling_corr(&x, &y, 30);
+///     assert!(corr[28].is_nan());
+///     assert!(corr[29 ..].iter().all(|&c| c > 0.9 && c <= 1f64 + 1e-12));
+///
+///     let flat = vec![1f64; 500];
+///     assert!(rolling_corr(&x, &flat, 30)[100].is_nan());
+///     assert!(rolling_corr_checked(&x, &y[.. 499], 30).is_err());
+///
+///     // Perfect anti-correlation
+///     let a = vec![1f64, 2f64, 3f64, 2f64, 1f64];
+///     let b = a.iter().map(|x| -2f64 * x).collect::<Vec<f64>>();
+///     assert!((rolling_corr(&a, &b, 3)[3] + 1f64).abs() < 1e-12);
+/// }
+/// ```
+pub fn rolling_corr(x: &[f64], y: &[f64], window: usize) -> Vec<f64> {
+    rolling_corr_checked(x, y, window).expect("rolling_corr: invalid input")
+}
+
+/// Rolling Correlation (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `x` and `y` differ in length
+/// * `InvalidPeriod` if `window == 0`
+pub fn rolling_corr_checked(x: &[f64], y: &[f64], window: usize) -> Result<Vec<f64>, QuantError> {
+    check_len(x.len(), y.len())?;
+    if window == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    let moments = rolling_comoments(x, y, window);
+    let flat_x = flat_windows(x, window);
+    let flat_y = flat_windows(y, window);
+    let mut result = vec![f64::NAN; x.len()];
+    for i in 0 .. x.len() {
+        let CoMoments { var_x, var_y, cov, .. } = moments[i];
+        let denom = (var_x * var_y).sqrt();
+        if !flat_x[i] && !flat_y[i] && denom > 0f64 {
+            result[i] = (cov / denom).clamp(-1f64, 1f64);
+        }
+    }
+    Ok(result)
+}
+
+/// Former name of `rolling_corr`
+#[doc(hidden)]
+#[deprecated(note = "use rolling_corr")]
+pub fn rolling_correlation(a: &[f64], b: &[f64], period: usize) -> Vec<f64> {
+    rolling_corr(a, b, period)
+}
+
+/// Rolling Covariance
+///
+/// # Arguments
+/// * `x` - &[f64]
+/// * `y` - &[f64]
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * Population covariance of x and y over each trailing window
+///   * Same incremental sums as `rolling_corr`
+///   * First `window - 1` values are NAN, NAN where the window contains a NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::rolling::rolling_cov;
+///
+/// fn main() {
+///     let x = vec![1f64, 2f64, 3f64, 5f64, 8f64];
+///     let y = vec![2f64, 1f64, 4f64, 3f64, 9f64];
+///     let cov = rolling_cov(&x, &y, 3);
+///     assert!(cov[1].is_nan());
+///     // window [3, 5, 8] x [4, 3, 9]: means 16/3, 16/3
+///     let expected = ((3f64 - 16f64 / 3f64) * (4f64 - 16f64 / 3f64)
+///         + (5f64 - 16f64 / 3f64) * (3f64 - 16f64 / 3f64)
+///         + (8f64 - 16f64 / 3f64) * (9f64 - 16f64 / 3f64)) / 3f64;
+///     assert!((cov[4] - expected).abs() < 1e-12);
+/// }
+/// ```
+pub fn rolling_cov(x: &[f64], y: &[f64], window: usize) -> Vec<f64> {
+    rolling_cov_checked(x, y, window).expect("rolling_cov: invalid input")
+}
+
+/// Rolling Covariance (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `x` and `y` differ in length
+/// * `InvalidPeriod` if `window == 0`
+pub fn rolling_cov_checked(x: &[f64], y: &[f64], window: usize) -> Result<Vec<f64>, QuantError> {
+    check_len(x.len(), y.len())?;
+    if window == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    Ok(rolling_comoments(x, y, window).iter().map(|m| m.cov).collect())
+}
+
+/// Relative variance below which a benchmark window is treated as constant
+const BETA_VARIANCE_EPS: f64 = 1e-12;
+
+/// Rolling Beta
+///
+/// # Arguments
+/// * `asset` - &[f64] (asset returns)
+/// * `market` - &[f64] (benchmark returns)
+/// * `period` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * rolling_cov(asset, market) / var(market) over each trailing window
+///   * First `period - 1` values are NAN
+///   * NAN where the benchmark window is (nearly) constant:
+///     var(market) <= 1e-12 * mean(market^2), so beta never blows up on rounding noise
+///
+/// # Examples
+/// ```
+/// use quantauri::rolling::{rolling_beta, rolling_beta_checked};
+///
+/// fn main() {
+///     let market = vec![0.01, -0.02, 0.03, 0.01, -0.01];
+///     let asset = market.iter().map(|x| 1.5 * x + 0.001).collect::<Vec<f64>>();
+///     let beta = rolling_beta(&asset, &market, 3);
+///     assert!((beta[4] - 1.5).abs() < 1e-12);
+///
+///     // Constant benchmark returns
+///     let flat = vec![0.001; 5];
+///     assert!(rolling_beta(&asset, &flat, 3)[4].is_nan());
+///     assert!(rolling_beta_checked(&asset, &market[.. 4], 3).is_err());
+/// }
+/// ```
+pub fn rolling_beta(asset: &[f64], market: &[f64], period: usize) -> Vec<f64> {
+    rolling_beta_checked(asset, market, period).expect("rolling_beta: invalid input")
+}
+
+/// Rolling Beta (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `asset` and `market` differ in length
+/// * `InvalidPeriod` if `period == 0`
+pub fn rolling_beta_checked(asset: &[f64], market: &[f64], period: usize) -> Result<Vec<f64>, QuantError> {
+    check_len(asset.len(), market.len())?;
+    if period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    let moments = rolling_comoments(asset, market, period);
+    let flat_m = flat_windows(market, period);
+    let mut result = vec![f64::NAN; asset.len()];
+    for i in 0 .. asset.len() {
+        let CoMoments { mean_y, var_y, cov, .. } = moments[i];
+        let scale = var_y + mean_y * mean_y;
+        if !flat_m[i] && var_y > BETA_VARIANCE_EPS * scale {
+            result[i] = cov / var_y;
+        }
+    }
+    Ok(result)
+}
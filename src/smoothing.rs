@@ -0,0 +1,453 @@
+use crate::error::{QuantError, check_nonempty, check_window, check_len};
+
+// =============================================================================
+// Holt & Holt-Winters Exponential Smoothing
+// =============================================================================
+/// Check that a smoothing parameter lies in the open interval (0, 1)
+fn check_unit(name: &str, x: f64) -> Result<(), QuantError> {
+    if x > 0f64 && x < 1f64 {
+        Ok(())
+    } else {
+        Err(QuantError::InvalidParameter(format!("{} must be in (0, 1), got {}", name, x)))
+    }
+}
+
+/// Grid of smoothing parameters used by the fit helpers: 0.05, 0.10, ..., 0.95
+fn unit_grid() -> Vec<f64> {
+    (1 .. 20).map(|i| i as f64 / 20f64).collect()
+}
+
+/// Mean squared one-step-ahead error over the non-NAN forecasts
+fn one_step_mse(v: &[f64], fitted: &[f64]) -> f64 {
+    let (sse, count) = v.iter().zip(fitted.iter())
+        .filter(|(_, f)| !f.is_nan())
+        .fold((0f64, 0usize), |(s, c), (x, f)| (s + (x - f).powi(2), c + 1));
+    sse / count as f64
+}
+
+/// Holt's Linear Trend Smoothing
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `alpha` - f64 (level smoothing, in (0, 1))
+/// * `beta` - f64 (trend smoothing, in (0, 1))
+///
+/// # Returns
+/// * (Vec<f64>, Vec<f64>)
+///   * (level, trend)
+///   * level[t] = alpha * v[t] + (1 - alpha) * (level[t-1] + trend[t-1])
+///   * trend[t] = beta * (level[t] - level[t-1]) + (1 - beta) * trend[t-1]
+///   * Seeded with level[0] = v[0], trend[0] = v[1] - v[0] (0 for a single value)
+///   * The k-step-ahead forecast from t is level[t] + k * trend[t]
+///
+/// # Examples
+/// ```
+/// use quantauri::smoothing::holt;
+///
+/// fn main() {
+///     // A line is tracked exactly, unlike an EMA which lags it
+///     let v = (0 .. 50).map(|x| 2f64 * x as f64 + 1f64).collect::<Vec<f64>>();
+///     let (level, trend) = holt(&v, 0.3, 0.1);
+///     for t in 0 .. 50 {
+///         assert!((level[t] - v[t]).abs() < 1e-9);
+///         assert!((trend[t] - 2f64).abs() < 1e-9);
+///     }
+/// }
+/// ```
+pub fn holt(v: &[f64], alpha: f64, beta: f64) -> (Vec<f64>, Vec<f64>) {
+    holt_checked(v, alpha, beta).expect("holt: invalid input")
+}
+
+/// Holt's Linear Trend Smoothing (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+/// * `InvalidParameter` if `alpha` or `beta` is outside (0, 1)
+pub fn holt_checked(v: &[f64], alpha: f64, beta: f64) -> Result<(Vec<f64>, Vec<f64>), QuantError> {
+    check_nonempty(v)?;
+    check_unit("alpha", alpha)?;
+    check_unit("beta", beta)?;
+    let n = v.len();
+    let mut level = vec![0f64; n];
+    let mut trend = vec![0f64; n];
+    level[0] = v[0];
+    trend[0] = if n > 1 { v[1] - v[0] } else { 0f64 };
+    for t in 1 .. n {
+        level[t] = alpha * v[t] + (1f64 - alpha) * (level[t-1] + trend[t-1]);
+        trend[t] = beta * (level[t] - level[t-1]) + (1f64 - beta) * trend[t-1];
+    }
+    Ok((level, trend))
+}
+
+/// Fit Holt's smoothing parameters by grid search
+///
+/// # Arguments
+/// * `v` - &[f64]
+///
+/// # Returns
+/// * `Result<(f64, f64), QuantError>`
+///   * (alpha, beta) on the grid 0.05, 0.10, ..., 0.95 minimizing the mean squared
+///     one-step-ahead error `v[t] - (level[t-1] + trend[t-1])` for t >= 2
+///
+/// # Errors
+/// * `WindowTooLarge` if `v` has fewer than 3 values
+///
+/// # Examples
+/// ```
+/// use quantauri::smoothing::fit_holt;
+///
+/// fn main() {
+///     // A level shift without trend: fast level, slow trend
+///     let mut v = vec![0f64; 20];
+///     v.extend(vec![10f64; 20]);
+///     let (alpha, beta) = fit_holt(&v).unwrap();
+///     assert_eq!((alpha, beta), (0.95, 0.05));
+/// }
+/// ```
+pub fn fit_holt(v: &[f64]) -> Result<(f64, f64), QuantError> {
+    check_window(3, v.len())?;
+    let grid = unit_grid();
+    let mut best = (f64::INFINITY, grid[0], grid[0]);
+    for &alpha in grid.iter() {
+        for &beta in grid.iter() {
+            let (level, trend) = holt_checked(v, alpha, beta)?;
+            let mut fitted = vec![f64::NAN; v.len()];
+            for t in 2 .. v.len() {
+                fitted[t] = level[t-1] + trend[t-1];
+            }
+            let mse = one_step_mse(v, &fitted);
+            if mse < best.0 {
+                best = (mse, alpha, beta);
+            }
+        }
+    }
+    Ok((best.1, best.2))
+}
+
+/// Output of `holt_winters`
+///
+/// # Fields
+/// * `level`, `trend`, `season` - smoothed components
+/// * `fitted` - one-step-ahead forecasts level[t-1] + trend[t-1] + season[t-m] (NAN for the first season)
+/// * `season_len` - m
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoltWintersOutput {
+    pub level: Vec<f64>,
+    pub trend: Vec<f64>,
+    pub season: Vec<f64>,
+    pub fitted: Vec<f64>,
+    pub season_len: usize,
+}
+
+impl HoltWintersOutput {
+    /// n-step-ahead forecast from the last bar
+    ///
+    /// # Arguments
+    /// * `horizon` - usize
+    ///
+    /// # Returns
+    /// * Vec<f64> of length `horizon`
+    ///   * level[n-1] + k * trend[n-1] + season[n-1-m+k'] for k = 1 ..= horizon,
+    ///     where the seasonal index wraps within the last season
+    pub fn forecast(&self, horizon: usize) -> Vec<f64> {
+        let n = self.level.len();
+        let m = self.season_len;
+        (1 ..= horizon).map(|k| {
+            let s = self.season[n - m + (k - 1) % m];
+            self.level[n-1] + k as f64 * self.trend[n-1] + s
+        }).collect()
+    }
+}
+
+/// Holt-Winters Smoothing (additive seasonality)
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `alpha` - f64 (level smoothing, in (0, 1))
+/// * `beta` - f64 (trend smoothing, in (0, 1))
+/// * `gamma` - f64 (seasonal smoothing, in (0, 1))
+/// * `season_len` - usize (m, e.g. 24 for hourly data with a daily cycle)
+///
+/// # Returns
+/// * `HoltWintersOutput`
+///   * level[t] = alpha * (v[t] - season[t-m]) + (1 - alpha) * (level[t-1] + trend[t-1])
+///   * trend[t] = beta * (level[t] - level[t-1]) + (1 - beta) * trend[t-1]
+///   * season[t] = gamma * (v[t] - level[t]) + (1 - gamma) * season[t-m]
+///   * Initialized from the first two seasons: trend = (mean of season 2 - mean of season 1) / m,
+///     level = the line through the season 1 mean (at its midpoint) with that slope,
+///     season = v - level over season 1.
+///     The first m bars hold these initial values and the recursion starts at t = m.
+///
+/// # Examples
+/// ```
+/// use quantauri::smoothing::holt_winters;
+///
+/// fn main() {
+///     // Trend + period-4 pattern is reproduced exactly, and so is its continuation
+///     let pattern = [1f64, -2f64, 3f64, -2f64];
+///     let v = (0 .. 40).map(|t| 0.5 * t as f64 + pattern[t % 4]).collect::<Vec<f64>>();
+///     let hw = holt_winters(&v, 0.5, 0.3, 0.2, 4);
+///     for t in 4 .. 40 {
+///         assert!((hw.fitted[t] - v[t]).abs() < 1e-9);
+///     }
+///     let f = hw.forecast(6);
+///     for k in 0 .. 6 {
+///         let t = 40 + k;
+///         assert!((f[k] - (0.5 * t as f64 + pattern[t % 4])).abs() < 1e-9);
+///     }
+/// }
+/// ```
+pub fn holt_winters(v: &[f64], alpha: f64, beta: f64, gamma: f64, season_len: usize) -> HoltWintersOutput {
+    holt_winters_checked(v, alpha, beta, gamma, season_len).expect("holt_winters: invalid input")
+}
+
+/// Holt-Winters Smoothing (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `v` is empty
+/// * `InvalidPeriod` if `season_len` is zero
+/// * `WindowTooLarge` if `v` is shorter than two seasons
+/// * `InvalidParameter` if `alpha`, `beta` or `gamma` is outside (0, 1)
+pub fn holt_winters_checked(v: &[f64], alpha: f64, beta: f64, gamma: f64, season_len: usize) -> Result<HoltWintersOutput, QuantError> {
+    check_nonempty(v)?;
+    if season_len == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    check_window(2 * season_len, v.len())?;
+    check_unit("alpha", alpha)?;
+    check_unit("beta", beta)?;
+    check_unit("gamma", gamma)?;
+    let n = v.len();
+    let m = season_len;
+    let first = v[.. m].iter().sum::<f64>() / m as f64;
+    let second = v[m .. 2 * m].iter().sum::<f64>() / m as f64;
+
+    let slope = (second - first) / m as f64;
+    let mut level = vec![0f64; n];
+    let mut trend = vec![slope; n];
+    let mut season = vec![0f64; n];
+    let mut fitted = vec![f64::NAN; n];
+    for t in 0 .. m {
+        // The season-1 mean is the level at its midpoint
+        level[t] = first + slope * (t as f64 - (m - 1) as f64 / 2f64);
+        season[t] = v[t] - level[t];
+    }
+    for t in m .. n {
+        fitted[t] = level[t-1] + trend[t-1] + season[t-m];
+        level[t] = alpha * (v[t] - season[t-m]) + (1f64 - alpha) * (level[t-1] + trend[t-1]);
+        trend[t] = beta * (level[t] - level[t-1]) + (1f64 - beta) * trend[t-1];
+        season[t] = gamma * (v[t] - level[t]) + (1f64 - gamma) * season[t-m];
+    }
+    Ok(HoltWintersOutput { level, trend, season, fitted, season_len })
+}
+
+/// Fit Holt-Winters smoothing parameters by grid search
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `season_len` - usize
+///
+/// # Returns
+/// * `Result<(f64, f64, f64), QuantError>`
+///   * (alpha, beta, gamma) on the grid 0.05, 0.10, ..., 0.95 minimizing the mean squared
+///     one-step-ahead error of `fitted`
+///
+/// # Errors
+/// * Same as `holt_winters_checked`
+///
+/// # Examples
+/// ```
+/// use quantauri::smoothing::{fit_holt_winters, holt_winters};
+///
+/// fn main() {
+///     // Daily cycle on hourly bars with a level shift halfway
+///     let v = (0 .. 24 * 10).map(|t| {
+///         let shift = if t >= 120 { 5f64 } else { 0f64 };
+///         shift + (t as f64 * 2f64 * std::f64::consts::PI / 24f64).sin()
+///     }).collect::<Vec<f64>>();
+///     let (alpha, beta, gamma) = fit_holt_winters(&v, 24).unwrap();
+///     let hw = holt_winters(&v, alpha, beta, gamma, 24);
+///     // Recovers from the shift: the last day is forecast almost exactly
+///     for t in 24 * 9 .. 24 * 10 {
+///         assert!((hw.fitted[t] - v[t]).abs() < 0.1);
+///     }
+/// }
+/// ```
+pub fn fit_holt_winters(v: &[f64], season_len: usize) -> Result<(f64, f64, f64), QuantError> {
+    let grid = unit_grid();
+    let mut best = (f64::INFINITY, grid[0], grid[0], grid[0]);
+    for &alpha in grid.iter() {
+        for &beta in grid.iter() {
+            for &gamma in grid.iter() {
+                let hw = holt_winters_checked(v, alpha, beta, gamma, season_len)?;
+                let mse = one_step_mse(v, &hw.fitted);
+                if mse < best.0 {
+                    best = (mse, alpha, beta, gamma);
+                }
+            }
+        }
+    }
+    Ok((best.1, best.2, best.3))
+}
+
+// =============================================================================
+// LOWESS
+// =============================================================================
+/// Default number of robustifying iterations of `lowess_series`
+pub const LOWESS_ITERATIONS: usize = 3;
+
+/// Locally weighted linear fit at `x[i]` over the neighbourhood `[left, left + r)`
+fn lowess_fit(x: &[f64], y: &[f64], robust: &[f64], i: usize, left: usize, r: usize) -> f64 {
+    let right = left + r - 1;
+    let h = (x[i] - x[left]).max(x[right] - x[i]);
+    let mut sw = 0f64;
+    let mut sx = 0f64;
+    let mut sy = 0f64;
+    let mut w = vec![0f64; r];
+    for j in left ..= right {
+        let tricube = if h == 0f64 {
+            1f64
+        } else {
+            let d = (x[j] - x[i]).abs() / h;
+            if d < 1f64 { (1f64 - d.powi(3)).powi(3) } else { 0f64 }
+        };
+        w[j - left] = tricube * robust[j];
+        sw += w[j - left];
+        sx += w[j - left] * x[j];
+        sy += w[j - left] * y[j];
+    }
+    if sw == 0f64 {
+        return y[i];
+    }
+    let (mx, my) = (sx / sw, sy / sw);
+    let mut sxx = 0f64;
+    let mut sxy = 0f64;
+    for j in left ..= right {
+        sxx += w[j - left] * (x[j] - mx).powi(2);
+        sxy += w[j - left] * (x[j] - mx) * (y[j] - my);
+    }
+    // Degenerate spread in x: local weighted mean
+    if sxx <= 1e-12 * (mx * mx).max(1f64) * sw {
+        my
+    } else {
+        my + sxy / sxx * (x[i] - mx)
+    }
+}
+
+fn median(v: &mut [f64]) -> f64 {
+    v.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = v.len();
+    if n % 2 == 1 { v[n / 2] } else { (v[n / 2 - 1] + v[n / 2]) / 2f64 }
+}
+
+/// LOWESS (locally weighted scatterplot smoothing)
+///
+/// # Arguments
+/// * `x` - &[f64] (ascending)
+/// * `y` - &[f64]
+/// * `frac` - f64 in (0, 1] (share of the points in each local fit)
+/// * `iterations` - usize (robustifying iterations, 0 for a plain local fit)
+///
+/// # Returns
+/// * Vec<f64>
+///   * Fitted value at each `x[i]` from a weighted linear regression on its
+///     r = max(ceil(frac * n), 2) nearest neighbours
+///   * Tricube weights (1 - (d / h)^3)^3, h = distance to the farthest neighbour
+///   * Each robustifying iteration multiplies the weights by the bisquare
+///     (1 - (e / 6s)^2)^2 of the residuals e, s = median |e|
+///     (stops early when s is negligible against the mean |e|)
+///
+/// # Description
+/// Costs O(n * r) = O(frac * n^2) per pass, with `iterations + 1` passes.
+/// The neighbourhood is found incrementally (it only slides right as `x` grows),
+/// so no pass adds a search cost on top.
+///
+/// # Examples
+/// ```
+/// use quantauri::smoothing::lowess;
+///
+/// fn main() {
+///     // A line is reproduced exactly
+///     let x = (0 .. 30).map(|i| i as f64 * 0.5).collect::<Vec<f64>>();
+///     let y = x.iter().map(|&t| 3f64 * t - 2f64).collect::<Vec<f64>>();
+///     let fit = lowess(&x, &y, 0.3, 0);
+///     assert!(fit.iter().zip(y.iter()).all(|(a, b)| (a - b).abs() < 1e-9));
+///
+///     // On a noisy line, a spike pulls the plain fit while the robust fit ignores it
+///     let mut y = (0 .. 30).map(|i| y[i] + 0.1 * (i as f64 * 1.7).sin()).collect::<Vec<f64>>();
+///     y[15] += 100f64;
+///     let plain = lowess(&x, &y, 0.5, 0);
+///     let robust = lowess(&x, &y, 0.5, 3);
+///     let line = 3f64 * x[15] - 2f64;
+///     assert!((plain[15] - line).abs() > 1f64);
+///     assert!((robust[15] - line).abs() < 0.1);
+/// }
+/// ```
+pub fn lowess(x: &[f64], y: &[f64], frac: f64, iterations: usize) -> Vec<f64> {
+    lowess_checked(x, y, frac, iterations).expect("lowess: invalid input")
+}
+
+/// LOWESS (fallible)
+///
+/// # Errors
+/// * `EmptyInput` if `x` is empty
+/// * `LengthMismatch` if `x` and `y` differ in length
+/// * `InvalidParameter` if `frac` is not in (0, 1], `x` is not ascending, or an input is NAN
+pub fn lowess_checked(x: &[f64], y: &[f64], frac: f64, iterations: usize) -> Result<Vec<f64>, QuantError> {
+    check_nonempty(x)?;
+    check_len(x.len(), y.len())?;
+    if !(frac > 0f64 && frac <= 1f64) {
+        return Err(QuantError::InvalidParameter(format!("frac {} is not in (0, 1]", frac)));
+    }
+    if x.iter().chain(y.iter()).any(|v| v.is_nan()) {
+        return Err(QuantError::InvalidParameter("lowess input contains NAN".to_string()));
+    }
+    if x.windows(2).any(|w| w[1] < w[0]) {
+        return Err(QuantError::InvalidParameter("lowess x must be ascending".to_string()));
+    }
+    let n = x.len();
+    let r = ((frac * n as f64).ceil() as usize).clamp(2.min(n), n);
+    let mut robust = vec![1f64; n];
+    let mut fit = vec![0f64; n];
+    for pass in 0 ..= iterations {
+        let mut left = 0usize;
+        for i in 0 .. n {
+            while left + r < n && x[i] - x[left] > x[left + r] - x[i] {
+                left += 1;
+            }
+            fit[i] = lowess_fit(x, y, &robust, i, left, r);
+        }
+        if pass == iterations {
+            break;
+        }
+        let residuals = (0 .. n).map(|i| y[i] - fit[i]).collect::<Vec<f64>>();
+        let mut abs_residuals = residuals.iter().map(|e| e.abs()).collect::<Vec<f64>>();
+        let mean_abs = abs_residuals.iter().sum::<f64>() / n as f64;
+        let s = median(&mut abs_residuals);
+        // Residuals are effectively zero: the fit is already exact for most points
+        if s <= 1e-7 * mean_abs || s == 0f64 {
+            break;
+        }
+        for i in 0 .. n {
+            let u = residuals[i] / (6f64 * s);
+            robust[i] = if u.abs() < 1f64 { (1f64 - u * u).powi(2) } else { 0f64 };
+        }
+    }
+    Ok(fit)
+}
+
+/// LOWESS for evenly spaced bars
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `frac` - f64 in (0, 1]
+///
+/// # Returns
+/// * Vec<f64>
+///   * `lowess(0, 1, .., n-1, v, frac, LOWESS_ITERATIONS)`
+///   * A fair-value curve to measure Bollinger-style deviations against;
+///     it uses future bars, so it is for analysis rather than live signals
+pub fn lowess_series(v: &[f64], frac: f64) -> Vec<f64> {
+    let x = (0 .. v.len()).map(|i| i as f64).collect::<Vec<f64>>();
+    lowess(&x, v, frac, LOWESS_ITERATIONS)
+}
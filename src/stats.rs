@@ -0,0 +1,681 @@
+use peroxide::fuga::{inc_gamma, lm, matrix, Shape};
+use crate::rolling::{rolling_max, rolling_quantile_checked};
+use crate::error::QuantError;
+use crate::price::cum_returns;
+
+// =============================================================================
+// Drawdown
+// =============================================================================
+/// Drawdown Series
+///
+/// # Arguments
+/// * `equity` - &[f64]
+///
+/// # Returns
+/// * Vec<f64>
+///   * (running_peak - equity) / running_peak at each bar, in [0, 1] (0 at a new high)
+///   * 0 while the running peak is not positive
+///
+/// # Examples
+/// ```
+/// use quantauri::stats::drawdown;
+///
+/// fn main() {
+///     let equity = vec![100f64, 120f64, 90f64, 130f64, 117f64];
+///     assert_eq!(drawdown(&equity), vec![0f64, 0f64, 0.25, 0f64, 0.1]);
+/// }
+/// ```
+pub fn drawdown(equity: &[f64]) -> Vec<f64> {
+    let mut peak = f64::NEG_INFINITY;
+    equity.iter().map(|&e| {
+        peak = peak.max(e);
+        if peak > 0f64 { (peak - e) / peak } else { 0f64 }
+    }).collect()
+}
+
+/// Summary of the deepest drawdown of an equity curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawdownStats {
+    /// Largest fractional decline from a running peak, in [0, 1]
+    pub depth: f64,
+    /// Index of the peak the decline started from (most recent bar at that level)
+    pub peak: usize,
+    /// Index of the lowest point of the decline
+    pub trough: usize,
+    /// First index after the trough where equity regains the peak, `None` if
+    /// the curve ends underwater
+    pub recovery: Option<usize>,
+}
+
+/// Maximum Drawdown
+///
+/// # Arguments
+/// * `equity` - &[f64]
+///
+/// # Returns
+/// * DrawdownStats
+///   * The first drawdown reaching the maximum depth
+///   * depth 0 with peak = trough = 0 and recovery `None` for an empty or never-declining curve
+///
+/// # Examples
+/// ```
+/// use quantauri::stats::{max_drawdown, DrawdownStats};
+///
+/// fn main() {
+///     // Two drawdowns: 120 -> 90 (25%, recovered at bar 4), 150 -> 105 (30%, still underwater)
+///     let equity = vec![100f64, 120f64, 100f64, 90f64, 125f64, 150f64, 120f64, 105f64, 140f64];
+///     let dd = max_drawdown(&equity);
+///     assert!((dd.depth - 0.3).abs() < 1e-12);
+///     assert_eq!((dd.peak, dd.trough, dd.recovery), (5, 7, None));
+///
+///     let dd = max_drawdown(&equity[.. 6]);
+///     assert_eq!(dd, DrawdownStats { depth: 0.25, peak: 1, trough: 3, recovery: Some(4) });
+///
+///     assert_eq!(max_drawdown(&[1f64, 2f64]).depth, 0f64);
+/// }
+/// ```
+pub fn max_drawdown(equity: &[f64]) -> DrawdownStats {
+    let mut stats = DrawdownStats { depth: 0f64, peak: 0, trough: 0, recovery: None };
+    let mut peak = f64::NEG_INFINITY;
+    let mut peak_index = 0usize;
+    for (i, &e) in equity.iter().enumerate() {
+        if e >= peak {
+            peak = e;
+            peak_index = i;
+        }
+        if peak > 0f64 {
+            let dd = (peak - e) / peak;
+            if dd > stats.depth {
+                stats = DrawdownStats { depth: dd, peak: peak_index, trough: i, recovery: None };
+            }
+        }
+    }
+    if stats.depth > 0f64 {
+        let level = equity[stats.peak];
+        stats.recovery = (stats.trough + 1 .. equity.len()).find(|&i| equity[i] >= level);
+    }
+    stats
+}
+
+// =============================================================================
+// Ulcer Index
+// =============================================================================
+/// Ulcer Index
+///
+/// # Arguments
+/// * `v` - &[f64] (prices or equity)
+/// * `window` - usize
+///
+/// # Returns
+/// * Vec<f64>
+///   * dd[k] = 100 * (v[k] - peak[k]) / peak[k], peak[k] = max(v[k+1-window ..= k])
+///   * dd[k] = 0 at the peak itself; NAN below a peak of 0 (no percentage drawdown)
+///   * ui[i] = sqrt(mean(dd[i+1-window ..= i]^2))
+///   * Each drawdown is measured against the peak of its own trailing window (not the
+///     peak of the averaging window), so the last value looks back `2 * window - 1` bars.
+///     At the start the peak window is truncated (running max since bar 0).
+///   * First `window - 1` values are NAN, NAN whenever the averaging window contains NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::stats::ulcer_index;
+///
+/// fn main() {
+///     // Monotonically rising: never below its peak
+///     let v = (0 .. 30).map(|x| 100f64 + x as f64).collect::<Vec<f64>>();
+///     let ui = ulcer_index(&v, 14);
+///     assert!(ui[12].is_nan());
+///     assert!(ui[13 ..].iter().all(|&x| x == 0f64));
+///
+///     // One 10% drawdown bar in a window of 4
+///     let v = vec![100f64, 100f64, 90f64, 100f64, 100f64];
+///     let ui = ulcer_index(&v, 4);
+///     assert!((ui[3] - (100f64 / 4f64).sqrt()).abs() < 1e-12);
+///
+///     // A series sitting at zero: no inf from a zero peak
+///     let v = vec![0f64, 0f64, 0f64, -1f64, 0f64];
+///     let ui = ulcer_index(&v, 2);
+///     assert_eq!(ui[2], 0f64);
+///     assert!(ui[3].is_nan() && ui[4].is_nan());
+/// }
+/// ```
+pub fn ulcer_index(v: &[f64], window: usize) -> Vec<f64> {
+    let n = v.len();
+    let mut result = vec![f64::NAN; n];
+    if window == 0 {
+        return result;
+    }
+    let peak = rolling_max(v, window);
+    let sq = (0 .. n).map(|k| {
+        if v[k] == peak[k] {
+            0f64
+        } else if peak[k] == 0f64 {
+            f64::NAN
+        } else {
+            (100f64 * (v[k] - peak[k]) / peak[k]).powi(2)
+        }
+    }).collect::<Vec<f64>>();
+    for i in window - 1 .. n {
+        result[i] = (sq[i + 1 - window ..= i].iter().sum::<f64>() / window as f64).sqrt();
+    }
+    result
+}
+
+/// Ulcer Performance Index (Martin ratio)
+///
+/// # Arguments
+/// * `returns` - &[f64] (per-period simple returns)
+/// * `window` - usize
+/// * `risk_free` - f64 (per-period rate, same units as `returns`)
+///
+/// # Returns
+/// * Vec<f64>
+///   * 100 * (mean(returns over the window) - risk_free) / ulcer_index(equity, window),
+///     equity = `cum_returns(returns, 1)`: excess return in percent per unit of ulcer
+///   * No drawdown in the window (UI = 0): `f64::INFINITY` if the excess return is positive,
+///     NAN otherwise (as `sortino_ratio`)
+///   * Warm-up as `ulcer_index`, NAN whenever the window contains a NAN return
+///
+/// # Examples
+/// ```
+/// use quantauri::stats::{ulcer_performance_index, ulcer_index};
+/// use quantauri::price::cum_returns;
+///
+/// fn main() {
+///     let r = vec![0.01, -0.02, 0.015, 0.01, -0.005, 0.02];
+///     let upi = ulcer_performance_index(&r, 4, 0.001);
+///     let ui = ulcer_index(&cum_returns(&r, 1f64), 4);
+///     let mean = r[2 ..= 5].iter().sum::<f64>() / 4f64;
+///     assert!((upi[5] - 100f64 * (mean - 0.001) / ui[5]).abs() < 1e-12);
+///
+///     // Steady gains: no ulcer at all
+///     let upi = ulcer_performance_index(&[0.01; 10], 4, 0f64);
+///     assert_eq!(upi[9], f64::INFINITY);
+/// }
+/// ```
+pub fn ulcer_performance_index(returns: &[f64], window: usize, risk_free: f64) -> Vec<f64> {
+    let n = returns.len();
+    let mut result = vec![f64::NAN; n];
+    if window == 0 {
+        return result;
+    }
+    let ui = ulcer_index(&cum_returns(returns, 1f64), window);
+    for i in window - 1 .. n {
+        let excess = 100f64 * (returns[i + 1 - window ..= i].iter().sum::<f64>() / window as f64 - risk_free);
+        result[i] = if excess.is_nan() {
+            f64::NAN
+        } else if ui[i] == 0f64 {
+            if excess > 0f64 { f64::INFINITY } else { f64::NAN }
+        } else {
+            excess / ui[i]
+        };
+    }
+    result
+}
+
+// =============================================================================
+// Value at Risk
+// =============================================================================
+/// Minimum number of valid returns in a window for `rolling_var` / `rolling_cvar`
+pub const VAR_MIN_OBS: usize = 5;
+
+/// Rolling historical Value at Risk
+///
+/// # Arguments
+/// * `returns` - &[f64] (per-period returns, losses are negative)
+/// * `period` - usize
+/// * `confidence` - f64 in (0, 1) (e.g. 0.95)
+///
+/// # Returns
+/// * Vec<f64>
+///   * `(1 - confidence)` quantile of the trailing window (`rolling_quantile`, linear interpolation)
+///   * Reported as a return, not a loss: a 95% VaR of -0.02 means a 5% chance of losing 2% or more
+///   * NAN until the window is full, and whenever it holds fewer than `VAR_MIN_OBS` valid returns
+///     (so every value is NAN if `period < VAR_MIN_OBS`)
+///
+/// # Examples
+/// ```
+/// use quantauri::stats::{rolling_var, rolling_cvar};
+///
+/// fn main() {
+///     // -10%, -9%, ..., 9%
+///     let r = (0 .. 20).map(|k| (k as f64 - 10f64) / 100f64).collect::<Vec<f64>>();
+///     let var = rolling_var(&r, 20, 0.9);
+///     assert!(var[18].is_nan());
+///     // h = 19 * 0.1 = 1.9: between -9% and -8%
+///     assert!((var[19] + 0.081).abs() < 1e-12);
+///
+///     // Mean of the returns at or below the VaR: -10% and -9%
+///     let cvar = rolling_cvar(&r, 20, 0.9);
+///     assert!((cvar[19] + 0.095).abs() < 1e-12);
+///
+///     // Too few observations
+///     assert!(rolling_var(&r, 3, 0.9).iter().all(|x| x.is_nan()));
+/// }
+/// ```
+pub fn rolling_var(returns: &[f64], period: usize, confidence: f64) -> Vec<f64> {
+    rolling_var_checked(returns, period, confidence).expect("rolling_var: invalid input")
+}
+
+/// Rolling historical Value at Risk (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `period == 0`
+/// * `InvalidParameter` if `confidence` is not in (0, 1)
+pub fn rolling_var_checked(returns: &[f64], period: usize, confidence: f64) -> Result<Vec<f64>, QuantError> {
+    if period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    if confidence.is_nan() || confidence <= 0f64 || confidence >= 1f64 {
+        return Err(QuantError::InvalidParameter(format!("confidence {} is not in (0, 1)", confidence)));
+    }
+    let mut result = rolling_quantile_checked(returns, period, 1f64 - confidence)?;
+    let mut valid = 0usize;
+    for i in 0 .. returns.len() {
+        if !returns[i].is_nan() {
+            valid += 1;
+        }
+        if i >= period && !returns[i - period].is_nan() {
+            valid -= 1;
+        }
+        if i + 1 < period || valid < VAR_MIN_OBS {
+            result[i] = f64::NAN;
+        }
+    }
+    Ok(result)
+}
+
+/// Rolling historical Conditional Value at Risk (expected shortfall)
+///
+/// # Arguments
+/// * `returns` - &[f64] (per-period returns, losses are negative)
+/// * `period` - usize
+/// * `confidence` - f64 in (0, 1)
+///
+/// # Returns
+/// * Vec<f64>
+///   * Mean of the valid returns in the trailing window at or below `rolling_var`
+///   * Same sign convention and NAN rules as `rolling_var`, and never above it
+pub fn rolling_cvar(returns: &[f64], period: usize, confidence: f64) -> Vec<f64> {
+    rolling_cvar_checked(returns, period, confidence).expect("rolling_cvar: invalid input")
+}
+
+/// Rolling historical Conditional Value at Risk (fallible)
+///
+/// # Errors
+/// * Errors of `rolling_var_checked`
+pub fn rolling_cvar_checked(returns: &[f64], period: usize, confidence: f64) -> Result<Vec<f64>, QuantError> {
+    let var = rolling_var_checked(returns, period, confidence)?;
+    Ok((0 .. returns.len()).map(|i| {
+        if var[i].is_nan() {
+            return f64::NAN;
+        }
+        let (sum, count) = returns[i + 1 - period ..= i].iter()
+            .filter(|&&x| x <= var[i])
+            .fold((0f64, 0usize), |(s, c), &x| (s + x, c + 1));
+        sum / count as f64
+    }).collect())
+}
+
+// =============================================================================
+// Risk-adjusted Performance
+// =============================================================================
+/// Sortino Ratio
+///
+/// # Arguments
+/// * `returns` - &[f64] (per-period simple returns)
+/// * `target` - f64 (minimum acceptable return per period)
+/// * `periods_per_year` - f64 (e.g. 252 for daily bars)
+///
+/// # Returns
+/// * f64
+///   * mean(r - target) / downside_deviation * sqrt(periods_per_year)
+///   * downside_deviation = sqrt(mean(min(r - target, 0)^2)) over all periods
+///   * No downside returns: `f64::INFINITY` if the mean excess return is positive,
+///     NAN if it is zero (every return equals `target`)
+///   * NAN for empty input
+///
+/// # Examples
+/// ```
+/// use quantauri::stats::sortino_ratio;
+///
+/// fn main() {
+///     let returns = vec![0.02, -0.01, 0.03, -0.02];
+///     // mean 0.005, downside deviation sqrt((0.01^2 + 0.02^2) / 4)
+///     let s = sortino_ratio(&returns, 0f64, 1f64);
+///     assert!((s - 0.005 / (0.0005f64 / 4f64).sqrt()).abs() < 1e-12);
+///
+///     assert_eq!(sortino_ratio(&[0.01, 0.02], 0f64, 252f64), f64::INFINITY);
+/// }
+/// ```
+pub fn sortino_ratio(returns: &[f64], target: f64, periods_per_year: f64) -> f64 {
+    if returns.is_empty() {
+        return f64::NAN;
+    }
+    let n = returns.len() as f64;
+    let mean_excess = returns.iter().map(|r| r - target).sum::<f64>() / n;
+    let downside = (returns.iter().map(|r| (r - target).min(0f64).powi(2)).sum::<f64>() / n).sqrt();
+    if downside == 0f64 {
+        return if mean_excess > 0f64 { f64::INFINITY } else { f64::NAN };
+    }
+    mean_excess / downside * periods_per_year.sqrt()
+}
+
+/// Calmar Ratio
+///
+/// # Arguments
+/// * `equity` - &[f64]
+/// * `periods_per_year` - f64
+///
+/// # Returns
+/// * f64
+///   * CAGR / max_drawdown(equity).depth
+///   * CAGR = (equity[n-1] / equity[0])^(periods_per_year / (n - 1)) - 1
+///   * No drawdown: `f64::INFINITY` if CAGR is positive, NAN otherwise
+///   * NAN for fewer than 2 points or a non-positive starting equity
+///
+/// # Examples
+/// ```
+/// use quantauri::stats::calmar_ratio;
+///
+/// fn main() {
+///     // One year of 2 periods: 100 -> 80 -> 120
+///     let equity = vec![100f64, 80f64, 120f64];
+///     let c = calmar_ratio(&equity, 2f64);
+///     assert!((c - 0.2 / 0.2).abs() < 1e-12);
+/// }
+/// ```
+pub fn calmar_ratio(equity: &[f64], periods_per_year: f64) -> f64 {
+    let n = equity.len();
+    if n < 2 || equity[0] <= 0f64 {
+        return f64::NAN;
+    }
+    let years = (n - 1) as f64 / periods_per_year;
+    let cagr = (equity[n-1] / equity[0]).powf(1f64 / years) - 1f64;
+    let mdd = max_drawdown(equity).depth;
+    if mdd == 0f64 {
+        return if cagr > 0f64 { f64::INFINITY } else { f64::NAN };
+    }
+    cagr / mdd
+}
+
+// =============================================================================
+// Serial Correlation
+// =============================================================================
+/// Autocorrelation Function
+///
+/// # Arguments
+/// * `v` - &[f64] (e.g. a return series)
+/// * `max_lag` - usize
+///
+/// # Returns
+/// * Vec<f64> of length `max_lag + 1`
+///   * acf[k] = sum_t (v[t] - m)(v[t-k] - m) / sum_t (v[t] - m)^2, with m = mean(v)
+///   * acf[0] = 1, NAN for lags >= v.len() or a constant (or empty) series
+///
+/// # Examples
+/// ```
+/// use quantauri::stats::acf;
+///
+/// fn main() {
+///     let r = acf(&[1f64, 2f64, 3f64, 4f64, 5f64], 2);
+///     assert_eq!(r[0], 1f64);
+///     assert!((r[1] - 0.4).abs() < 1e-12);
+///     assert!((r[2] + 0.1).abs() < 1e-12);
+/// }
+/// ```
+pub fn acf(v: &[f64], max_lag: usize) -> Vec<f64> {
+    let n = v.len();
+    let mut result = vec![f64::NAN; max_lag + 1];
+    if n == 0 {
+        return result;
+    }
+    let mean = v.iter().sum::<f64>() / n as f64;
+    let d = v.iter().map(|x| x - mean).collect::<Vec<f64>>();
+    let denom = d.iter().map(|x| x * x).sum::<f64>();
+    if denom == 0f64 {
+        return result;
+    }
+    for k in 0 .. (max_lag + 1).min(n) {
+        result[k] = (k .. n).map(|t| d[t] * d[t-k]).sum::<f64>() / denom;
+    }
+    result
+}
+
+/// Partial Autocorrelation Function
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `max_lag` - usize
+///
+/// # Returns
+/// * Vec<f64> of length `max_lag + 1`
+///   * Durbin-Levinson recursion on `acf(v, max_lag)`, pacf[0] = 1
+///   * For an AR(p) process pacf[k] is close to 0 for k > p
+///   * NAN where `acf` is NAN (and for all later lags)
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::stats::{acf, pacf};
+///
+/// fn main() {
+///     // Known values: pacf[2] = (r2 - r1^2) / (1 - r1^2)
+///     let p = pacf(&[1f64, 2f64, 3f64, 4f64, 5f64], 2);
+///     assert!((p[1] - 0.4).abs() < 1e-12);
+///     assert!((p[2] - (-0.1 - 0.16) / 0.84).abs() < 1e-12);
+///
+///     // AR(1) with phi = 0.6
+///     let n = 20_000;
+///     let e = Normal(0, 1).sample(n);
+///     let mut x = vec![0f64; n];
+///     for i in 1 .. n {
+///         x[i] = 0.6 * x[i-1] + e[i];
+///     }
+///     let r = acf(&x, 5);
+///     let p = pacf(&x, 5);
+///     for k in 1 ..= 5 {
+///         assert!((r[k] - 0.6f64.powi(k as i32)).abs() < 0.05);
+///     }
+///     assert!((p[1] - 0.6).abs() < 0.05);
+///     assert!(p[2 ..].iter().all(|x| x.abs() < 0.05));
+/// }
+/// ```
+pub fn pacf(v: &[f64], max_lag: usize) -> Vec<f64> {
+    let r = acf(v, max_lag);
+    let mut result = vec![f64::NAN; max_lag + 1];
+    result[0] = r[0];
+    if max_lag == 0 || r[1].is_nan() {
+        return result;
+    }
+    // phi[j] holds phi_{k, j} for the current order k
+    let mut phi = vec![0f64; max_lag + 1];
+    phi[1] = r[1];
+    result[1] = r[1];
+    for k in 2 ..= max_lag {
+        if r[k].is_nan() {
+            break;
+        }
+        let num = r[k] - (1 .. k).map(|j| phi[j] * r[k-j]).sum::<f64>();
+        let den = 1f64 - (1 .. k).map(|j| phi[j] * r[j]).sum::<f64>();
+        let phi_kk = num / den;
+        let prev = phi.clone();
+        for j in 1 .. k {
+            phi[j] = prev[j] - phi_kk * prev[k-j];
+        }
+        phi[k] = phi_kk;
+        result[k] = phi_kk;
+    }
+    result
+}
+
+/// Ljung-Box Test
+///
+/// # Arguments
+/// * `v` - &[f64]
+/// * `lags` - usize (number of autocorrelations tested)
+///
+/// # Returns
+/// * (f64, f64)
+///   * (Q, p-value)
+///   * Q = n (n + 2) sum_{k=1}^{lags} acf[k]^2 / (n - k)
+///   * p-value = P(chi^2(lags) > Q); small values reject "no autocorrelation"
+///   * (NAN, NAN) if `lags == 0`, `lags >= v.len()` or the series is constant
+///
+/// # Examples
+/// ```
+/// use quantauri::stats::ljung_box;
+///
+/// fn main() {
+///     let (q, p) = ljung_box(&[1f64, 2f64, 3f64, 4f64, 5f64], 2);
+///     let expected_q = 5f64 * 7f64 * (0.16 / 4f64 + 0.01 / 3f64);
+///     assert!((q - expected_q).abs() < 1e-12);
+///     // chi^2 with 2 degrees of freedom: P(X > q) = exp(-q / 2)
+///     assert!((p - (-expected_q / 2f64).exp()).abs() < 1e-9);
+/// }
+/// ```
+pub fn ljung_box(v: &[f64], lags: usize) -> (f64, f64) {
+    let n = v.len();
+    if lags == 0 || lags >= n {
+        return (f64::NAN, f64::NAN);
+    }
+    let r = acf(v, lags);
+    let nf = n as f64;
+    let q = nf * (nf + 2f64) * (1 ..= lags).map(|k| r[k].powi(2) / (nf - k as f64)).sum::<f64>();
+    if q.is_nan() {
+        return (f64::NAN, f64::NAN);
+    }
+    let p = 1f64 - inc_gamma(lags as f64 / 2f64, q / 2f64);
+    (q, p)
+}
+
+// =============================================================================
+// Long Memory
+// =============================================================================
+/// Variance-of-lagged-differences Hurst estimate of `v` over sorted, distinct,
+/// positive `lags` (all smaller than `v.len()`)
+///
+/// Least-squares slope of ln(std(v[j] - v[j-lag])) against ln(lag); NAN if `v`
+/// has a NAN or fewer than two lags have a positive spread.
+pub(crate) fn lagged_difference_hurst(v: &[f64], lags: &[usize]) -> f64 {
+    if v.iter().any(|x| x.is_nan()) {
+        return f64::NAN;
+    }
+    let n = v.len();
+    let mut xs = Vec::with_capacity(lags.len());
+    let mut ys = Vec::with_capacity(lags.len());
+    for &lag in lags.iter() {
+        let m = (n - lag) as f64;
+        let diffs = (lag .. n).map(|j| v[j] - v[j - lag]);
+        let mean = diffs.clone().sum::<f64>() / m;
+        let var = diffs.map(|d| (d - mean).powi(2)).sum::<f64>() / m;
+        if var > 0f64 {
+            xs.push((lag as f64).ln());
+            ys.push(0.5 * var.ln());
+        }
+    }
+    if xs.len() < 2 {
+        return f64::NAN;
+    }
+    let k = xs.len() as f64;
+    let x_bar = xs.iter().sum::<f64>() / k;
+    let y_bar = ys.iter().sum::<f64>() / k;
+    let sxy = (0 .. xs.len()).map(|j| (xs[j] - x_bar) * (ys[j] - y_bar)).sum::<f64>();
+    let sxx = xs.iter().map(|x| (x - x_bar).powi(2)).sum::<f64>();
+    sxy / sxx
+}
+
+/// Hurst Exponent
+///
+/// # Arguments
+/// * `v` - &[f64] (price or log-price levels, not returns)
+/// * `min_lag` - usize (at least 1)
+/// * `max_lag` - usize
+///
+/// # Returns
+/// * f64
+///   * Variance-of-lagged-differences estimator over every lag in `min_lag ..= max_lag`:
+///     std(v[j] - v[j-lag]) scales as lag^H, H is the slope of the log-log least-squares fit
+///   * H > 0.5: trending, H < 0.5: mean-reverting, H = 0.5: random walk
+///   * NAN if `min_lag == 0`, `min_lag >= max_lag`, `v.len() <= max_lag`, or `v` has a NAN
+///   * Same estimator as `rolling::rolling_hurst` applied to the whole series
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::stats::hurst_exponent;
+///
+/// fn main() {
+///     let n = 10_000;
+///     let e = Normal(0, 1).sample(n);
+///     let mut walk = vec![0f64; n];
+///     let mut inc = vec![0f64; n];
+///     let mut trend = vec![0f64; n];
+///     for i in 1 .. n {
+///         walk[i] = walk[i-1] + e[i];
+///         inc[i] = 0.8 * inc[i-1] + e[i];
+///         trend[i] = trend[i-1] + inc[i];
+///     }
+///     assert!((hurst_exponent(&walk, 2, 20) - 0.5).abs() < 0.05);
+///     assert!(hurst_exponent(&trend, 2, 20) > 0.6);
+///     assert!(hurst_exponent(&walk[.. 20], 2, 20).is_nan());
+/// }
+/// ```
+pub fn hurst_exponent(v: &[f64], min_lag: usize, max_lag: usize) -> f64 {
+    if min_lag == 0 || min_lag >= max_lag || v.len() <= max_lag {
+        return f64::NAN;
+    }
+    let lags = (min_lag ..= max_lag).collect::<Vec<usize>>();
+    lagged_difference_hurst(v, &lags)
+}
+
+/// Mean-reversion Half-life
+///
+/// # Arguments
+/// * `spread` - &[f64] (e.g. the residual of a pair)
+///
+/// # Returns
+/// * f64
+///   * AR(1) fit: spread[t] - spread[t-1] = a + lambda * spread[t-1] by least squares
+///     (peroxide `lm`), half-life = -ln(2) / lambda in bars
+///   * NAN when lambda >= 0 (no mean reversion), for fewer than 3 points,
+///     or if the spread has a NAN
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::stats::half_life;
+///
+/// fn main() {
+///     // AR(1) with phi = 0.9: lambda = -0.1, half-life = ln 2 / 0.1
+///     let n = 10_000;
+///     let e = Normal(0, 1).sample(n);
+///     let mut x = vec![0f64; n];
+///     for i in 1 .. n {
+///         x[i] = 0.9 * x[i-1] + e[i];
+///     }
+///     let hl = half_life(&x);
+///     let expected = 2f64.ln() / 0.1;
+///     assert!((hl - expected).abs() < 0.2 * expected);
+///
+///     // Explosive series: no mean reversion
+///     let grow = (0 .. 50).map(|i| 1.01f64.powi(i)).collect::<Vec<f64>>();
+///     assert!(half_life(&grow).is_nan());
+/// }
+/// ```
+pub fn half_life(spread: &[f64]) -> f64 {
+    let n = spread.len();
+    if n < 3 || spread.iter().any(|x| x.is_nan()) {
+        return f64::NAN;
+    }
+    let lagged = spread[.. n - 1].to_vec();
+    let delta = (1 .. n).map(|i| spread[i] - spread[i-1]).collect::<Vec<f64>>();
+    let x = matrix(lagged, n - 1, 1, Shape::Col);
+    let y = matrix(delta, n - 1, 1, Shape::Col);
+    let lambda = lm(&x, &y)[(1, 0)];
+    if lambda.is_finite() && lambda < 0f64 {
+        -2f64.ln() / lambda
+    } else {
+        f64::NAN
+    }
+}
@@ -0,0 +1,356 @@
+use crate::error::{QuantError, check_len};
+use crate::stats::drawdown;
+
+// =============================================================================
+// Positions
+// =============================================================================
+/// Target position of a strategy at a bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Position {
+    Long,
+    Short,
+    #[default]
+    Flat,
+}
+
+impl Position {
+    /// Exposure sign: +1 long, -1 short, 0 flat
+    pub fn sign(&self) -> f64 {
+        match self {
+            Position::Long => 1f64,
+            Position::Short => -1f64,
+            Position::Flat => 0f64,
+        }
+    }
+}
+
+// =============================================================================
+// Equity Curve Filter
+// =============================================================================
+/// Condition for resuming after `equity_filter` pauses a strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reentry {
+    /// Resume once the equity curve makes a new high (drawdown back to 0)
+    NewHigh,
+    /// Resume once the drawdown shrinks to this level or below (must be below `max_dd`)
+    Drawdown(f64),
+}
+
+/// Equity Curve Filter
+///
+/// # Arguments
+/// * `equity` - &[f64] (equity curve of the *unfiltered* strategy)
+/// * `signals` - &[Position]
+/// * `max_dd` - f64 in (0, 1] (fractional drawdown, as `stats::drawdown`)
+/// * `reentry` - Reentry
+///
+/// # Returns
+/// * Vec<Position>
+///   * `signals[i]` while trading, `Flat` while paused
+///   * Pauses at the first bar whose drawdown exceeds `max_dd`,
+///     resumes at the first later bar meeting `reentry`
+///   * The decision at bar i uses `equity[..= i]`, the same alignment as `signals`
+///
+/// # Description
+/// Pass the equity of the strategy run without the filter (a shadow curve).
+/// The filtered strategy's own equity is frozen while flat, so it could never recover.
+///
+/// # Examples
+/// ```
+/// use quantauri::strategy::{equity_filter, Position, Reentry};
+///
+/// fn main() {
+///     let equity = vec![100f64, 110f64, 95f64, 90f64, 100f64, 105f64, 112f64, 111f64];
+///     let signals = vec![Position::Long; 8];
+///     // drawdown: 0, 0, 13.6%, 18.2%, 9.1%, 4.5%, 0, 0.9%
+///
+///     let f = equity_filter(&equity, &signals, 0.15, Reentry::NewHigh);
+///     use Position::*;
+///     assert_eq!(f, vec![Long, Long, Long, Flat, Flat, Flat, Long, Long]);
+///
+///     let f = equity_filter(&equity, &signals, 0.15, Reentry::Drawdown(0.1));
+///     assert_eq!(f, vec![Long, Long, Long, Flat, Long, Long, Long, Long]);
+/// }
+/// ```
+pub fn equity_filter(equity: &[f64], signals: &[Position], max_dd: f64, reentry: Reentry) -> Vec<Position> {
+    equity_filter_checked(equity, signals, max_dd, reentry).expect("equity_filter: invalid input")
+}
+
+/// Equity Curve Filter (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `equity` and `signals` differ in length
+/// * `InvalidParameter` if `max_dd` is not in (0, 1], or the `Reentry::Drawdown`
+///   level is negative or not below `max_dd`
+pub fn equity_filter_checked(equity: &[f64], signals: &[Position], max_dd: f64, reentry: Reentry) -> Result<Vec<Position>, QuantError> {
+    check_len(equity.len(), signals.len())?;
+    if !(max_dd > 0f64 && max_dd <= 1f64) {
+        return Err(QuantError::InvalidParameter(format!("max_dd {} is not in (0, 1]", max_dd)));
+    }
+    if let Reentry::Drawdown(level) = reentry {
+        if !(level >= 0f64 && level < max_dd) {
+            return Err(QuantError::InvalidParameter(format!("reentry drawdown {} is not in [0, max_dd)", level)));
+        }
+    }
+    let dd = drawdown(equity);
+    let mut paused = false;
+    Ok((0 .. equity.len()).map(|i| {
+        if paused {
+            paused = match reentry {
+                Reentry::NewHigh => dd[i] > 0f64,
+                Reentry::Drawdown(level) => dd[i] > level,
+            };
+        } else if dd[i] > max_dd {
+            paused = true;
+        }
+        if paused { Position::Flat } else { signals[i] }
+    }).collect())
+}
+
+// =============================================================================
+// Backtest
+// =============================================================================
+/// Round-trip trade of `backtest`
+///
+/// # Fields
+/// * `entry_index`, `exit_index` - bars whose close opened / closed the trade
+/// * `entry_price`, `exit_price` - closes of those bars
+/// * `direction` - `Long` or `Short`
+/// * `ret` - compounded return of the position over the trade, as in the equity curve
+/// * `closed` - `false` if the trade was still open at the last bar (marked to market there)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    pub entry_index: usize,
+    pub exit_index: usize,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub direction: Position,
+    pub ret: f64,
+    pub closed: bool,
+}
+
+/// Output of `backtest`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestResult {
+    pub equity: Vec<f64>,
+    pub trades: Vec<Trade>,
+}
+
+/// Backtest a position series on close prices
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `signals` - &[Position] (position decided at the close of each bar)
+///
+/// # Returns
+/// * BacktestResult
+///   * equity: starts at 1, equity[i] = equity[i-1] * (1 + signals[i-1].sign() * r[i]),
+///     r[i] = close[i] / close[i-1] - 1 (0 if the previous close is 0).
+///     The position taken at bar i earns the return of bar i+1, so there is no look-ahead
+///   * trades: a trade opens at the close of the bar where the position becomes `Long` or
+///     `Short` (from `Flat` or the opposite side) and closes at the close of the next bar
+///     where the position changes; a reversal closes one trade and opens the next on the same bar
+///   * A position still open at the last bar is closed there at its close (mark-to-market)
+///     with `closed = false`
+///   * ret = equity[exit] / equity[entry] - 1, exit / entry - 1 for a long trade
+///
+/// # Examples
+/// ```
+/// use quantauri::strategy::{backtest, Position};
+///
+/// fn main() {
+///     use Position::*;
+///     let close = vec![100f64, 110f64, 121f64, 110f64, 99f64, 104f64];
+///     let signals = vec![Long, Long, Short, Short, Flat, Long];
+///     let bt = backtest(&close, &signals);
+///
+///     // +10%, +10%, short -9.09%, short -10%, flat
+///     assert!((bt.equity[2] - 1.21).abs() < 1e-12);
+///     assert!((bt.equity[4] - 1.21 * (1f64 + 1f64 / 11f64) * 1.1).abs() < 1e-12);
+///     assert_eq!(bt.equity[5], bt.equity[4]);
+///
+///     assert_eq!(bt.trades.len(), 3);
+///     let t = bt.trades[0];
+///     assert_eq!((t.entry_index, t.exit_index, t.direction), (0, 2, Long));
+///     assert_eq!((t.entry_price, t.exit_price), (100f64, 121f64));
+///     assert!((t.ret - 0.21).abs() < 1e-12 && t.closed);
+///
+///     // Reversal on bar 2, exit to flat on bar 4
+///     assert_eq!((bt.trades[1].entry_index, bt.trades[1].exit_index), (2, 4));
+///
+///     // Still long at the end: marked to market on the last bar
+///     let last = bt.trades[2];
+///     assert_eq!((last.entry_index, last.exit_index, last.ret, last.closed), (5, 5, 0f64, false));
+/// }
+/// ```
+pub fn backtest(close: &[f64], signals: &[Position]) -> BacktestResult {
+    backtest_checked(close, signals).expect("backtest: invalid input")
+}
+
+/// Backtest a position series on close prices (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `close` and `signals` differ in length
+pub fn backtest_checked(close: &[f64], signals: &[Position]) -> Result<BacktestResult, QuantError> {
+    check_len(close.len(), signals.len())?;
+    let n = close.len();
+    let mut equity = vec![1f64; n];
+    for i in 1 .. n {
+        let r = if close[i-1] == 0f64 { 0f64 } else { close[i] / close[i-1] - 1f64 };
+        equity[i] = equity[i-1] * (1f64 + signals[i-1].sign() * r);
+    }
+
+    let mut trades = vec![];
+    let mut open: Option<usize> = None;
+    let close_trade = |entry: usize, exit: usize, closed: bool| Trade {
+        entry_index: entry,
+        exit_index: exit,
+        entry_price: close[entry],
+        exit_price: close[exit],
+        direction: signals[entry],
+        ret: equity[exit] / equity[entry] - 1f64,
+        closed,
+    };
+    for i in 0 .. n {
+        if let Some(entry) = open {
+            if signals[i] != signals[entry] {
+                trades.push(close_trade(entry, i, true));
+                open = None;
+            }
+        }
+        if open.is_none() && signals[i] != Position::Flat {
+            open = Some(i);
+        }
+    }
+    if let Some(entry) = open {
+        trades.push(close_trade(entry, n - 1, false));
+    }
+    Ok(BacktestResult { equity, trades })
+}
+
+/// Risk-based exit of `backtest_with`
+///
+/// # Variants
+/// * `StopLossPct(p)` - exit a long at close <= entry * (1 - p), a short at close >= entry * (1 + p)
+/// * `TakeProfitPct(p)` - exit a long at close >= entry * (1 + p), a short at close <= entry * (1 - p)
+/// * `AtrStop { mult, atr }` - exit a long at close <= entry - mult * atr[entry],
+///   a short at close >= entry + mult * atr[entry] (`atr` aligned with `close`;
+///   the stop is fixed at entry, no stop while `atr[entry]` is NAN)
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskExit {
+    StopLossPct(f64),
+    TakeProfitPct(f64),
+    AtrStop { mult: f64, atr: Vec<f64> },
+}
+
+impl RiskExit {
+    /// Whether `price` breaches the exit of a trade opened at `close[entry]` in `direction`
+    fn hit(&self, close: &[f64], entry: usize, direction: Position, price: f64) -> bool {
+        let e = close[entry];
+        let s = direction.sign();
+        match self {
+            RiskExit::StopLossPct(p) => s * (price - e) <= -p * e,
+            RiskExit::TakeProfitPct(p) => s * (price - e) >= p * e,
+            RiskExit::AtrStop { mult, atr } => !atr[entry].is_nan() && s * (price - e) <= -mult * atr[entry],
+        }
+    }
+}
+
+/// Backtest a position series with risk-based exits
+///
+/// # Arguments
+/// * `close` - &[f64]
+/// * `signals` - &[Position] (entry triggers)
+/// * `exits` - &[RiskExit] (the first breach of any of them closes the trade)
+///
+/// # Returns
+/// * BacktestResult (as `backtest`, on the positions after the exits)
+///   * A trade is force-closed at the close of the first bar after entry where an exit is breached
+///   * After a forced exit the position stays `Flat` until the signal leaves the stopped
+///     direction (to `Flat` or the other side), so a stop is not re-entered on the next bar
+///
+/// # Description
+/// Only closes are available, so exits are checked on closes and filled *at* the close of
+/// the breaching bar, not at the stop or target level. A gap through a stop is filled at the
+/// (worse) close, and a breach that happens intrabar but recovers by the close is missed.
+/// This is more pessimistic than a fill at the level for gaps, and more optimistic than a
+/// worst-case intrabar touch otherwise.
+///
+/// # Examples
+/// ```
+/// use quantauri::strategy::{backtest, backtest_with, Position, RiskExit};
+///
+/// fn main() {
+///     use Position::*;
+///     let close = vec![100f64, 102f64, 97f64, 95f64, 99f64, 105f64, 103f64];
+///     let signals = vec![Long, Long, Long, Long, Long, Flat, Long];
+///
+///     // Without exits the long rides to the signal flip on bar 5
+///     assert_eq!(backtest(&close, &signals).trades[0].exit_index, 5);
+///
+///     // 4% stop at 96: breached by the close of bar 3, before the signal flips
+///     let bt = backtest_with(&close, &signals, &[RiskExit::StopLossPct(0.04)]);
+///     let t = bt.trades[0];
+///     assert_eq!((t.entry_index, t.exit_index, t.exit_price), (0, 3, 95f64));
+///     assert!((t.ret + 0.05).abs() < 1e-12 && t.closed);
+///     // Flat until the signal re-triggers on bar 6
+///     assert!((bt.equity[5] - 0.95).abs() < 1e-12);
+///     assert_eq!(bt.trades[1].entry_index, 6);
+///
+///     // 2% target hit on bar 1
+///     let bt = backtest_with(&close, &signals, &[RiskExit::TakeProfitPct(0.02)]);
+///     assert_eq!(bt.trades[0].exit_index, 1);
+///
+///     // ATR stop: 100 - 2 * 2 = 96
+///     let atr = RiskExit::AtrStop { mult: 2f64, atr: vec![2f64; 7] };
+///     assert_eq!(backtest_with(&close, &signals, &[atr]).trades[0].exit_index, 3);
+/// }
+/// ```
+pub fn backtest_with(close: &[f64], signals: &[Position], exits: &[RiskExit]) -> BacktestResult {
+    backtest_with_checked(close, signals, exits).expect("backtest_with: invalid input")
+}
+
+/// Backtest a position series with risk-based exits (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `close`, `signals` or an `AtrStop` series differ in length
+/// * `InvalidParameter` if a percentage or ATR multiplier is not positive
+pub fn backtest_with_checked(close: &[f64], signals: &[Position], exits: &[RiskExit]) -> Result<BacktestResult, QuantError> {
+    check_len(close.len(), signals.len())?;
+    for exit in exits {
+        let x = match exit {
+            RiskExit::StopLossPct(p) | RiskExit::TakeProfitPct(p) => *p,
+            RiskExit::AtrStop { mult, atr } => {
+                check_len(close.len(), atr.len())?;
+                *mult
+            }
+        };
+        if x.is_nan() || x <= 0f64 {
+            return Err(QuantError::InvalidParameter(format!("{:?}: level must be positive", exit)));
+        }
+    }
+    let mut positions = vec![Position::Flat; close.len()];
+    let mut open: Option<usize> = None;
+    let mut blocked: Option<Position> = None;
+    for i in 0 .. close.len() {
+        let mut target = signals[i];
+        if blocked == Some(target) {
+            target = Position::Flat;
+        } else {
+            blocked = None;
+        }
+        if let Some(entry) = open {
+            let direction = positions[entry];
+            if target == direction && exits.iter().any(|x| x.hit(close, entry, direction, close[i])) {
+                target = Position::Flat;
+                blocked = Some(direction);
+            }
+        }
+        positions[i] = target;
+        match open {
+            Some(entry) if positions[entry] == target => (),
+            _ => open = if target == Position::Flat { None } else { Some(i) },
+        }
+    }
+    backtest_checked(close, &positions)
+}
@@ -0,0 +1,666 @@
+use crate::rolling::rolling_sum;
+use crate::error::{QuantError, check_len};
+
+// =============================================================================
+// Return-based Volatility
+// =============================================================================
+/// Normalization of a variance estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarianceKind {
+    /// Divide by n (same as `mstd`)
+    Population,
+    /// Divide by n - 1
+    Sample,
+}
+
+/// Variance of `x` over each full trailing window
+///
+/// Sums of `x - origin` (origin = first valid value) are updated incrementally;
+/// NAN values are skipped and any window containing one is NAN, so a leading
+/// NAN (e.g. from `returns`) only delays the first value. NAN for `Sample`
+/// with `window < 2`.
+fn rolling_variance(x: &[f64], window: usize, kind: VarianceKind) -> Vec<f64> {
+    let mut result = vec![f64::NAN; x.len()];
+    let ddof = match kind {
+        VarianceKind::Population => 0usize,
+        VarianceKind::Sample => 1usize,
+    };
+    if window <= ddof || window > x.len() {
+        return result;
+    }
+    let origin = x.iter().copied().find(|v| !v.is_nan()).unwrap_or(0f64);
+    let (mut s1, mut s2) = (0f64, 0f64);
+    let mut nan_count = 0usize;
+    let w = window as f64;
+    for i in 0 .. x.len() {
+        if x[i].is_nan() {
+            nan_count += 1;
+        } else {
+            let d = x[i] - origin;
+            s1 += d;
+            s2 += d * d;
+        }
+        if i >= window {
+            let old = x[i - window];
+            if old.is_nan() {
+                nan_count -= 1;
+            } else {
+                let d = old - origin;
+                s1 -= d;
+                s2 -= d * d;
+            }
+        }
+        if i + 1 >= window && nan_count == 0 {
+            let mean = s1 / w;
+            result[i] = clamp_variance((s2 / w - mean * mean) * w / (w - ddof as f64));
+        }
+    }
+    result
+}
+
+/// Realized Volatility
+///
+/// # Arguments
+/// * `returns` - &[f64] (e.g. `price::returns` or `price::log_returns`, may start with NAN)
+/// * `window` - usize
+/// * `periods_per_year` - f64 (252 for daily bars, 365 * 24 for hourly crypto)
+/// * `kind` - VarianceKind (`Population` matches `mstd`, `Sample` divides by n - 1)
+///
+/// # Returns
+/// * Vec<f64>
+///   * std(returns over each trailing window) * sqrt(periods_per_year)
+///   * NAN until a full window of valid returns is available: with a leading NAN
+///     (as from `returns`) the first value is at index `window`
+///   * NAN for any window that contains a NAN, and for `Sample` with `window < 2`
+///
+/// # Examples
+/// ```
+/// use quantauri::price::returns;
+/// use quantauri::volatility::{realized_vol, VarianceKind};
+/// use quantauri::base::{sma, mstd};
+///
+/// fn main() {
+///     let prices = vec![100f64, 101f64, 99f64, 102f64, 104f64, 103f64, 105f64];
+///     let r = returns(&prices);
+///     let w = 3;
+///     let pop = realized_vol(&r, w, 252f64, VarianceKind::Population);
+///     let sample = realized_vol(&r, w, 252f64, VarianceKind::Sample);
+///     assert!(pop[.. w].iter().all(|x| x.is_nan()));
+///
+///     // Population variant agrees with mstd on the valid region
+///     let reference = mstd(&r[1 ..], w, &sma(&r[1 ..], w));
+///     for i in w .. prices.len() {
+///         assert!((pop[i] - reference[i - 1] * 252f64.sqrt()).abs() < 1e-12);
+///         let ratio = (w as f64 / (w as f64 - 1f64)).sqrt();
+///         assert!((sample[i] - pop[i] * ratio).abs() < 1e-12);
+///     }
+/// }
+/// ```
+pub fn realized_vol(returns: &[f64], window: usize, periods_per_year: f64, kind: VarianceKind) -> Vec<f64> {
+    let scale = periods_per_year.sqrt();
+    rolling_variance(returns, window, kind).iter().map(|var| var.sqrt() * scale).collect()
+}
+
+/// RiskMetrics decay factor for daily returns
+pub const RISKMETRICS_LAMBDA: f64 = 0.94;
+
+/// Seed of the EWMA variance recursion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EwmaSeed {
+    /// First valid squared return
+    FirstSquared,
+    /// Mean of the first `n` valid squared returns (output starts at the n-th)
+    Average(usize),
+}
+
+/// EWMA (RiskMetrics) Volatility
+///
+/// # Arguments
+/// * `returns` - &[f64] (may start with NAN)
+/// * `lambda` - f64 in (0, 1) (`RISKMETRICS_LAMBDA` = 0.94 for daily data)
+/// * `annualization` - f64 (e.g. `252f64.sqrt()`)
+///
+/// # Returns
+/// * Vec<f64>
+///   * `ewma_vol_with(returns, lambda, annualization, EwmaSeed::FirstSquared)`
+pub fn ewma_vol(returns: &[f64], lambda: f64, annualization: f64) -> Vec<f64> {
+    ewma_vol_with(returns, lambda, annualization, EwmaSeed::FirstSquared)
+}
+
+/// EWMA Volatility with a configurable seed
+///
+/// # Arguments
+/// * `returns` - &[f64]
+/// * `lambda` - f64 in (0, 1)
+/// * `annualization` - f64
+/// * `seed` - EwmaSeed
+///
+/// # Returns
+/// * Vec<f64>
+///   * sigma2[i] = lambda * sigma2[i-1] + (1 - lambda) * r[i]^2, vol = sqrt(sigma2) * annualization
+///   * The seed bar holds the seed variance; earlier bars (and leading NAN returns) are NAN
+///   * A NAN return after the seed leaves the variance unchanged
+///
+/// # Examples
+/// ```
+/// use quantauri::volatility::{ewma_vol, ewma_vol_with, EwmaSeed, RISKMETRICS_LAMBDA};
+///
+/// fn main() {
+///     let r = vec![f64::NAN, 0.01, -0.02, 0.03];
+///     let l = RISKMETRICS_LAMBDA;
+///     let v = ewma_vol(&r, l, 1f64);
+///     assert!(v[0].is_nan());
+///     assert!((v[1] - 0.01).abs() < 1e-15);
+///     let s2 = l * 1e-4 + (1f64 - l) * 4e-4;
+///     assert!((v[2] - s2.sqrt()).abs() < 1e-15);
+///
+///     let v = ewma_vol_with(&r, l, 1f64, EwmaSeed::Average(2));
+///     assert!(v[1].is_nan());
+///     assert!((v[2] - (2.5e-4f64).sqrt()).abs() < 1e-15);
+///     let s3 = l * 2.5e-4 + (1f64 - l) * 9e-4;
+///     assert!((v[3] - s3.sqrt()).abs() < 1e-15);
+/// }
+/// ```
+pub fn ewma_vol_with(returns: &[f64], lambda: f64, annualization: f64, seed: EwmaSeed) -> Vec<f64> {
+    ewma_vol_checked(returns, lambda, annualization, seed).expect("ewma_vol: invalid input")
+}
+
+/// EWMA Volatility (fallible)
+///
+/// # Errors
+/// * `InvalidParameter` if `lambda` is not in (0, 1)
+/// * `InvalidPeriod` for `EwmaSeed::Average(0)`
+pub fn ewma_vol_checked(returns: &[f64], lambda: f64, annualization: f64, seed: EwmaSeed) -> Result<Vec<f64>, QuantError> {
+    if !(lambda > 0f64 && lambda < 1f64) {
+        return Err(QuantError::InvalidParameter(format!("lambda {} is not in (0, 1)", lambda)));
+    }
+    let seed_count = match seed {
+        EwmaSeed::FirstSquared => 1,
+        EwmaSeed::Average(0) => return Err(QuantError::InvalidPeriod),
+        EwmaSeed::Average(n) => n,
+    };
+    let mut result = vec![f64::NAN; returns.len()];
+    let mut var = 0f64;
+    let mut seen = 0usize;
+    for i in 0 .. returns.len() {
+        let r = returns[i];
+        if seen < seed_count {
+            if r.is_nan() {
+                continue;
+            }
+            seen += 1;
+            var += (r * r - var) / seen as f64;
+            if seen < seed_count {
+                continue;
+            }
+        } else if !r.is_nan() {
+            var = lambda * var + (1f64 - lambda) * r * r;
+        }
+        result[i] = var.sqrt() * annualization;
+    }
+    Ok(result)
+}
+
+// =============================================================================
+// Range-based Volatility
+// =============================================================================
+/// Mean of `terms` over each full trailing window (NAN for the first `window - 1`)
+fn rolling_mean_full(terms: &[f64], window: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; terms.len()];
+    if window == 0 || window > terms.len() {
+        return result;
+    }
+    let sum = rolling_sum(terms, window);
+    for i in window - 1 .. terms.len() {
+        result[i] = sum[i] / window as f64;
+    }
+    result
+}
+
+/// Clamp rounding-negative variances to 0 while keeping NAN (`f64::max` would drop it)
+fn clamp_variance(var: f64) -> f64 {
+    if var < 0f64 { 0f64 } else { var }
+}
+
+/// Annualized volatility from per-bar variance contributions
+///
+/// Shared core of the estimators in this module: the mean of `terms` over each
+/// full trailing window, square-rooted and scaled by `annualization`.
+/// First `window - 1` values are NAN.
+fn rolling_vol_from_variance(terms: &[f64], window: usize, annualization: f64) -> Vec<f64> {
+    rolling_mean_full(terms, window).iter().map(|&var| clamp_variance(var).sqrt() * annualization).collect()
+}
+
+/// Rogers-Satchell variance term of every bar,
+/// ln(h/c) ln(h/o) + ln(l/c) ln(l/o) (0 when the bar opens or closes at both extremes)
+fn rogers_satchell_terms(open: &[f64], high: &[f64], low: &[f64], close: &[f64]) -> Vec<f64> {
+    (0 .. close.len()).map(|i| {
+        (high[i] / close[i]).ln() * (high[i] / open[i]).ln() + (low[i] / close[i]).ln() * (low[i] / open[i]).ln()
+    }).collect()
+}
+
+/// Check that every price is strictly positive (log prices are taken)
+fn check_positive(v: &[f64], name: &str) -> Result<(), QuantError> {
+    match v.iter().position(|&x| x <= 0f64) {
+        Some(i) => Err(QuantError::InvalidParameter(format!("{}[{}] = {} is not positive", name, i, v[i]))),
+        None => Ok(()),
+    }
+}
+
+/// Check that OHLC columns have equal lengths and positive prices
+fn check_ohlc(open: &[f64], high: &[f64], low: &[f64], close: &[f64]) -> Result<(), QuantError> {
+    check_len(open.len(), high.len())?;
+    check_len(open.len(), low.len())?;
+    check_len(open.len(), close.len())?;
+    check_positive(open, "open")?;
+    check_positive(high, "high")?;
+    check_positive(low, "low")?;
+    check_positive(close, "close")
+}
+
+/// Parkinson Volatility
+///
+/// # Arguments
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `window` - usize
+/// * `annualization` - f64 (e.g. `252f64.sqrt()` for daily bars, `(365f64 * 24f64).sqrt()` for hourly crypto)
+///
+/// # Returns
+/// * Vec<f64>
+///   * sqrt(mean(ln(high / low)^2) / (4 ln 2)) * annualization over each trailing window
+///   * Bars with high == low contribute 0
+///   * First `window - 1` values are NAN
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::volatility::{parkinson_vol, parkinson_vol_checked};
+/// use quantauri::base::{sma, mstd};
+///
+/// fn main() {
+///     // GBM observed on 100 sub-steps per bar, sigma = 1% per bar
+///     let (bars, steps, sigma) = (2000, 100, 0.01);
+///     let z = Normal(0, 1).sample(bars * steps);
+///     let dt_sigma = sigma / (steps as f64).sqrt();
+///     let mut log_p = 0f64;
+///     let (mut high, mut low, mut close) = (vec![0f64; bars], vec![0f64; bars], vec![0f64; bars]);
+///     for b in 0 .. bars {
+///         let (mut h, mut l) = (log_p, log_p);
+///         for s in 0 .. steps {
+///             log_p += dt_sigma * z[b * steps + s];
+///             h = h.max(log_p);
+///             l = l.min(log_p);
+///         }
+///         high[b] = 100f64 * h.exp();
+///         low[b] = 100f64 * l.exp();
+///         close[b] = 100f64 * log_p.exp();
+///     }
+///     let w = 20;
+///     let park = parkinson_vol(&high, &low, w, 1f64);
+///     assert!(park[w - 2].is_nan());
+///
+///     let ret = (1 .. bars).map(|i| (close[i] / close[i-1]).ln()).collect::<Vec<f64>>();
+///     let cc = mstd(&ret, w, &sma(&ret, w));
+///
+///     let spread = |v: &[f64]| {
+///         let m = v.iter().sum::<f64>() / v.len() as f64;
+///         (v.iter().map(|x| (x - m).powi(2)).sum::<f64>() / v.len() as f64).sqrt()
+///     };
+///     let park_est = &park[w ..];
+///     let cc_est = &cc[w ..];
+///     let park_mean = park_est.iter().sum::<f64>() / park_est.len() as f64;
+///     assert!((park_mean - sigma).abs() < 0.15 * sigma);
+///     // Range-based estimator is markedly more efficient than close-to-close
+///     assert!(spread(park_est) < 0.7 * spread(cc_est));
+///
+///     let bad_low = vec![0f64; bars];
+///     assert!(parkinson_vol_checked(&high, &bad_low, w, 1f64).is_err());
+/// }
+/// ```
+pub fn parkinson_vol(high: &[f64], low: &[f64], window: usize, annualization: f64) -> Vec<f64> {
+    parkinson_vol_checked(high, low, window, annualization).expect("parkinson_vol: invalid input")
+}
+
+/// Parkinson Volatility (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if `high` and `low` differ in length
+/// * `InvalidParameter` if a high or low is zero or negative (ln(high / low) is undefined)
+pub fn parkinson_vol_checked(high: &[f64], low: &[f64], window: usize, annualization: f64) -> Result<Vec<f64>, QuantError> {
+    check_len(high.len(), low.len())?;
+    check_positive(high, "high")?;
+    check_positive(low, "low")?;
+    let factor = 1f64 / (4f64 * 2f64.ln());
+    let terms = (0 .. high.len()).map(|i| factor * (high[i] / low[i]).ln().powi(2)).collect::<Vec<f64>>();
+    Ok(rolling_vol_from_variance(&terms, window, annualization))
+}
+
+/// Garman-Klass Volatility
+///
+/// # Arguments
+/// * `open` - &[f64]
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `window` - usize
+/// * `annualization` - f64 (same convention as `parkinson_vol`)
+///
+/// # Returns
+/// * Vec<f64>
+///   * Per bar: 0.5 ln(high / low)^2 - (2 ln 2 - 1) ln(close / open)^2
+///   * sqrt(mean over each trailing window) * annualization
+///   * First `window - 1` values are NAN
+///
+/// # Examples
+/// ```
+/// use quantauri::volatility::{garman_klass_vol, garman_klass_vol_checked};
+///
+/// fn main() {
+///     let n = 10;
+///     let open = vec![100f64; n];
+///     let high = vec![100f64 * 0.02f64.exp(); n];
+///     let low = vec![100f64; n];
+///     let close = vec![100f64 * 0.01f64.exp(); n];
+///     let gk = garman_klass_vol(&open, &high, &low, &close, 5, 252f64.sqrt());
+///     let var = 0.5 * 0.02f64.powi(2) - (2f64 * 2f64.ln() - 1f64) * 0.01f64.powi(2);
+///     assert!(gk[3].is_nan());
+///     assert!((gk[9] - var.sqrt() * 252f64.sqrt()).abs() < 1e-12);
+///
+///     assert!(garman_klass_vol_checked(&open, &high, &low, &vec![-1f64; n], 5, 1f64).is_err());
+/// }
+/// ```
+pub fn garman_klass_vol(open: &[f64], high: &[f64], low: &[f64], close: &[f64], window: usize, annualization: f64) -> Vec<f64> {
+    garman_klass_vol_checked(open, high, low, close, window, annualization).expect("garman_klass_vol: invalid input")
+}
+
+/// Garman-Klass Volatility (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if the inputs differ in length
+/// * `InvalidParameter` if a price is zero or negative
+pub fn garman_klass_vol_checked(open: &[f64], high: &[f64], low: &[f64], close: &[f64], window: usize, annualization: f64) -> Result<Vec<f64>, QuantError> {
+    check_ohlc(open, high, low, close)?;
+    let k = 2f64 * 2f64.ln() - 1f64;
+    let terms = (0 .. close.len()).map(|i| {
+        0.5 * (high[i] / low[i]).ln().powi(2) - k * (close[i] / open[i]).ln().powi(2)
+    }).collect::<Vec<f64>>();
+    Ok(rolling_vol_from_variance(&terms, window, annualization))
+}
+
+/// Rogers-Satchell Volatility
+///
+/// # Arguments
+/// * `open` - &[f64]
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `window` - usize
+/// * `annualization` - f64 (same convention as `parkinson_vol`)
+///
+/// # Returns
+/// * Vec<f64>
+///   * Per bar: ln(high / close) ln(high / open) + ln(low / close) ln(low / open)
+///   * sqrt(mean over each trailing window) * annualization
+///   * Drift-independent: a bar trending straight from low to high contributes 0;
+///     bars opening at the high or low are fine (their terms go to 0)
+///   * First `window - 1` values are NAN; also the range component of `yang_zhang_vol`
+///
+/// # Examples
+/// ```
+/// use quantauri::volatility::{rogers_satchell_vol, rogers_satchell_vol_checked};
+///
+/// fn main() {
+///     // Pure drift: open at the low, close at the high
+///     let open = vec![100f64, 101f64, 102f64];
+///     let close = vec![101f64, 102f64, 103f64];
+///     let rs = rogers_satchell_vol(&open, &close, &open, &close, 2, 1f64);
+///     assert!(rs[0].is_nan());
+///     assert_eq!(rs[1 ..], [0f64, 0f64]);
+///
+///     // Open at the high, close in the middle of the range
+///     let (o, h, l, c) = (vec![110f64; 2], vec![110f64; 2], vec![100f64; 2], vec![105f64; 2]);
+///     let rs = rogers_satchell_vol(&o, &h, &l, &c, 2, 1f64);
+///     let term = (100f64 / 105f64).ln() * (100f64 / 110f64).ln();
+///     assert!((rs[1] - term.sqrt()).abs() < 1e-15);
+///
+///     assert!(rogers_satchell_vol_checked(&o, &h, &[0f64, 100f64], &c, 2, 1f64).is_err());
+/// }
+/// ```
+pub fn rogers_satchell_vol(open: &[f64], high: &[f64], low: &[f64], close: &[f64], window: usize, annualization: f64) -> Vec<f64> {
+    rogers_satchell_vol_checked(open, high, low, close, window, annualization).expect("rogers_satchell_vol: invalid input")
+}
+
+/// Rogers-Satchell Volatility (fallible)
+///
+/// # Errors
+/// * `LengthMismatch` if the inputs differ in length
+/// * `InvalidParameter` if a price is zero or negative
+pub fn rogers_satchell_vol_checked(open: &[f64], high: &[f64], low: &[f64], close: &[f64], window: usize, annualization: f64) -> Result<Vec<f64>, QuantError> {
+    check_ohlc(open, high, low, close)?;
+    Ok(rolling_vol_from_variance(&rogers_satchell_terms(open, high, low, close), window, annualization))
+}
+
+/// Yang-Zhang variance components over each trailing window
+#[derive(Debug, Clone, PartialEq)]
+pub struct YangZhangComponents {
+    /// Sample variance of ln(open[i] / close[i-1])
+    pub overnight: Vec<f64>,
+    /// Sample variance of ln(close[i] / open[i])
+    pub open_close: Vec<f64>,
+    /// Mean of ln(h/c) ln(h/o) + ln(l/c) ln(l/o)
+    pub rogers_satchell: Vec<f64>,
+    /// Weight 0.34 / (1.34 + (window + 1) / (window - 1))
+    pub k: f64,
+}
+
+/// Yang-Zhang variance components
+///
+/// # Arguments
+/// * `open` - &[f64]
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `window` - usize (at least 2)
+///
+/// # Returns
+/// * `Result<YangZhangComponents, QuantError>`
+///   * Per-bar variances over each window
+///   * Bar 0 has no previous close, so the first `window` values are NAN (warm-up `window + 1` bars)
+///
+/// # Errors
+/// * `LengthMismatch` if the inputs differ in length
+/// * `InvalidParameter` if a price is zero or negative
+/// * `InvalidPeriod` if `window < 2`
+pub fn yang_zhang_components(open: &[f64], high: &[f64], low: &[f64], close: &[f64], window: usize) -> Result<YangZhangComponents, QuantError> {
+    check_ohlc(open, high, low, close)?;
+    if window < 2 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    let n = close.len();
+    let mut overnight = vec![f64::NAN; n];
+    let mut open_close = vec![f64::NAN; n];
+    let mut rogers_satchell = vec![f64::NAN; n];
+    let w = window as f64;
+    let k = 0.34 / (1.34 + (w + 1f64) / (w - 1f64));
+    if n < 2 {
+        return Ok(YangZhangComponents { overnight, open_close, rogers_satchell, k });
+    }
+    // Components start at bar 1 so that all three share the same windows
+    let gap = (1 .. n).map(|i| (open[i] / close[i-1]).ln()).collect::<Vec<f64>>();
+    let body = (1 .. n).map(|i| (close[i] / open[i]).ln()).collect::<Vec<f64>>();
+    let range = rogers_satchell_terms(&open[1 ..], &high[1 ..], &low[1 ..], &close[1 ..]);
+    overnight[1 ..].copy_from_slice(&rolling_variance(&gap, window, VarianceKind::Sample));
+    open_close[1 ..].copy_from_slice(&rolling_variance(&body, window, VarianceKind::Sample));
+    rogers_satchell[1 ..].copy_from_slice(&rolling_mean_full(&range, window));
+    Ok(YangZhangComponents { overnight, open_close, rogers_satchell, k })
+}
+
+/// Yang-Zhang Volatility
+///
+/// # Arguments
+/// * `open` - &[f64]
+/// * `high` - &[f64]
+/// * `low` - &[f64]
+/// * `close` - &[f64]
+/// * `window` - usize (at least 2)
+/// * `annualization` - f64 (same convention as `parkinson_vol`)
+///
+/// # Returns
+/// * Vec<f64>
+///   * sqrt(overnight + k * open_close + (1 - k) * rogers_satchell) * annualization
+///     (see `yang_zhang_components`)
+///   * Robust to opening gaps; first `window` values are NAN
+///
+/// # Examples
+/// ```
+/// use peroxide::fuga::*;
+/// use quantauri::volatility::{yang_zhang_vol, yang_zhang_components, garman_klass_vol};
+///
+/// fn main() {
+///     // Gap-free GBM (open = previous close), 50 sub-steps per bar
+///     let (bars, steps, sigma) = (1000, 50, 0.01);
+///     let z = Normal(0, 1).sample(bars * steps);
+///     let dt_sigma = sigma / (steps as f64).sqrt();
+///     let mut log_p = 0f64;
+///     let mut ohlc = vec![vec![0f64; bars]; 4];
+///     for b in 0 .. bars {
+///         let (o, mut h, mut l) = (log_p, log_p, log_p);
+///         for s in 0 .. steps {
+///             log_p += dt_sigma * z[b * steps + s];
+///             h = h.max(log_p);
+///             l = l.min(log_p);
+///         }
+///         for (j, x) in [o, h, l, log_p].iter().enumerate() {
+///             ohlc[j][b] = 100f64 * x.exp();
+///         }
+///     }
+///     let (open, high, low, close) = (&ohlc[0], &ohlc[1], &ohlc[2], &ohlc[3]);
+///     let w = 30;
+///     let yz = yang_zhang_vol(open, high, low, close, w, 1f64);
+///     assert!(yz[w - 1].is_nan() && !yz[w].is_nan());
+///
+///     // Decomposition
+///     let c = yang_zhang_components(open, high, low, close, w).unwrap();
+///     for i in w .. bars {
+///         let var = c.overnight[i] + c.k * c.open_close[i] + (1f64 - c.k) * c.rogers_satchell[i];
+///         assert!((yz[i].powi(2) - var).abs() < 1e-15);
+///     }
+///
+///     // Without gaps YZ tracks Garman-Klass
+///     let gk = garman_klass_vol(open, high, low, close, w, 1f64);
+///     let mean = |v: &[f64]| v[w ..].iter().sum::<f64>() / (bars - w) as f64;
+///     assert!((mean(&yz) / mean(&gk) - 1f64).abs() < 0.1);
+/// }
+/// ```
+pub fn yang_zhang_vol(open: &[f64], high: &[f64], low: &[f64], close: &[f64], window: usize, annualization: f64) -> Vec<f64> {
+    yang_zhang_vol_checked(open, high, low, close, window, annualization).expect("yang_zhang_vol: invalid input")
+}
+
+/// Yang-Zhang Volatility (fallible)
+///
+/// # Errors
+/// * Errors of `yang_zhang_components`
+pub fn yang_zhang_vol_checked(open: &[f64], high: &[f64], low: &[f64], close: &[f64], window: usize, annualization: f64) -> Result<Vec<f64>, QuantError> {
+    let c = yang_zhang_components(open, high, low, close, window)?;
+    Ok((0 .. close.len()).map(|i| {
+        let var = c.overnight[i] + c.k * c.open_close[i] + (1f64 - c.k) * c.rogers_satchell[i];
+        clamp_variance(var).sqrt() * annualization
+    }).collect())
+}
+
+// =============================================================================
+// Position Sizing
+// =============================================================================
+/// Default leverage cap of `volatility_target_weights`
+pub const DEFAULT_MAX_LEVERAGE: f64 = 2f64;
+
+/// Volatility-targeting Weights
+///
+/// # Arguments
+/// * `returns` - &[f64] (may start with NAN)
+/// * `target_vol` - f64 (annualized, e.g. 0.1 for 10%)
+/// * `period` - usize (trailing window of the realized volatility)
+/// * `periods_per_year` - f64
+///
+/// # Returns
+/// * Vec<f64>
+///   * `volatility_target_weights_with(returns, target_vol, period, periods_per_year, DEFAULT_MAX_LEVERAGE)`
+pub fn volatility_target_weights(returns: &[f64], target_vol: f64, period: usize, periods_per_year: f64) -> Vec<f64> {
+    volatility_target_weights_with(returns, target_vol, period, periods_per_year, DEFAULT_MAX_LEVERAGE)
+}
+
+/// Volatility-targeting Weights with a configurable leverage cap
+///
+/// # Arguments
+/// * `returns` - &[f64] (may start with NAN)
+/// * `target_vol` - f64 (annualized)
+/// * `period` - usize
+/// * `periods_per_year` - f64
+/// * `max_leverage` - f64
+///
+/// # Returns
+/// * Vec<f64>
+///   * min(target_vol / realized_vol, max_leverage), with the population `realized_vol`
+///     (same normalization as `mstd`)
+///   * `max_leverage` where the trailing volatility is 0
+///   * NAN during the warm-up of `realized_vol`
+///   * The weight at bar i uses returns up to i: apply it to the return of bar i + 1
+///
+/// # Examples
+/// ```
+/// use quantauri::volatility::{volatility_target_weights, volatility_target_weights_with};
+///
+/// fn main() {
+///     // Volatility falls from ±2% to ±0.5% daily moves
+///     let mut r = (0 .. 40).map(|i| if i % 2 == 0 { 0.02 } else { -0.02 }).collect::<Vec<f64>>();
+///     r.extend((0 .. 40).map(|i| if i % 2 == 0 { 0.005 } else { -0.005 }));
+///     let w = volatility_target_weights_with(&r, 0.1, 20, 252f64, 10f64);
+///     assert!(w[.. 19].iter().all(|x| x.is_nan()));
+///
+///     // 0.1 / (0.02 * sqrt(252)) in the noisy regime, 4x that in the quiet one
+///     let loud = 0.1 / (0.02 * 252f64.sqrt());
+///     assert!((w[39] - loud).abs() < 1e-12);
+///     assert!((w[79] - 4f64 * loud).abs() < 1e-12);
+///     // Leverage rises as volatility falls
+///     assert!((40 .. 60).all(|i| w[i] >= w[i - 1] - 1e-12));
+///
+///     // Capped at the default max leverage (2x)
+///     let w = volatility_target_weights(&r, 0.5, 20, 252f64);
+///     assert_eq!(w[79], 2f64);
+///     // Zero volatility is clamped to the cap
+///     let w = volatility_target_weights(&vec![0.001; 30], 0.1, 20, 252f64);
+///     assert_eq!(w[29], 2f64);
+/// }
+/// ```
+pub fn volatility_target_weights_with(returns: &[f64], target_vol: f64, period: usize, periods_per_year: f64, max_leverage: f64) -> Vec<f64> {
+    volatility_target_weights_checked(returns, target_vol, period, periods_per_year, max_leverage)
+        .expect("volatility_target_weights: invalid input")
+}
+
+/// Volatility-targeting Weights (fallible)
+///
+/// # Errors
+/// * `InvalidPeriod` if `period` is zero
+/// * `InvalidParameter` if `target_vol` or `max_leverage` is not positive
+pub fn volatility_target_weights_checked(returns: &[f64], target_vol: f64, period: usize, periods_per_year: f64, max_leverage: f64) -> Result<Vec<f64>, QuantError> {
+    if period == 0 {
+        return Err(QuantError::InvalidPeriod);
+    }
+    if target_vol.is_nan() || target_vol <= 0f64 {
+        return Err(QuantError::InvalidParameter(format!("target_vol {} is not positive", target_vol)));
+    }
+    if max_leverage.is_nan() || max_leverage <= 0f64 {
+        return Err(QuantError::InvalidParameter(format!("max_leverage {} is not positive", max_leverage)));
+    }
+    let vol = realized_vol(returns, period, periods_per_year, VarianceKind::Population);
+    Ok(vol.iter().map(|&sigma| {
+        if sigma.is_nan() {
+            f64::NAN
+        } else if sigma == 0f64 {
+            max_leverage
+        } else {
+            (target_vol / sigma).min(max_leverage)
+        }
+    }).collect())
+}